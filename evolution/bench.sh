@@ -0,0 +1,18 @@
+#!/bin/bash
+# Run the benchmark on the tree from before species counted their food, then
+# on this tree, with the same arguments, e.g. `./bench.sh 2000 4 --strategy random`.
+set -e
+cd "$(dirname "$0")"
+
+# The counting change added the benchmark, so its parent is the old tree.
+ADDED=$(git log --format=%H --diff-filter=A -n1 -- src/bin/bench.rs)
+BEFORE=$(mktemp -d)
+trap 'git worktree remove --force "$BEFORE"' EXIT
+git worktree add -q --detach "$BEFORE" "$ADDED^"
+git show "$ADDED:./src/bin/bench.rs" > "$BEFORE/evolution/src/bin/bench.rs"
+
+echo "Before - $(git log --format='%h %s' -n1 "$ADDED^")"
+(cd "$BEFORE/evolution" && cargo run -q --release --bin bench -- "$@")
+
+echo "After - $(git log --format='%h %s' -n1 HEAD)"
+cargo run -q --release --bin bench -- "$@"
@@ -1,14 +1,16 @@
 use std::ops::{Deref, DerefMut};
 use std::fmt::{self, Debug};
-use std::io::{Read, Write, Error};
+use std::io::{ErrorKind, Read, Write};
+use std::iter;
 use std::time::Duration;
 use std::net::{ToSocketAddrs, TcpStream, TcpListener};
 use serde::{Serialize, Deserialize};
 use serde_json as json;
-use Either;
+use {Either, Error};
 
-// HACK: <refactor> We really want our own Error type.
-use std::io::ErrorKind;
+/// The number of seconds a channel waits for the other end during the
+/// handshake, and by default for each message after that.
+pub const DEFAULT_TIMEOUT_SECS: u64 = 2;
 
 /// Sending and receiving *whole* wire messages.
 pub struct Channel(String, TcpStream);
@@ -32,7 +34,7 @@ impl Channel {
         //     Ok(channel)
         // } else {
         //     let error = format!("invalid channel ack: {}", ack);
-        //     Err(Error::new(ErrorKind::Other, error))
+        //     Err(Error::invalid(error))
         // }
         Ok(channel)
     }
@@ -47,8 +49,7 @@ impl Channel {
     /// TODO: <question> Boolean to tell channel it was not good?
     pub fn accept_from_tcp_stream(stream: TcpStream) -> Result<Channel, Error> {
         let mut channel = Channel("pending".into(), stream);
-        try!(channel.set_read_timeout(Some(Duration::from_secs(2))));
-        try!(channel.set_write_timeout(Some(Duration::from_secs(2))));
+        try!(channel.set_timeout(Some(Duration::from_secs(DEFAULT_TIMEOUT_SECS))));
         let info = try!(channel.recv::<String>());
         try!(channel.send::<String>(&"ok".into()));
         channel.0 = info;
@@ -60,6 +61,14 @@ impl Channel {
         &self.0
     }
 
+    /// Sets how long to wait on each read or write before failing with
+    /// `Error::Timeout`, or `None` to wait forever.
+    pub fn set_timeout(&mut self, timeout: Option<Duration>) -> Result<(), Error> {
+        try!(self.1.set_read_timeout(timeout));
+        try!(self.1.set_write_timeout(timeout));
+        Ok(())
+    }
+
     pub fn call<D, C>(&mut self, domain: &D) -> Result<C, Error>
         where D: Serialize + Debug,
               C: Deserialize + Debug,
@@ -84,18 +93,37 @@ impl Channel {
     pub fn send<T: Serialize + Debug>(&mut self, message: &T) -> Result<(), Error> {
         try!(json::to_writer(&mut self.1, message).map_err(|e| {
             warn!("error sending: {}", e);
-            Error::new(ErrorKind::Other, e)
+            Error::from(e)
         }));
         try!(self.1.write(b"\n"));
         trace!("{:?}.send<T>", self);
         Ok(())
     }
 
+    /// Receive a message, failing with `Error::Closed` if the other end
+    /// closed the connection before sending any of it.
     pub fn recv<T: Deserialize + Debug>(&mut self) -> Result<T, Error> {
-        let mut de = json::Deserializer::new(try!(self.1.try_clone()).bytes());
+        let mut bytes = try!(self.1.try_clone()).bytes();
+        // Skip the whitespace between messages, to tell a connection closed
+        // between messages apart from one closed part way through one. A
+        // peer closing with some of our whitespace unread resets the
+        // connection, instead of ending it.
+        let first;
+        loop {
+            match bytes.next() {
+                Some(Ok(b' ')) | Some(Ok(b'\n')) | Some(Ok(b'\r')) | Some(Ok(b'\t')) => {},
+                Some(Err(ref e)) if e.kind() == ErrorKind::ConnectionReset => return Err(Error::Closed),
+                Some(byte) => {
+                    first = try!(byte);
+                    break
+                },
+                None => return Err(Error::Closed),
+            }
+        }
+        let mut de = json::Deserializer::new(iter::once(Ok(first)).chain(bytes));
         let message = try!((Deserialize::deserialize(&mut de)).map_err(|e| {
             warn!("error receiving: {}", e);
-            Error::new(ErrorKind::Other, e)
+            Error::from(e)
         }));
         // HACK: The following *should* work too... wtf serde.
         // let message = try!(json::from_reader(&mut self.1).map_err(|e| {
@@ -114,7 +142,7 @@ impl Channel {
     {
         let value = try!(self.recv());
         let either = Either::<T, U>::from_value(value);
-        either.map_err(|_| Error::new(ErrorKind::InvalidInput, "error creating either type"))
+        either.map_err(|_| Error::invalid("error creating either type"))
     }
 }
 
@@ -402,6 +430,36 @@ mod tests {
         }).join().unwrap();
     }
 
+    #[test]
+    fn recv_timeout() {
+        thread::spawn(move || {
+            let _channel = accept_channel();
+            thread::sleep(Duration::from_millis(500));
+        });
+        thread::sleep(Duration::from_millis(10));
+        let mut channel = connect_channel();
+        channel.set_timeout(Some(Duration::from_millis(50))).unwrap();
+        match channel.recv::<bool>() {
+            Err(Error::Timeout) => {},
+            r => panic!("expected a timeout, got {:?}", r),
+        }
+    }
+
+    #[test]
+    fn recv_closed() {
+        thread::spawn(move || {
+            let mut channel = accept_channel();
+            channel.send(&true).unwrap();
+        });
+        thread::sleep(Duration::from_millis(10));
+        let mut channel = connect_channel();
+        assert_eq!(true, channel.recv::<bool>().unwrap());
+        match channel.recv::<bool>() {
+            Err(Error::Closed) => {},
+            r => panic!("expected the connection closed, got {:?}", r),
+        }
+    }
+
     #[test]
     fn remote_call() {
         thread::spawn(move || {
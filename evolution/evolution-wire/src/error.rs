@@ -0,0 +1,93 @@
+use std::error;
+use std::fmt;
+use std::io::{self, ErrorKind};
+use serde_json as json;
+
+/// Everything that can go wrong getting data off of, or onto the wire.
+#[derive(Debug)]
+pub enum Error {
+    /// The other end of the wire took too long to respond.
+    Timeout,
+    /// The other end closed the connection between messages.
+    Closed,
+    /// Reading or writing the underlying stream failed.
+    Io(io::Error),
+    /// The message wasn't valid JSON for the expected wire type.
+    Json(String),
+    /// The wire data was well formed, but doesn't make sense, the string
+    /// says why.
+    Invalid(String),
+}
+
+impl Error {
+    /// Create an `Invalid` error with the given reason.
+    pub fn invalid<S: Into<String>>(reason: S) -> Self {
+        Error::Invalid(reason.into())
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Timeout => write!(f, "timed out"),
+            Error::Closed => write!(f, "connection closed"),
+            Error::Io(ref e) => write!(f, "io error: {}", e),
+            Error::Json(ref e) => write!(f, "json error: {}", e),
+            Error::Invalid(ref reason) => write!(f, "invalid wire data: {}", reason),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn description(&self) -> &str {
+        match *self {
+            Error::Timeout => "timed out",
+            Error::Closed => "connection closed",
+            Error::Io(ref e) => e.description(),
+            Error::Json(_) => "json error",
+            Error::Invalid(_) => "invalid wire data",
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        match error.kind() {
+            // A read or write timeout shows up as `WouldBlock` on unix, and
+            // `TimedOut` on windows.
+            ErrorKind::WouldBlock | ErrorKind::TimedOut => Error::Timeout,
+            _ => Error::Io(error),
+        }
+    }
+}
+
+impl From<json::Error> for Error {
+    fn from(error: json::Error) -> Self {
+        match error {
+            json::Error::Io(e) => Error::from(e),
+            e => Error::Json(format!("{}", e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+    use super::*;
+
+    #[test]
+    fn io_timeouts() {
+        let would_block = io::Error::new(io::ErrorKind::WouldBlock, "");
+        let timed_out = io::Error::new(io::ErrorKind::TimedOut, "");
+
+        assert!(match Error::from(would_block) { Error::Timeout => true, _ => false });
+        assert!(match Error::from(timed_out) { Error::Timeout => true, _ => false });
+    }
+
+    #[test]
+    fn io_other() {
+        let error = io::Error::new(io::ErrorKind::ConnectionReset, "");
+
+        assert!(match Error::from(error) { Error::Io(_) => true, _ => false });
+    }
+}
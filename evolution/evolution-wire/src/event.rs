@@ -0,0 +1,182 @@
+use serde::{Serialize, Serializer, Deserialize, Deserializer, Error};
+use serde::de::{SeqVisitor, Visitor};
+use serde::ser::impls::{TupleVisitor2, TupleVisitor3, TupleVisitor4, TupleVisitor7};
+use super::*;
+
+/// A list of events, in the order they happened.
+pub type LOE = Vec<Event>;
+
+/// Something which happened during a game.
+///
+/// Each event is an array tagged with the kind of event.
+///
+/// ```text
+/// ["start", Seed, [NaturalPlus, ...], Rules]
+/// ["deal", [[NaturalPlus, LOC], ...]]
+/// ["action", NaturalPlus, Action4 | null]
+/// ["reveal", LOC, Natural]
+/// ["feed", NaturalPlus, FeedChoice | null]
+/// ["skip", NaturalPlus]
+/// ["attack", NaturalPlus, Natural, NaturalPlus, Natural, Boolean, Boolean]
+/// ["eject", NaturalPlus]
+/// ["bag", [[NaturalPlus, Natural], ...]]
+/// ```
+///
+/// A `null` choice means the player failed to give a valid choice. An attack
+/// lists the attacking player, attacker, defending player and defender,
+/// followed by whether the attacker and defender went extinct. A start
+/// without rules was played by the course rules.
+#[derive(Debug)]
+pub enum Event {
+    Start(u64, Vec<NaturalPlus>, Rules),
+    Deal(Vec<(NaturalPlus, LOC)>),
+    Action(NaturalPlus, Option<remote::Action4>),
+    Reveal(LOC, Natural),
+    Feed(NaturalPlus, Option<FeedChoice>),
+    Skip(NaturalPlus),
+    Attack(NaturalPlus, Natural, NaturalPlus, Natural, bool, bool),
+    Eject(NaturalPlus),
+    Bag(Vec<(NaturalPlus, Natural)>),
+}
+
+impl Serialize for Event {
+    fn serialize<S>(&self, serializer: &mut S) -> Result<(), S::Error>
+        where S: Serializer
+    {
+        match *self {
+            Event::Start(seed, ref players, ref rules) => {
+                let tuple = ("start", seed, players, rules);
+                serializer.serialize_tuple(TupleVisitor4::new(&tuple))
+            },
+            Event::Deal(ref hands) => {
+                let tuple = ("deal", hands);
+                serializer.serialize_tuple(TupleVisitor2::new(&tuple))
+            },
+            Event::Action(player, ref choice) => {
+                let tuple = ("action", player, choice);
+                serializer.serialize_tuple(TupleVisitor3::new(&tuple))
+            },
+            Event::Reveal(ref cards, food) => {
+                let tuple = ("reveal", cards, food);
+                serializer.serialize_tuple(TupleVisitor3::new(&tuple))
+            },
+            Event::Feed(player, ref choice) => {
+                let tuple = ("feed", player, choice);
+                serializer.serialize_tuple(TupleVisitor3::new(&tuple))
+            },
+            Event::Skip(player) => {
+                let tuple = ("skip", player);
+                serializer.serialize_tuple(TupleVisitor2::new(&tuple))
+            },
+            Event::Attack(player, attacker, target, defender, attacker_extinct, defender_extinct) => {
+                let tuple = ("attack", player, attacker, target, defender, attacker_extinct, defender_extinct);
+                serializer.serialize_tuple(TupleVisitor7::new(&tuple))
+            },
+            Event::Eject(player) => {
+                let tuple = ("eject", player);
+                serializer.serialize_tuple(TupleVisitor2::new(&tuple))
+            },
+            Event::Bag(ref extinctions) => {
+                let tuple = ("bag", extinctions);
+                serializer.serialize_tuple(TupleVisitor2::new(&tuple))
+            },
+        }
+    }
+}
+
+impl Deserialize for Event {
+    fn deserialize<D>(deserializer: &mut D) -> Result<Self, D::Error>
+        where D: Deserializer
+    {
+        deserializer.deserialize_seq(EventVisitor)
+    }
+}
+
+#[derive(Debug)]
+struct EventVisitor;
+
+impl Visitor for EventVisitor {
+    type Value = Event;
+
+    fn visit_seq<V>(&mut self, mut visitor: V) -> Result<Self::Value, V::Error>
+        where V: SeqVisitor
+    {
+        let tag: Option<String> = try!(visitor.visit());
+        let event = match tag.as_ref().map(|t| t.as_str()) {
+            Some("start") => {
+                let seed = try!(visitor.visit());
+                let players = try!(visitor.visit());
+                let rules: Option<Rules> = try!(visitor.visit());
+                match (seed, players) {
+                    (Some(seed), Some(players)) => Event::Start(seed, players, rules.unwrap_or(Rules::default())),
+                    _ => return Err(Error::custom("invalid start event")),
+                }
+            },
+            Some("deal") => {
+                match try!(visitor.visit()) {
+                    Some(hands) => Event::Deal(hands),
+                    _ => return Err(Error::custom("invalid deal event")),
+                }
+            },
+            Some("action") => {
+                match (try!(visitor.visit()), try!(visitor.visit())) {
+                    (Some(player), Some(choice)) => Event::Action(player, choice),
+                    _ => return Err(Error::custom("invalid action event")),
+                }
+            },
+            Some("reveal") => {
+                match (try!(visitor.visit()), try!(visitor.visit())) {
+                    (Some(cards), Some(food)) => Event::Reveal(cards, food),
+                    _ => return Err(Error::custom("invalid reveal event")),
+                }
+            },
+            Some("feed") => {
+                match (try!(visitor.visit()), try!(visitor.visit())) {
+                    (Some(player), Some(choice)) => Event::Feed(player, choice),
+                    _ => return Err(Error::custom("invalid feed event")),
+                }
+            },
+            Some("skip") => {
+                match try!(visitor.visit()) {
+                    Some(player) => Event::Skip(player),
+                    _ => return Err(Error::custom("invalid skip event")),
+                }
+            },
+            Some("attack") => {
+                let player = try!(visitor.visit());
+                let attacker = try!(visitor.visit());
+                let target = try!(visitor.visit());
+                let defender = try!(visitor.visit());
+                let attacker_extinct = try!(visitor.visit());
+                let defender_extinct = try!(visitor.visit());
+                match (player, attacker, target, defender, attacker_extinct, defender_extinct) {
+                    (Some(player),
+                     Some(attacker),
+                     Some(target),
+                     Some(defender),
+                     Some(attacker_extinct),
+                     Some(defender_extinct)) => {
+                        Event::Attack(player, attacker, target, defender, attacker_extinct, defender_extinct)
+                    },
+                    _ => return Err(Error::custom("invalid attack event")),
+                }
+            },
+            Some("eject") => {
+                match try!(visitor.visit()) {
+                    Some(player) => Event::Eject(player),
+                    _ => return Err(Error::custom("invalid eject event")),
+                }
+            },
+            Some("bag") => {
+                match try!(visitor.visit()) {
+                    Some(extinctions) => Event::Bag(extinctions),
+                    _ => return Err(Error::custom("invalid bag event")),
+                }
+            },
+            _ => return Err(Error::custom("invalid event")),
+        };
+        try!(visitor.end());
+
+        Ok(event)
+    }
+}
@@ -12,13 +12,13 @@ extern crate serde_json;
 /// application data formats. Here the wire type `W` can be much looser
 /// than the type this trait is implemented for. In the cases where the
 /// data from the wire is invalid the `from_wire` function will return
-/// an `Err`.
+/// an `Err` saying why.
 pub trait FromWire<W: serde::Deserialize>: Sized {
-    fn from_wire(wire: W) -> Result<Self, ()>;
+    fn from_wire(wire: W) -> Result<Self, Error>;
 }
 
 impl<T: FromWire<U>, U: serde::Deserialize> FromWire<Vec<U>> for Vec<T> {
-    fn from_wire(wire: Vec<U>) -> Result<Self, ()> {
+    fn from_wire(wire: Vec<U>) -> Result<Self, Error> {
         let mut vec = Vec::new();
         for element in wire.into_iter() {
             vec.push(try!(T::from_wire(element)));
@@ -53,6 +53,10 @@ macro_rules! assert_json {
     };
 }
 
+/// Errors getting data off of, or onto the wire.
+pub mod error;
+pub use self::error::Error;
+
 /// TODO: <doc>
 pub mod channel;
 pub use self::channel::Channel;
@@ -82,6 +86,9 @@ pub use self::configuration::Configuration;
 mod either;
 pub use self::either::Either;
 
+mod event;
+pub use self::event::{LOE, Event};
+
 mod feed_choice;
 pub use self::feed_choice::FeedChoice;
 
@@ -118,9 +125,16 @@ pub use self::player::{LOP, Player};
 mod rt;
 pub use self::rt::RT;
 
+mod rules;
+pub use self::rules::{Rules, Mode};
+
 mod situation;
 pub use self::situation::Situation;
 
+/// Snapshots of whole games, and the parts of a game only they hold.
+pub mod snapshot;
+pub use self::snapshot::Snapshot;
+
 mod species_card;
 pub use self::species_card::{LOC, SpeciesCard};
 
@@ -0,0 +1,143 @@
+use std::collections::BTreeMap;
+use serde::{Serialize, Serializer, Deserialize, Deserializer, Error};
+use serde_json::{self as json, Value};
+use super::*;
+
+/// House rules for a game.
+///
+/// Rules are an object, where every key is optional, and a missing key
+/// leaves the rule of the `mode` in place.
+///
+/// ```text
+/// {
+///     "mode": Mode,
+///     "players": [Natural, Natural],
+///     "population": Natural,
+///     "body_size": Natural,
+///     "traits": Natural,
+///     "carnivore_cards": [Integer, Integer],
+///     "trait_cards": [Integer, Integer],
+///     "expansion_cards": [Integer, Integer],
+///     "cards_per_round": Natural,
+///     "cards_per_species": Natural,
+///     "cards_per_extinction": Natural,
+///     "rounds": NaturalPlus
+/// }
+/// ```
+///
+/// `players` is the fewest and most players, the card keys are the lowest
+/// and highest food value of those cards, inclusive, and `rounds` is the
+/// most rounds to play.
+#[derive(Debug, Default, PartialEq)]
+pub struct Rules {
+    pub mode: Option<Mode>,
+    pub players: Option<(Natural, Natural)>,
+    pub population: Option<Natural>,
+    pub body_size: Option<Natural>,
+    pub traits: Option<Natural>,
+    pub carnivore_cards: Option<(Integer, Integer)>,
+    pub trait_cards: Option<(Integer, Integer)>,
+    pub expansion_cards: Option<(Integer, Integer)>,
+    pub cards_per_round: Option<Natural>,
+    pub cards_per_species: Option<Natural>,
+    pub cards_per_extinction: Option<Natural>,
+    pub rounds: Option<NaturalPlus>,
+}
+
+impl Serialize for Rules {
+    fn serialize<S>(&self, serializer: &mut S) -> Result<(), S::Error>
+        where S: Serializer
+    {
+        let mut map = BTreeMap::new();
+        insert(&mut map, "mode", &self.mode);
+        insert(&mut map, "players", &self.players);
+        insert(&mut map, "population", &self.population);
+        insert(&mut map, "body_size", &self.body_size);
+        insert(&mut map, "traits", &self.traits);
+        insert(&mut map, "carnivore_cards", &self.carnivore_cards);
+        insert(&mut map, "trait_cards", &self.trait_cards);
+        insert(&mut map, "expansion_cards", &self.expansion_cards);
+        insert(&mut map, "cards_per_round", &self.cards_per_round);
+        insert(&mut map, "cards_per_species", &self.cards_per_species);
+        insert(&mut map, "cards_per_extinction", &self.cards_per_extinction);
+        insert(&mut map, "rounds", &self.rounds);
+        Value::Object(map).serialize(serializer)
+    }
+}
+
+impl Deserialize for Rules {
+    fn deserialize<D>(deserializer: &mut D) -> Result<Self, D::Error>
+        where D: Deserializer
+    {
+        let mut map = match try!(Deserialize::deserialize(deserializer)) {
+            Value::Object(map) => map,
+            _ => return Err(Error::custom("rules must be an object")),
+        };
+        let rules = Rules {
+            mode: try!(remove(&mut map, "mode")),
+            players: try!(remove(&mut map, "players")),
+            population: try!(remove(&mut map, "population")),
+            body_size: try!(remove(&mut map, "body_size")),
+            traits: try!(remove(&mut map, "traits")),
+            carnivore_cards: try!(remove(&mut map, "carnivore_cards")),
+            trait_cards: try!(remove(&mut map, "trait_cards")),
+            expansion_cards: try!(remove(&mut map, "expansion_cards")),
+            cards_per_round: try!(remove(&mut map, "cards_per_round")),
+            cards_per_species: try!(remove(&mut map, "cards_per_species")),
+            cards_per_extinction: try!(remove(&mut map, "cards_per_extinction")),
+            rounds: try!(remove(&mut map, "rounds")),
+        };
+        // Catch misspelled rules, rather than quietly playing the official ones.
+        if let Some(key) = map.keys().next() {
+            return Err(Error::custom(format!("unknown rule `{}`", key)))
+        }
+        Ok(rules)
+    }
+}
+
+/// The rule book a game follows, either `"course"` or `"official"`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Mode {
+    Course,
+    Official,
+}
+
+impl Serialize for Mode {
+    fn serialize<S>(&self, serializer: &mut S) -> Result<(), S::Error>
+        where S: Serializer
+    {
+        match *self {
+            Mode::Course   => serializer.serialize_str("course"),
+            Mode::Official => serializer.serialize_str("official"),
+        }
+    }
+}
+
+impl Deserialize for Mode {
+    fn deserialize<D>(deserializer: &mut D) -> Result<Self, D::Error> where D: Deserializer {
+        match &try!(String::deserialize(deserializer))[..] {
+            "course"   => Ok(Mode::Course),
+            "official" => Ok(Mode::Official),
+            _ => Err(Error::custom("unknown rules mode")),
+        }
+    }
+}
+
+/// Insert the rule into the map, if it's given.
+fn insert<T: Serialize>(map: &mut BTreeMap<String, Value>, key: &str, rule: &Option<T>) {
+    if let Some(ref rule) = *rule {
+        map.insert(key.to_string(), json::to_value(rule));
+    }
+}
+
+/// Remove the rule from the map, if it's given.
+fn remove<T: Deserialize, E: Error>(map: &mut BTreeMap<String, Value>, key: &str) -> Result<Option<T>, E> {
+    match map.remove(key) {
+        Some(value) => {
+            json::from_value(value).map(Some).map_err(|_| {
+                E::custom(format!("invalid value for rule `{}`", key))
+            })
+        },
+        None => Ok(None),
+    }
+}
@@ -0,0 +1,431 @@
+use std::collections::BTreeMap;
+use serde::{Serialize, Serializer, Deserialize, Deserializer, Error};
+use serde_json::{self as json, Value};
+use super::*;
+
+/// A whole game, which can be saved and resumed at any stage of a round.
+///
+/// A snapshot is an object, where the last three keys are only given for
+/// games with choosers, and left out for a game's state alone.
+///
+/// ```text
+/// {
+///     "rules": Rules,
+///     "seed": Natural | null,
+///     "round": Natural,
+///     "stage": Stage,
+///     "players": [Player, ...],
+///     "current_player": Natural | null,
+///     "skipped": [Natural, ...],
+///     "food": Natural,
+///     "board_cards": [Card, ...] | null,
+///     "deck": [Card, ...],
+///     "events": LOE,
+///     "kicked": [Kick, ...],
+///     "budget": Budget,
+///     "clock": [[NaturalPlus, Natural], ...],
+///     "info": [[NaturalPlus, String], ...]
+/// }
+/// ```
+///
+/// The players are in round order, and the current and skipped players are
+/// indices into them. The clock is the nanoseconds each player has used,
+/// and the info is what each player's chooser said about itself.
+#[derive(Debug)]
+pub struct Snapshot {
+    pub rules: Rules,
+    pub seed: Option<Natural>,
+    pub round: Natural,
+    pub stage: Stage,
+    pub players: Vec<Player>,
+    pub current_player: Option<Natural>,
+    pub skipped: Vec<Natural>,
+    pub food: Natural,
+    pub board_cards: Option<Vec<Card>>,
+    pub deck: Vec<Card>,
+    pub events: LOE,
+    pub kicked: Vec<Kick>,
+    pub budget: Option<Budget>,
+    pub clock: Option<Vec<(NaturalPlus, Natural)>>,
+    pub info: Option<Vec<(NaturalPlus, String)>>,
+}
+
+impl Serialize for Snapshot {
+    fn serialize<S>(&self, serializer: &mut S) -> Result<(), S::Error>
+        where S: Serializer
+    {
+        let mut map = BTreeMap::new();
+        insert(&mut map, "rules", &self.rules);
+        insert(&mut map, "seed", &self.seed);
+        insert(&mut map, "round", &self.round);
+        insert(&mut map, "stage", &self.stage);
+        insert(&mut map, "players", &self.players);
+        insert(&mut map, "current_player", &self.current_player);
+        insert(&mut map, "skipped", &self.skipped);
+        insert(&mut map, "food", &self.food);
+        insert(&mut map, "board_cards", &self.board_cards);
+        insert(&mut map, "deck", &self.deck);
+        insert(&mut map, "events", &self.events);
+        insert(&mut map, "kicked", &self.kicked);
+        if let Some(ref budget) = self.budget {
+            insert(&mut map, "budget", budget);
+        }
+        if let Some(ref clock) = self.clock {
+            insert(&mut map, "clock", clock);
+        }
+        if let Some(ref info) = self.info {
+            insert(&mut map, "info", info);
+        }
+        Value::Object(map).serialize(serializer)
+    }
+}
+
+impl Deserialize for Snapshot {
+    fn deserialize<D>(deserializer: &mut D) -> Result<Self, D::Error>
+        where D: Deserializer
+    {
+        let mut map = try!(object(try!(Deserialize::deserialize(deserializer)), "snapshot"));
+        let snapshot = Snapshot {
+            rules: try!(remove(&mut map, "snapshot", "rules")),
+            seed: try!(remove(&mut map, "snapshot", "seed")),
+            round: try!(remove(&mut map, "snapshot", "round")),
+            stage: try!(remove(&mut map, "snapshot", "stage")),
+            players: try!(remove(&mut map, "snapshot", "players")),
+            current_player: try!(remove(&mut map, "snapshot", "current_player")),
+            skipped: try!(remove(&mut map, "snapshot", "skipped")),
+            food: try!(remove(&mut map, "snapshot", "food")),
+            board_cards: try!(remove(&mut map, "snapshot", "board_cards")),
+            deck: try!(remove(&mut map, "snapshot", "deck")),
+            events: try!(remove(&mut map, "snapshot", "events")),
+            kicked: try!(remove(&mut map, "snapshot", "kicked")),
+            budget: try!(remove_optional(&mut map, "snapshot", "budget")),
+            clock: try!(remove_optional(&mut map, "snapshot", "clock")),
+            info: try!(remove_optional(&mut map, "snapshot", "info")),
+        };
+        try!(end(map, "snapshot"));
+        Ok(snapshot)
+    }
+}
+
+/// The step a game will take next.
+///
+/// ```text
+/// "deal" | ["action", Natural] | "reveal" | "feed" | "bag"
+/// ```
+///
+/// The action stage counts the players left to choose an action.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Stage {
+    Deal,
+    Action(Natural),
+    Reveal,
+    Feed,
+    Bag,
+}
+
+impl Serialize for Stage {
+    fn serialize<S>(&self, serializer: &mut S) -> Result<(), S::Error>
+        where S: Serializer
+    {
+        match *self {
+            Stage::Deal => serializer.serialize_str("deal"),
+            Stage::Action(left) => ("action", left).serialize(serializer),
+            Stage::Reveal => serializer.serialize_str("reveal"),
+            Stage::Feed => serializer.serialize_str("feed"),
+            Stage::Bag => serializer.serialize_str("bag"),
+        }
+    }
+}
+
+impl Deserialize for Stage {
+    fn deserialize<D>(deserializer: &mut D) -> Result<Self, D::Error>
+        where D: Deserializer
+    {
+        let value: Value = try!(Deserialize::deserialize(deserializer));
+        if let Ok((name, left)) = json::from_value::<(String, Natural)>(value.clone()) {
+            if name == "action" {
+                return Ok(Stage::Action(left))
+            }
+        }
+        match value.as_string() {
+            Some("deal") => Ok(Stage::Deal),
+            Some("reveal") => Ok(Stage::Reveal),
+            Some("feed") => Ok(Stage::Feed),
+            Some("bag") => Ok(Stage::Bag),
+            _ => Err(Error::custom("invalid stage")),
+        }
+    }
+}
+
+/// A card, written like a `SpeciesCard` without its bounds on food values,
+/// which the rules can change.
+pub type Card = (Integer, Trait);
+
+/// A player, with the food in their bag, their hand and their species.
+///
+/// ```text
+/// {
+///     "id": NaturalPlus,
+///     "bag": Natural,
+///     "hand": [Card, ...],
+///     "domain": [Species, ...]
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Player {
+    pub id: NaturalPlus,
+    pub bag: Natural,
+    pub hand: Vec<Card>,
+    pub domain: Vec<Species>,
+}
+
+impl Serialize for Player {
+    fn serialize<S>(&self, serializer: &mut S) -> Result<(), S::Error>
+        where S: Serializer
+    {
+        let mut map = BTreeMap::new();
+        insert(&mut map, "id", &self.id);
+        insert(&mut map, "bag", &self.bag);
+        insert(&mut map, "hand", &self.hand);
+        insert(&mut map, "domain", &self.domain);
+        Value::Object(map).serialize(serializer)
+    }
+}
+
+impl Deserialize for Player {
+    fn deserialize<D>(deserializer: &mut D) -> Result<Self, D::Error>
+        where D: Deserializer
+    {
+        let mut map = try!(object(try!(Deserialize::deserialize(deserializer)), "player"));
+        let player = Player {
+            id: try!(remove(&mut map, "player", "id")),
+            bag: try!(remove(&mut map, "player", "bag")),
+            hand: try!(remove(&mut map, "player", "hand")),
+            domain: try!(remove(&mut map, "player", "domain")),
+        };
+        try!(end(map, "player"));
+        Ok(player)
+    }
+}
+
+/// A species, written without the bounds of a wire `Species`, which the
+/// rules can change.
+///
+/// ```text
+/// {
+///     "population": Natural,
+///     "body_size": Natural,
+///     "traits": [Trait, ...],
+///     "food": Natural,
+///     "fat": Natural
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Species {
+    pub population: Natural,
+    pub body_size: Natural,
+    pub traits: Vec<Trait>,
+    pub food: Natural,
+    pub fat: Natural,
+}
+
+impl Serialize for Species {
+    fn serialize<S>(&self, serializer: &mut S) -> Result<(), S::Error>
+        where S: Serializer
+    {
+        let mut map = BTreeMap::new();
+        insert(&mut map, "population", &self.population);
+        insert(&mut map, "body_size", &self.body_size);
+        insert(&mut map, "traits", &self.traits);
+        insert(&mut map, "food", &self.food);
+        insert(&mut map, "fat", &self.fat);
+        Value::Object(map).serialize(serializer)
+    }
+}
+
+impl Deserialize for Species {
+    fn deserialize<D>(deserializer: &mut D) -> Result<Self, D::Error>
+        where D: Deserializer
+    {
+        let mut map = try!(object(try!(Deserialize::deserialize(deserializer)), "species"));
+        let species = Species {
+            population: try!(remove(&mut map, "species", "population")),
+            body_size: try!(remove(&mut map, "species", "body_size")),
+            traits: try!(remove(&mut map, "species", "traits")),
+            food: try!(remove(&mut map, "species", "food")),
+            fat: try!(remove(&mut map, "species", "fat")),
+        };
+        try!(end(map, "species"));
+        Ok(species)
+    }
+}
+
+/// A player ejected from the game, with the round, the offense and the
+/// reason they were ejected, and their score at the time.
+///
+/// ```text
+/// {
+///     "id": NaturalPlus,
+///     "round": Natural,
+///     "offense": Offense,
+///     "reason": String,
+///     "score": Natural
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Kick {
+    pub id: NaturalPlus,
+    pub round: Natural,
+    pub offense: Offense,
+    pub reason: String,
+    pub score: Natural,
+}
+
+impl Serialize for Kick {
+    fn serialize<S>(&self, serializer: &mut S) -> Result<(), S::Error>
+        where S: Serializer
+    {
+        let mut map = BTreeMap::new();
+        insert(&mut map, "id", &self.id);
+        insert(&mut map, "round", &self.round);
+        insert(&mut map, "offense", &self.offense);
+        insert(&mut map, "reason", &self.reason);
+        insert(&mut map, "score", &self.score);
+        Value::Object(map).serialize(serializer)
+    }
+}
+
+impl Deserialize for Kick {
+    fn deserialize<D>(deserializer: &mut D) -> Result<Self, D::Error>
+        where D: Deserializer
+    {
+        let mut map = try!(object(try!(Deserialize::deserialize(deserializer)), "kick"));
+        let kick = Kick {
+            id: try!(remove(&mut map, "kick", "id")),
+            round: try!(remove(&mut map, "kick", "round")),
+            offense: try!(remove(&mut map, "kick", "offense")),
+            reason: try!(remove(&mut map, "kick", "reason")),
+            score: try!(remove(&mut map, "kick", "score")),
+        };
+        try!(end(map, "kick"));
+        Ok(kick)
+    }
+}
+
+/// The step a player was ejected during, with the choice they made, if it
+/// was a valid choice at all.
+///
+/// ```text
+/// ["action", Action4 | null] | ["feed", FeedChoice | null]
+/// ```
+#[derive(Debug)]
+pub enum Offense {
+    Action(Option<remote::Action4>),
+    Feed(Option<FeedChoice>),
+}
+
+impl Serialize for Offense {
+    fn serialize<S>(&self, serializer: &mut S) -> Result<(), S::Error>
+        where S: Serializer
+    {
+        match *self {
+            Offense::Action(ref choice) => ("action", choice).serialize(serializer),
+            Offense::Feed(ref choice) => ("feed", choice).serialize(serializer),
+        }
+    }
+}
+
+impl Deserialize for Offense {
+    fn deserialize<D>(deserializer: &mut D) -> Result<Self, D::Error>
+        where D: Deserializer
+    {
+        let (step, choice) = try!(<(String, Value)>::deserialize(deserializer));
+        let invalid = |_| Error::custom(format!("invalid {} offense", step));
+        match &step[..] {
+            "action" => json::from_value(choice).map(Offense::Action).map_err(&invalid),
+            "feed" => json::from_value(choice).map(Offense::Feed).map_err(&invalid),
+            _ => Err(Error::custom("invalid offense")),
+        }
+    }
+}
+
+/// The time limits of a game, in nanoseconds, where `null` is no limit.
+///
+/// ```text
+/// {
+///     "per_choose": Natural | null,
+///     "per_game": Natural | null
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Budget {
+    pub per_choose: Option<Natural>,
+    pub per_game: Option<Natural>,
+}
+
+impl Serialize for Budget {
+    fn serialize<S>(&self, serializer: &mut S) -> Result<(), S::Error>
+        where S: Serializer
+    {
+        let mut map = BTreeMap::new();
+        insert(&mut map, "per_choose", &self.per_choose);
+        insert(&mut map, "per_game", &self.per_game);
+        Value::Object(map).serialize(serializer)
+    }
+}
+
+impl Deserialize for Budget {
+    fn deserialize<D>(deserializer: &mut D) -> Result<Self, D::Error>
+        where D: Deserializer
+    {
+        let mut map = try!(object(try!(Deserialize::deserialize(deserializer)), "budget"));
+        let budget = Budget {
+            per_choose: try!(remove(&mut map, "budget", "per_choose")),
+            per_game: try!(remove(&mut map, "budget", "per_game")),
+        };
+        try!(end(map, "budget"));
+        Ok(budget)
+    }
+}
+
+/// Insert the value into the map.
+fn insert<T: Serialize>(map: &mut BTreeMap<String, Value>, key: &str, value: &T) {
+    map.insert(key.to_string(), json::to_value(value));
+}
+
+/// Returns the value as the map of an object.
+fn object<E: Error>(value: Value, what: &str) -> Result<BTreeMap<String, Value>, E> {
+    match value {
+        Value::Object(map) => Ok(map),
+        _ => Err(E::custom(format!("{} must be an object", what))),
+    }
+}
+
+/// Remove the value of the key from the map.
+fn remove<T: Deserialize, E: Error>(map: &mut BTreeMap<String, Value>, what: &str, key: &str) -> Result<T, E> {
+    match remove_optional(map, what, key) {
+        Ok(Some(value)) => Ok(value),
+        Ok(None) => Err(E::custom(format!("{} is missing `{}`", what, key))),
+        Err(e) => Err(e),
+    }
+}
+
+/// Remove the value of the key from the map, if it's given.
+fn remove_optional<T: Deserialize, E: Error>(map: &mut BTreeMap<String, Value>, what: &str, key: &str) -> Result<Option<T>, E> {
+    match map.remove(key) {
+        Some(value) => {
+            json::from_value(value).map(Some).map_err(|_| {
+                E::custom(format!("invalid value for `{}` of {}", key, what))
+            })
+        },
+        None => Ok(None),
+    }
+}
+
+/// Catch keys left in the map, which aren't part of the object.
+fn end<E: Error>(map: BTreeMap<String, Value>, what: &str) -> Result<(), E> {
+    match map.keys().next() {
+        Some(key) => Err(E::custom(format!("unknown key `{}` in {}", key, what))),
+        None => Ok(()),
+    }
+}
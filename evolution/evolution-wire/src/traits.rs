@@ -10,6 +10,10 @@ use super::*;
 /// This file was last updated from
 /// [this link](http://www.ccs.neu.edu/home/matthias/4500-s16/5.html#%28tech._trait%29)
 /// on 04/12/16.
+///
+/// The expansion traits, `intelligent` through `mass-extinction`, are not
+/// part of the specification, and are only dealt in games with expansion
+/// cards.
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub enum Trait {
     Carnivore,
@@ -28,6 +32,11 @@ pub enum Trait {
     Scavenger,
     Symbiosis,
     WarningCall,
+    Intelligent,
+    Nocturnal,
+    DefensiveHerding,
+    Migratory,
+    MassExtinction,
 }
 
 impl Serialize for Trait {
@@ -35,22 +44,27 @@ impl Serialize for Trait {
         where S: Serializer
     {
         match *self {
-            Trait::Carnivore   => serializer.serialize_str("carnivore"),
-            Trait::Ambush      => serializer.serialize_str("ambush"),
-            Trait::Burrowing   => serializer.serialize_str("burrowing"),
-            Trait::Climbing    => serializer.serialize_str("climbing"),
-            Trait::Cooperation => serializer.serialize_str("cooperation"),
-            Trait::FatTissue   => serializer.serialize_str("fat-tissue"),
-            Trait::Fertile     => serializer.serialize_str("fertile"),
-            Trait::Foraging    => serializer.serialize_str("foraging"),
-            Trait::HardShell   => serializer.serialize_str("hard-shell"),
-            Trait::Herding     => serializer.serialize_str("herding"),
-            Trait::Horns       => serializer.serialize_str("horns"),
-            Trait::LongNeck    => serializer.serialize_str("long-neck"),
-            Trait::PackHunting => serializer.serialize_str("pack-hunting"),
-            Trait::Scavenger   => serializer.serialize_str("scavenger"),
-            Trait::Symbiosis   => serializer.serialize_str("symbiosis"),
-            Trait::WarningCall => serializer.serialize_str("warning-call"),
+            Trait::Carnivore        => serializer.serialize_str("carnivore"),
+            Trait::Ambush           => serializer.serialize_str("ambush"),
+            Trait::Burrowing        => serializer.serialize_str("burrowing"),
+            Trait::Climbing         => serializer.serialize_str("climbing"),
+            Trait::Cooperation      => serializer.serialize_str("cooperation"),
+            Trait::FatTissue        => serializer.serialize_str("fat-tissue"),
+            Trait::Fertile          => serializer.serialize_str("fertile"),
+            Trait::Foraging         => serializer.serialize_str("foraging"),
+            Trait::HardShell        => serializer.serialize_str("hard-shell"),
+            Trait::Herding          => serializer.serialize_str("herding"),
+            Trait::Horns            => serializer.serialize_str("horns"),
+            Trait::LongNeck         => serializer.serialize_str("long-neck"),
+            Trait::PackHunting      => serializer.serialize_str("pack-hunting"),
+            Trait::Scavenger        => serializer.serialize_str("scavenger"),
+            Trait::Symbiosis        => serializer.serialize_str("symbiosis"),
+            Trait::WarningCall      => serializer.serialize_str("warning-call"),
+            Trait::Intelligent      => serializer.serialize_str("intelligent"),
+            Trait::Nocturnal        => serializer.serialize_str("nocturnal"),
+            Trait::DefensiveHerding => serializer.serialize_str("defensive-herding"),
+            Trait::Migratory        => serializer.serialize_str("migratory"),
+            Trait::MassExtinction   => serializer.serialize_str("mass-extinction"),
         }
     }
 }
@@ -58,22 +72,27 @@ impl Serialize for Trait {
 impl Deserialize for Trait {
     fn deserialize<D>(deserializer: &mut D) -> Result<Self, D::Error> where D: Deserializer {
         match &try!(String::deserialize(deserializer))[..] {
-            "carnivore"    => Ok(Trait::Carnivore),
-            "ambush"       => Ok(Trait::Ambush),
-            "burrowing"    => Ok(Trait::Burrowing),
-            "climbing"     => Ok(Trait::Climbing),
-            "cooperation"  => Ok(Trait::Cooperation),
-            "fat-tissue"   => Ok(Trait::FatTissue),
-            "fertile"      => Ok(Trait::Fertile),
-            "foraging"     => Ok(Trait::Foraging),
-            "hard-shell"   => Ok(Trait::HardShell),
-            "herding"      => Ok(Trait::Herding),
-            "horns"        => Ok(Trait::Horns),
-            "long-neck"    => Ok(Trait::LongNeck),
-            "pack-hunting" => Ok(Trait::PackHunting),
-            "scavenger"    => Ok(Trait::Scavenger),
-            "symbiosis"    => Ok(Trait::Symbiosis),
-            "warning-call" => Ok(Trait::WarningCall),
+            "carnivore"         => Ok(Trait::Carnivore),
+            "ambush"            => Ok(Trait::Ambush),
+            "burrowing"         => Ok(Trait::Burrowing),
+            "climbing"          => Ok(Trait::Climbing),
+            "cooperation"       => Ok(Trait::Cooperation),
+            "fat-tissue"        => Ok(Trait::FatTissue),
+            "fertile"           => Ok(Trait::Fertile),
+            "foraging"          => Ok(Trait::Foraging),
+            "hard-shell"        => Ok(Trait::HardShell),
+            "herding"           => Ok(Trait::Herding),
+            "horns"             => Ok(Trait::Horns),
+            "long-neck"         => Ok(Trait::LongNeck),
+            "pack-hunting"      => Ok(Trait::PackHunting),
+            "scavenger"         => Ok(Trait::Scavenger),
+            "symbiosis"         => Ok(Trait::Symbiosis),
+            "warning-call"      => Ok(Trait::WarningCall),
+            "intelligent"       => Ok(Trait::Intelligent),
+            "nocturnal"         => Ok(Trait::Nocturnal),
+            "defensive-herding" => Ok(Trait::DefensiveHerding),
+            "migratory"         => Ok(Trait::Migratory),
+            "mass-extinction"   => Ok(Trait::MassExtinction),
             _ => return Err(Error::custom("invalid trait name"))
         }
     }
@@ -0,0 +1,75 @@
+use evolution_wire::*;
+
+#[test]
+fn serde_start() {
+    assert_serde! {
+        Event,
+        r#"["start",42,[1,2,3],{"mode":"official","population":5}]"#
+    };
+}
+
+#[test]
+fn serde_start_without_rules() {
+    assert_serde! {
+        Event,
+        r#"["start",42,[1,2,3]]"#,
+        r#"["start",42,[1,2,3],{}]"#
+    };
+}
+
+#[test]
+fn serde_deal() {
+    assert_serde! {
+        Event,
+        r#"["deal",[[1,[[3,"carnivore"]]],[2,[]]]]"#
+    };
+}
+
+#[test]
+fn serde_action() {
+    assert_serde! {
+        Event,
+        r#"["action",1,[0,[],[],[],[]]]"#
+    };
+}
+
+#[test]
+fn serde_action_failed() {
+    assert_serde! {
+        Event,
+        r#"["action",1,null]"#
+    };
+}
+
+#[test]
+fn serde_feed() {
+    assert_serde! {
+        Event,
+        r#"["feed",2,[0,1,0]]"#
+    };
+}
+
+#[test]
+fn serde_attack() {
+    assert_serde! {
+        Event,
+        r#"["attack",1,0,2,0,false,true]"#
+    };
+}
+
+#[test]
+fn serde_bag() {
+    assert_serde! {
+        Event,
+        r#"["bag",[[1,0],[2,1]]]"#
+    };
+}
+
+#[test]
+#[should_panic]
+fn serde_unknown() {
+    assert_serde! {
+        Event,
+        r#"["win",1]"#
+    };
+}
@@ -3,6 +3,7 @@ mod bt;
 mod choice;
 mod configuration;
 mod either;
+mod event;
 mod feed_choice;
 mod feeding;
 mod food_value;
@@ -13,7 +14,9 @@ mod natural;
 mod player;
 mod remote;
 mod rt;
+mod rules;
 mod situation;
+mod snapshot;
 mod species_card;
 mod species;
 mod start_round;
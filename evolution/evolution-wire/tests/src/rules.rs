@@ -0,0 +1,70 @@
+use evolution_wire::*;
+
+#[test]
+fn serde_empty() {
+    assert_serde! {
+        Rules,
+        r#"{}"#
+    };
+}
+
+#[test]
+fn serde_rules() {
+    assert_serde! {
+        Rules,
+        r#"{
+            "cards_per_species": 2,
+            "carnivore_cards": [-4, 4],
+            "players": [2, 4],
+            "rounds": 5,
+            "traits": 4
+        }"#
+    };
+}
+
+#[test]
+fn serde_official() {
+    assert_serde! {
+        Rules,
+        r#"{
+            "mode": "official",
+            "population": 7
+        }"#
+    };
+}
+
+#[test]
+#[should_panic]
+fn serde_unknown_mode() {
+    assert_serde! {
+        Rules,
+        r#"{"mode": "house"}"#
+    };
+}
+
+#[test]
+#[should_panic]
+fn serde_unknown_rule() {
+    assert_serde! {
+        Rules,
+        r#"{"populaton": 4}"#
+    };
+}
+
+#[test]
+#[should_panic]
+fn serde_zero_rounds() {
+    assert_serde! {
+        Rules,
+        r#"{"rounds": 0}"#
+    };
+}
+
+#[test]
+#[should_panic]
+fn serde_not_object() {
+    assert_serde! {
+        Rules,
+        r#"[2, 4]"#
+    };
+}
@@ -0,0 +1,87 @@
+use evolution_wire::snapshot::*;
+
+#[test]
+fn serde_stage() {
+    assert_serde! {
+        Stage,
+        r#""deal""#
+    };
+    assert_serde! {
+        Stage,
+        r#"["action",2]"#
+    };
+}
+
+#[test]
+#[should_panic]
+fn serde_unknown_stage() {
+    assert_serde! {
+        Stage,
+        r#""rest""#
+    };
+}
+
+#[test]
+fn serde_player() {
+    assert_serde! {
+        Player,
+        r#"{
+            "bag": 3,
+            "domain": [{"body_size": 0, "fat": 0, "food": 1, "population": 2, "traits": ["carnivore"]}],
+            "hand": [[-9, "carnivore"]],
+            "id": 1
+        }"#
+    };
+}
+
+#[test]
+fn serde_kick() {
+    assert_serde! {
+        Kick,
+        r#"{
+            "id": 2,
+            "offense": ["feed", [0, 1, 0]],
+            "reason": "timed out",
+            "round": 1,
+            "score": 4
+        }"#
+    };
+}
+
+#[test]
+fn serde_budget() {
+    assert_serde! {
+        Budget,
+        r#"{"per_choose": 1000000, "per_game": null}"#
+    };
+}
+
+#[test]
+fn serde_snapshot() {
+    assert_serde! {
+        Snapshot,
+        r#"{
+            "board_cards": null,
+            "current_player": null,
+            "deck": [[2, "fat-tissue"]],
+            "events": [["start", 1, [1, 2, 3], {}]],
+            "food": 0,
+            "kicked": [],
+            "players": [],
+            "round": 0,
+            "rules": {},
+            "seed": 1,
+            "skipped": [],
+            "stage": "deal"
+        }"#
+    };
+}
+
+#[test]
+#[should_panic]
+fn serde_snapshot_missing_key() {
+    assert_serde! {
+        Snapshot,
+        r#"{"rules": {}}"#
+    };
+}
@@ -9,6 +9,14 @@ fn serde_trait() {
     }
 }
 
+#[test]
+fn serde_expansion_trait() {
+    assert_serde! {
+        Trait,
+        "\"defensive-herding\""
+    }
+}
+
 #[test]
 fn deserialize_trait_err() {
     let json = "\"Carnivore\"";
@@ -7,7 +7,7 @@ fn main() {
     // Create a game.
     let mut game = Game::<Silly>::new(4).unwrap();
     // Play the game.
-    game.play();
+    game.play().unwrap();
 
     println!("{:#?}", game);
 }
@@ -0,0 +1,126 @@
+extern crate rand;
+extern crate evolution_logger;
+extern crate evolution;
+
+use std::collections::BTreeMap;
+use std::env;
+use std::fs::File;
+use evolution_logger::*;
+use evolution::game::*;
+use evolution::game::rules;
+use evolution::strategies::{self, Solver, Strategy, DEFAULT_POSITIONS};
+
+fn main() {
+    Logger::init().expect("logger failed to start");
+
+    let (player_count, seed, names, rules_path, positions) = parse_args();
+    // Seat the named strategies in turn until every seat is full, players
+    // are given ids counting up from 1 in this order.
+    let choosers = (0..player_count).map(|i| {
+        let name = &names[i % names.len()];
+        Strategy::from_name(name).unwrap_or_else(|| {
+            panic!("unknown strategy `{}`, expected one of {:?}", name, strategies::NAMES)
+        })
+    }).collect();
+    let seed = seed.unwrap_or_else(rand::random);
+    let rules = match rules_path {
+        Some(path) => {
+            let file = File::open(&path).expect("couldn't open rules");
+            rules::read_rules(file).expect("invalid rules")
+        },
+        None => RulesConfig::default(),
+    };
+    let mut game = Game::with_rules(choosers, seed, rules).unwrap();
+    let solver = Solver::new(positions);
+    let mut summaries = BTreeMap::new();
+
+    println!("seed: {}", seed);
+    while !game.is_finished() {
+        let solution = if game.stage() == Stage::Feed {
+            solver.solve(&game)
+        } else {
+            None
+        };
+        let logged = game.events().len();
+        game.step().expect("step failed");
+
+        for event in &game.events()[logged..] {
+            let (player, choice) = match *event {
+                Event::Feed { player, choice: Some(choice) } => (player, choice),
+                _ => continue,
+            };
+            let name = &names[(player as usize - 1) % names.len()];
+            let summary = summaries.entry(player).or_insert(Summary::default());
+            summary.choices += 1;
+            let solution = match solution {
+                Some(ref solution) => solution,
+                None => {
+                    summary.unsolved += 1;
+                    continue
+                },
+            };
+            let regret = solution.regret(choice).expect("played an illegal choice");
+            if regret == 0 {
+                summary.optimal += 1;
+            } else {
+                summary.regret += regret;
+                println!("round {}, player {} ({}): {:?} is {} food behind {:?}",
+                         game.round(), player, name, choice, regret,
+                         solution.best().expect("solved without a choice"));
+            }
+        }
+    }
+
+    for (player, summary) in summaries {
+        let name = &names[(player as usize - 1) % names.len()];
+        println!("player {} ({}): {} choices, {} optimal, {} unsolved, {} food behind in all",
+                 player, name, summary.choices, summary.optimal, summary.unsolved, summary.regret);
+    }
+}
+
+/// How close to the best food margin a player's feeding choices were.
+#[derive(Debug, Default)]
+struct Summary {
+    choices: usize,
+    optimal: usize,
+    unsolved: usize,
+    regret: i64,
+}
+
+/// Get the requested number of players, optionally a seed for the deck,
+/// the strategies to play (`--strategy silly,greedy`, defaulting to
+/// `silly`), optionally a JSON file of house rules (`--rules <file>`), and
+/// the most positions to search per choice (`--positions <n>`) from the
+/// command line arguments.
+fn parse_args() -> (usize, Option<u64>, Vec<String>, Option<String>, usize) {
+    let mut args = env::args().skip(1);
+    let mut positional = Vec::new();
+    let mut names = vec!["silly".to_string()];
+    let mut rules_path = None;
+    let mut positions = DEFAULT_POSITIONS;
+    while let Some(arg) = args.next() {
+        if arg == "--strategy" {
+            let list = args.next().expect("not given a strategy");
+            names = list.split(',').map(|s| s.to_string()).collect();
+        } else if arg == "--rules" {
+            rules_path = Some(args.next().expect("not given a rules file"));
+        } else if arg == "--positions" {
+            let n = args.next().expect("not given a number of positions");
+            positions = n.parse().expect("not given a valid number of positions");
+        } else {
+            positional.push(arg);
+        }
+    }
+    let mut positional = positional.into_iter();
+    let player_count = if let Some(s) = positional.next() {
+        if let Ok(n) = s.parse() {
+            n
+        } else {
+            panic!("not given a valid number");
+        }
+    } else {
+        panic!("not given a number of players");
+    };
+    let seed = positional.next().map(|s| s.parse().expect("not given a valid seed"));
+    (player_count, seed, names, rules_path, positions)
+}
@@ -0,0 +1,55 @@
+extern crate evolution_logger;
+extern crate evolution;
+
+use std::env;
+use std::time::Instant;
+use evolution_logger::*;
+use evolution::game::*;
+use evolution::strategies::{self, Strategy};
+
+fn main() {
+    Logger::init().expect("logger failed to start");
+
+    let (games, player_count, names) = parse_args();
+    let start = Instant::now();
+    let mut rounds = 0;
+    for seed in 0..games {
+        // Seat the named strategies in turn until every seat is full.
+        let choosers = (0..player_count).map(|i| {
+            let name = &names[i % names.len()];
+            Strategy::from_name(name).unwrap_or_else(|| {
+                panic!("unknown strategy `{}`, expected one of {:?}", name, strategies::NAMES)
+            })
+        }).collect();
+        let mut game = Game::with_choosers(choosers, seed as u64).unwrap();
+        game.play().expect("game failed");
+        rounds += game.round();
+    }
+    let elapsed = start.elapsed();
+    let seconds = elapsed.as_secs() as f64 + elapsed.subsec_nanos() as f64 / 1e9;
+
+    println!("{} games, {} rounds in {:.3}s", games, rounds, seconds);
+    println!("{:.1} games/s, {:.1} rounds/s", games as f64 / seconds, rounds as f64 / seconds);
+}
+
+/// Get the number of games and players, and the strategies to play
+/// (`--strategy random,greedy`, defaulting to `random`) from the command
+/// line arguments. Games are seeded counting up from 0, so every run plays
+/// the same games.
+fn parse_args() -> (usize, usize, Vec<String>) {
+    let mut args = env::args().skip(1);
+    let mut positional = Vec::new();
+    let mut names = vec!["random".to_string()];
+    while let Some(arg) = args.next() {
+        if arg == "--strategy" {
+            let list = args.next().expect("not given a strategy");
+            names = list.split(',').map(|s| s.to_string()).collect();
+        } else {
+            positional.push(arg);
+        }
+    }
+    let mut positional = positional.into_iter();
+    let games = positional.next().map_or(1000, |s| s.parse().expect("not given a valid number of games"));
+    let players = positional.next().map_or(4, |s| s.parse().expect("not given a valid number of players"));
+    (games, players, names)
+}
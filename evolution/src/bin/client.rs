@@ -5,69 +5,142 @@ extern crate evolution_wire as wire;
 extern crate evolution;
 
 use std::env;
+use std::io::{self, Write};
+use std::process;
+use std::str::FromStr;
+use std::thread;
+use std::time::Duration;
 use evolution_logger::Logger;
-use wire::{ToWire, FromWire, Channel};
-use evolution::interact::*;
-use evolution::silly::*;
+use wire::Channel;
+use evolution::remote::ClientSession;
+use evolution::strategies::{self, Strategy};
+
+const USAGE: &'static str = "\
+usage: client [options] <info> [strategy]
+
+Signs up with the server as <info>, and plays the named strategy, one of
+silly, random, greedy, carnivore, mcts or solver (default silly).
+
+options:
+    --host <host>       address of the server (default 127.0.0.1)
+    --port <port>       port of the server (default 1337)
+    --rejoin            sign up again for the next game after each game ends,
+                        waiting up to 5 seconds for the server
+
+The client exits successfully when the server closes the connection between
+rounds, which ends the game, and fails on any other error.";
+
+/// The number of times to try connecting when rejoining, while the server
+/// starts it's next game.
+const CONNECT_ATTEMPTS: usize = 10;
+
+/// How long to wait between attempts to connect.
+const CONNECT_DELAY_MS: u64 = 500;
+
+/// How the client is set up, from the command line.
+#[derive(Debug)]
+struct Config {
+    host: String,
+    port: u16,
+    info: String,
+    strategy: String,
+    rejoin: bool,
+}
 
 fn main() {
     Logger::init().expect("logger failed to start");
     debug!("starting client...");
-    let mut channel = establish_channel();
-    let mut next_start: Option<wire::remote::Start> = None;
-    next_start = Some(channel.recv().expect("failed to recv"));
-    debug!("got initial start {:?}", next_start);
 
+    let config = match parse_args(env::args().skip(1).collect()) {
+        Ok(config) => config,
+        Err(message) => exit(&format!("{}\n\n{}", message, USAGE)),
+    };
+    let addr = format!("{}:{}", config.host, config.port);
+    let mut games = 0;
     loop {
-        // Block for another message.
-        let observation = {
-            let o: (wire::remote::LOB, wire::remote::LOB) = channel.recv().expect("failed to recv");
-            debug!("got obvs {:?}", o);
-            ActionObservation::from_wire((next_start.expect("no start message"), o)).expect("invalid action observation")
+        // Only wait for the server when it's between games.
+        let attempts = if games == 0 { 1 } else { CONNECT_ATTEMPTS };
+        let mut channel = match connect(&config.info, &addr, attempts) {
+            Ok(channel) => channel,
+            Err(e) => exit(&format!("couldn't connect to {}: {}", addr, e)),
         };
-        next_start = None;
-        let choice = Silly.choose(&observation).unwrap().expect("no valid choice");
-        let wire_choice = ToWire::<wire::remote::Action4>::to_wire(&choice);
-        channel.send(&wire_choice).expect("failed to send");
-        debug!("send choice {:?}", choice);
+        let strategy = Strategy::from_name(&config.strategy).expect("strategy names are checked");
+        if let Err(e) = ClientSession::new(strategy).run(&mut channel) {
+            exit(&format!("{}", e));
+        }
+        games += 1;
+        info!("Game {} is over.", games);
+        if !config.rejoin {
+            break
+        }
+    }
+}
+
+/// Print the message to STDERR and exit with a failing status.
+fn exit(message: &str) -> ! {
+    let _ = writeln!(io::stderr(), "client: {}", message);
+    process::exit(1);
+}
 
-        while let Ok(either) = channel.recv_either::<wire::remote::Start, wire::remote::State>() {
-            match either {
-                wire::Either::Left(start) => {
-                    debug!("got new start {:?}", start);
-                    next_start = Some(start);
-                    debug!("breaking feed loop");
-                    break;
-                },
-                wire::Either::Right(state) => {
-                    debug!("got state {:?}", state);
-                    let observation = FeedObservation::from_wire(state).expect("invalid feed observation");
-                    debug!("got obvs {:?}", observation);
-                    let choice = Silly.choose(&observation).unwrap().expect("no valid choice");
-                    let wire_choice = choice.to_wire();
-                    channel.send(&wire_choice).expect("failed to send");
-                    debug!("send choice {:?}", choice);
-                },
+/// Connect to the server, trying up to `attempts` times.
+fn connect(info: &str, addr: &str, attempts: usize) -> Result<Channel, wire::Error> {
+    let mut attempt = 1;
+    loop {
+        match Channel::connect_to_socket_addr(info.to_string(), addr) {
+            Ok(channel) => return Ok(channel),
+            Err(e) => {
+                if attempt >= attempts {
+                    return Err(e)
+                }
+                debug!("connecting failed: {}, trying again", e);
+                attempt += 1;
+                thread::sleep(Duration::from_millis(CONNECT_DELAY_MS));
             }
         }
     }
 }
 
-fn establish_channel() -> Channel {
-    let info = parse_args();
-    let socket_addr = "127.0.0.1:1337";
-    if let Ok(channel) = Channel::connect_to_socket_addr(info, socket_addr) {
-        channel
-    } else {
-        panic!("failed to connect to channel");
+/// Get the client's configuration from the command line arguments, see
+/// `USAGE`.
+fn parse_args(args: Vec<String>) -> Result<Config, String> {
+    let mut host = "127.0.0.1".to_string();
+    let mut port = 1337;
+    let mut rejoin = false;
+    let mut positional = Vec::new();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match &*arg {
+            "--host" => host = try!(option_value(&arg, args.next())),
+            "--port" => port = try!(option_value(&arg, args.next())),
+            "--rejoin" => rejoin = true,
+            _ if arg.starts_with("--") => return Err(format!("unexpected argument `{}`", arg)),
+            _ => positional.push(arg),
+        }
+    }
+    let mut positional = positional.into_iter();
+    let info = try!(positional.next().ok_or("not given info to sign up with".to_string()));
+    let strategy = positional.next().unwrap_or("silly".to_string());
+    if Strategy::from_name(&strategy).is_none() {
+        return Err(format!("unknown strategy `{}`, expected one of {:?}", strategy, strategies::NAMES));
+    }
+    if let Some(arg) = positional.next() {
+        return Err(format!("unexpected argument `{}`", arg));
     }
+    Ok(Config {
+        host: host,
+        port: port,
+        info: info,
+        strategy: strategy,
+        rejoin: rejoin,
+    })
 }
 
-fn parse_args() -> String {
-    let mut args = env::args();
-    if let Some(s) = args.nth(1) {
-        s
-    } else {
-        panic!("not given argument");
+/// Parse the value given for an option.
+fn option_value<T: FromStr>(option: &str, value: Option<String>) -> Result<T, String> {
+    match value {
+        Some(value) => value.parse().map_err(|_| {
+            format!("not given a valid value for {}: `{}`", option, value)
+        }),
+        None => Err(format!("not given a value for {}", option)),
     }
 }
@@ -0,0 +1,100 @@
+extern crate evolution_logger;
+extern crate evolution_wire as wire;
+extern crate evolution;
+
+use std::env;
+use std::io::{self, Write};
+use std::net::TcpListener;
+use std::process;
+use std::str::FromStr;
+use std::time::Duration;
+use evolution_logger::Logger;
+use wire::Channel;
+use evolution::game::*;
+use evolution::lobby::Lobby;
+
+const USAGE: &'static str = "\
+usage: lobby [options]
+
+Plays games back to back between the clients waiting in the lobby.
+
+options:
+    --host <host>       address to listen on (default 127.0.0.1)
+    --port <port>       port to listen on (default 1337)
+    --results <dir>     directory to write each game's results to (default results)
+    --choose-ms <ms>    time each player has for a choice (default 2000)
+    --game-ms <ms>      time each player has for a whole game";
+
+/// How the lobby is set up, from the command line.
+#[derive(Debug)]
+struct Config {
+    host: String,
+    port: u16,
+    results: String,
+    budget: Budget,
+}
+
+fn main() {
+    Logger::init().expect("logger failed to start");
+
+    let config = match parse_args(env::args().skip(1).collect()) {
+        Ok(config) => config,
+        Err(message) => exit(&format!("{}\n\n{}", message, USAGE)),
+    };
+    let addr = format!("{}:{}", config.host, config.port);
+    let listener = match TcpListener::bind(&*addr) {
+        Ok(listener) => listener,
+        Err(e) => exit(&format!("couldn't listen on {}: {}", addr, e)),
+    };
+    let mut lobby = match Lobby::<Channel>::new(config.results) {
+        Ok(lobby) => lobby,
+        Err(e) => exit(&format!("couldn't create the results directory: {}", e)),
+    };
+    lobby.set_budget(config.budget);
+    if let Err(e) = lobby.run(listener) {
+        exit(&format!("{}", e));
+    }
+}
+
+/// Print the message to STDERR and exit with a failing status.
+fn exit(message: &str) -> ! {
+    let _ = writeln!(io::stderr(), "lobby: {}", message);
+    process::exit(1);
+}
+
+/// Get the lobby's configuration from the command line arguments, see
+/// `USAGE`.
+fn parse_args(args: Vec<String>) -> Result<Config, String> {
+    let mut config = Config {
+        host: "127.0.0.1".to_string(),
+        port: 1337,
+        results: "results".to_string(),
+        budget: Budget::default(),
+    };
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match &*arg {
+            "--host" => config.host = try!(option_value(&arg, args.next())),
+            "--port" => config.port = try!(option_value(&arg, args.next())),
+            "--results" => config.results = try!(option_value(&arg, args.next())),
+            "--choose-ms" => {
+                config.budget.per_choose = Some(Duration::from_millis(try!(option_value(&arg, args.next()))));
+            }
+            "--game-ms" => {
+                config.budget.per_game = Some(Duration::from_millis(try!(option_value(&arg, args.next()))));
+            }
+            _ => return Err(format!("unexpected argument `{}`", arg)),
+        }
+    }
+    Ok(config)
+}
+
+/// Parse the value given for an option.
+fn option_value<T: FromStr>(option: &str, value: Option<String>) -> Result<T, String> {
+    match value {
+        Some(value) => value.parse().map_err(|_| {
+            format!("not given a valid value for {}: `{}`", option, value)
+        }),
+        None => Err(format!("not given a value for {}", option)),
+    }
+}
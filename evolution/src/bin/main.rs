@@ -1,26 +1,61 @@
 #[macro_use]
 extern crate log;
+extern crate rand;
 extern crate evolution_logger;
 extern crate evolution;
 
 use std::env;
+use std::fs::File;
 use evolution_logger::*;
 use evolution::game::*;
-use evolution::silly::*;
+use evolution::game::rules;
+use evolution::strategies::{self, Strategy};
 
 fn main() {
     Logger::init().expect("logger failed to start");
 
-    let player_count = parse_args();
-    let mut game = Game::<Silly>::new(player_count).unwrap();
-    game.play();
+    let (player_count, seed, names, rules_path) = parse_args();
+    // Seat the named strategies in turn until every seat is full.
+    let choosers = (0..player_count).map(|i| {
+        let name = &names[i % names.len()];
+        Strategy::from_name(name).unwrap_or_else(|| {
+            panic!("unknown strategy `{}`, expected one of {:?}", name, strategies::NAMES)
+        })
+    }).collect();
+    let seed = seed.unwrap_or_else(rand::random);
+    let rules = match rules_path {
+        Some(path) => {
+            let file = File::open(&path).expect("couldn't open rules");
+            rules::read_rules(file).expect("invalid rules")
+        },
+        None => RulesConfig::default(),
+    };
+    let mut game = Game::with_rules(choosers, seed, rules).unwrap();
+    game.play().expect("game failed");
     game.print_scores();
 }
 
-/// Get the requested number of players from the command line arguments.
-fn parse_args() -> usize {
-    let mut args = env::args();
-    if let Some(s) = args.nth(1) {
+/// Get the requested number of players, optionally a seed for the deck,
+/// the strategies to play (`--strategy silly,greedy`, defaulting to
+/// `silly`), and optionally a JSON file of house rules (`--rules <file>`)
+/// from the command line arguments.
+fn parse_args() -> (usize, Option<u64>, Vec<String>, Option<String>) {
+    let mut args = env::args().skip(1);
+    let mut positional = Vec::new();
+    let mut names = vec!["silly".to_string()];
+    let mut rules_path = None;
+    while let Some(arg) = args.next() {
+        if arg == "--strategy" {
+            let list = args.next().expect("not given a strategy");
+            names = list.split(',').map(|s| s.to_string()).collect();
+        } else if arg == "--rules" {
+            rules_path = Some(args.next().expect("not given a rules file"));
+        } else {
+            positional.push(arg);
+        }
+    }
+    let mut positional = positional.into_iter();
+    let player_count = if let Some(s) = positional.next() {
         if let Ok(n) = s.parse() {
             n
         } else {
@@ -28,5 +63,7 @@ fn parse_args() -> usize {
         }
     } else {
         panic!("not given a number of players");
-    }
+    };
+    let seed = positional.next().map(|s| s.parse().expect("not given a valid seed"));
+    (player_count, seed, names, rules_path)
 }
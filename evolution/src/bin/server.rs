@@ -7,37 +7,109 @@ extern crate evolution;
 
 use std::env;
 use std::cmp;
+use std::io::{self, Write};
 use std::net::TcpListener;
+use std::process;
+use std::str::FromStr;
 use std::sync::{Arc, Mutex};
-use std::{thread, time};
+use std::thread;
+use std::time::{Duration, Instant};
 use itertools::Itertools;
 use evolution_logger::Logger;
 use wire::Channel;
 use evolution::game::*;
+use evolution::interact::Chooser;
+use evolution::strategies::{self, Strategy};
+
+/// The address the server listens on by default.
+const DEFAULT_HOST: &'static str = "127.0.0.1";
+
+/// The port the server listens on by default.
+const DEFAULT_PORT: u16 = 1337;
+
+const USAGE: &'static str = "\
+usage: server [options] <players>
+
+Waits for <players> clients to sign up, then plays a game with them.
+
+options:
+    --host <host>       address to listen on (default 127.0.0.1)
+    --port <port>       port to listen on (default 1337)
+    --min <n>           fewest players to start a game with (default 3)
+    --signup <secs>     start with whoever joined after this many seconds
+    --fill <names>      seat built in strategies in place of players who
+                        didn't sign up in time, comma separated names are
+                        taken in turn (needs --signup)
+    --choose-ms <ms>    time each player has for a choice (default 2000
+                        for clients, unlimited for house strategies)
+    --game-ms <ms>      time each player has for the whole game";
+
+/// How the server is set up, from the command line.
+#[derive(Debug)]
+struct Config {
+    host: String,
+    port: u16,
+    players: usize,
+    min: usize,
+    signup: Option<Duration>,
+    fill: Vec<String>,
+    budget: Budget,
+}
 
 fn main() {
     Logger::init().expect("logger failed to start");
 
-    let channels = accept_clients(parse_args());
+    let config = match parse_args(env::args().skip(1).collect()) {
+        Ok(config) => config,
+        Err(message) => exit(&format!("{}\n\n{}", message, USAGE)),
+    };
+    let channels = match accept_clients(&config) {
+        Ok(channels) => channels,
+        Err(message) => exit(&message),
+    };
+    let mut choosers: Vec<Box<Chooser + Send>> = channels.into_iter().map(|channel| {
+        Box::new(channel) as Box<Chooser + Send>
+    }).collect();
+    let empty = cmp::min(config.players, MAX_PLAYERS).saturating_sub(choosers.len());
+    choosers.extend(fill_seats(&config.fill, empty));
     // TODO: <refactor> A struct for the player notion above it's state would help.
-    info!("Playing game with {}.", channels.iter().enumerate().map(|(i, channel)| {
-        format!("{}=>{}", i + 1, channel.info())
+    info!("Playing game with {}.", choosers.iter().enumerate().map(|(i, chooser)| {
+        format!("{}=>{}", i + 1, chooser.info().unwrap_or(""))
     }).join(" "));
-    let mut game = Game::<Channel>::new(channels).expect("failed to create game");
-    game.play();
+    let mut game = Game::<Box<Chooser + Send>>::new(choosers).expect("failed to create game");
+    game.set_budget(config.budget);
+    if let Err(e) = game.play() {
+        exit(&format!("game failed: {}", e));
+    }
     game.print_scores();
 }
 
-/// Wait for `n` clients to connect, returning at most `MAX_PLAYERS`
-/// channels.
-fn accept_clients(n: usize) -> Vec<Channel> {
-    let listener = TcpListener::bind("127.0.0.1:1337").unwrap();
+/// Print the message to STDERR and exit with a failing status.
+fn exit(message: &str) -> ! {
+    let _ = writeln!(io::stderr(), "server: {}", message);
+    process::exit(1);
+}
+
+/// Wait for `config.players` clients to connect, or until the sign up
+/// deadline passes, returning at most `MAX_PLAYERS` channels.
+///
+/// Fails if fewer than `config.min` clients signed up in time, unless the
+/// empty seats are to be filled with built in strategies.
+fn accept_clients(config: &Config) -> Result<Vec<Channel>, String> {
+    let addr = format!("{}:{}", config.host, config.port);
+    let listener = try!(TcpListener::bind(&*addr)
+        .map_err(|e| format!("couldn't listen on {}: {}", addr, e)));
+    // Don't block waiting for clients, so we can check the deadline.
+    try!(listener.set_nonblocking(true)
+        .map_err(|e| format!("couldn't listen on {}: {}", addr, e)));
+    info!("Listening on {}.", addr);
 
+    let deadline = config.signup.map(|signup| Instant::now() + signup);
     let channels = Arc::new(Mutex::new(vec![]));
 
-    for stream in listener.incoming() {
-        match stream {
-            Ok(stream) => {
+    loop {
+        match listener.accept() {
+            Ok((stream, _)) => {
                 // Clone the arc, this internally bumps the reference count.
                 let channels = channels.clone();
 
@@ -47,6 +119,10 @@ fn accept_clients(n: usize) -> Vec<Channel> {
                     let mut channels = channels.lock().expect("failed to get lock");
                     // TODO: <question> Can we try to establish a connection
                     // if we are full of players?
+                    if let Err(e) = stream.set_nonblocking(false) {
+                        warn!("couldn't establish channel: {:?}", e);
+                        return;
+                    }
                     match Channel::accept_from_tcp_stream(stream) {
                         Ok(channel) => {
                             // Push the client's channel into the channels.
@@ -59,39 +135,113 @@ fn accept_clients(n: usize) -> Vec<Channel> {
                     }
                 });
             }
+            Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => {}
             Err(e) => {
                 warn!("connecting failed: {:?}", e);
             }
         }
 
-        // Check if we've gotten enough, or time has run up?
-        // TODO: <needed> Check time.
         // XXX: Waiting for the accepter thread to spin up.
-        thread::sleep(time::Duration::from_millis(10));
+        thread::sleep(Duration::from_millis(10));
         let channels = channels.lock().expect("failed to get lock");
-        if channels.len() >= n {
+        if channels.len() >= config.players {
             debug!("Channel count: {}", channels.len());
             break
         }
+        if deadline.map_or(false, |deadline| Instant::now() >= deadline) {
+            info!("Sign up closed with {} players.", channels.len());
+            break
+        }
     }
 
     let mut channels = channels.lock().expect("failed to get lock");
+    if channels.len() < config.min && config.fill.is_empty() {
+        return Err(format!("only {} players signed up, need at least {}",
+                           channels.len(),
+                           config.min));
+    }
     let num = cmp::min(channels.len(), MAX_PLAYERS);
-    let vec = channels.drain(0..num).collect::<Vec<_>>();
-    assert!(vec.len() >= MIN_PLAYERS);
-    vec
+    Ok(channels.drain(0..num).collect())
 }
 
-/// Get the requested number of players from the command line arguments.
-fn parse_args() -> usize {
-    let mut args = env::args();
-    if let Some(s) = args.nth(1) {
-        if let Ok(n) = s.parse() {
-            n
-        } else {
-            panic!("not given a valid number");
+/// Built in strategies to seat in `n` empty seats, taking the given names
+/// in turn.
+fn fill_seats(names: &[String], n: usize) -> Vec<Box<Chooser + Send>> {
+    names.iter().cycle().take(n).map(|name| {
+        let strategy = Strategy::from_name(name).expect("strategy names are checked");
+        Box::new(strategy) as Box<Chooser + Send>
+    }).collect()
+}
+
+/// Get the server's configuration from the command line arguments, see
+/// `USAGE`.
+fn parse_args(args: Vec<String>) -> Result<Config, String> {
+    let mut config = Config {
+        host: DEFAULT_HOST.to_string(),
+        port: DEFAULT_PORT,
+        players: 0,
+        min: MIN_PLAYERS,
+        signup: None,
+        fill: vec![],
+        budget: Budget::default(),
+    };
+    let mut players = None;
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match &*arg {
+            "--host" => config.host = try!(option_value(&arg, args.next())),
+            "--port" => config.port = try!(option_value(&arg, args.next())),
+            "--min" => config.min = try!(option_value(&arg, args.next())),
+            "--signup" => {
+                config.signup = Some(Duration::from_secs(try!(option_value(&arg, args.next()))));
+            }
+            "--fill" => {
+                let names: String = try!(option_value(&arg, args.next()));
+                config.fill = try!(names.split(',').map(|name| {
+                    match Strategy::from_name(name) {
+                        Some(_) => Ok(name.to_string()),
+                        None => Err(format!("unknown strategy `{}`, expected one of {:?}",
+                                            name,
+                                            strategies::NAMES)),
+                    }
+                }).collect());
+            }
+            "--choose-ms" => {
+                config.budget.per_choose = Some(Duration::from_millis(try!(option_value(&arg, args.next()))));
+            }
+            "--game-ms" => {
+                config.budget.per_game = Some(Duration::from_millis(try!(option_value(&arg, args.next()))));
+            }
+            _ if players.is_none() => {
+                players = Some(try!(arg.parse().map_err(|_| {
+                    format!("not given a valid number of players: `{}`", arg)
+                })));
+            }
+            _ => return Err(format!("unexpected argument `{}`", arg)),
         }
-    } else {
-        panic!("not given a number of players");
+    }
+    config.players = try!(players.ok_or("not given a number of players".to_string()));
+
+    if config.min < MIN_PLAYERS {
+        return Err(format!("a game needs at least {} players", MIN_PLAYERS));
+    }
+    if !config.fill.is_empty() && config.signup.is_none() {
+        return Err("can't fill seats without a sign up deadline".to_string());
+    }
+    if config.players < config.min {
+        return Err(format!("can't wait for {} players, fewer than the minimum {}",
+                           config.players,
+                           config.min));
+    }
+    Ok(config)
+}
+
+/// Parse the value given for an option.
+fn option_value<T: FromStr>(option: &str, value: Option<String>) -> Result<T, String> {
+    match value {
+        Some(value) => value.parse().map_err(|_| {
+            format!("not given a valid value for {}: `{}`", option, value)
+        }),
+        None => Err(format!("not given a value for {}", option)),
     }
 }
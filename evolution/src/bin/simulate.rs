@@ -0,0 +1,71 @@
+extern crate rand;
+extern crate evolution_logger;
+extern crate evolution;
+
+use std::env;
+use std::fs::File;
+use evolution_logger::*;
+use evolution::game::*;
+use evolution::game::rules;
+use evolution::simulation::*;
+
+fn main() {
+    Logger::init().expect("logger failed to start");
+
+    let (games, players, seed, names, rules_path, format) = parse_args();
+    let seed = seed.unwrap_or_else(rand::random);
+    let rules = match rules_path {
+        Some(path) => {
+            let file = File::open(&path).expect("couldn't open rules");
+            rules::read_rules(file).expect("invalid rules")
+        },
+        None => RulesConfig::default(),
+    };
+    let simulation = Simulation::new(names, players, games, seed, rules)
+        .expect("invalid simulation");
+    let statistics = simulation.run().expect("simulation failed");
+
+    match &format[..] {
+        "json" => println!("{}", statistics.to_json()),
+        "csv" => print!("{}", statistics.to_csv()),
+        _ => {
+            println!("seed: {}", seed);
+            print!("{}", statistics);
+        },
+    }
+}
+
+/// Get the number of games and players, optionally a seed, the strategies
+/// to play (`--strategy silly,greedy`, defaulting to `silly`), optionally a
+/// JSON file of house rules (`--rules <file>`), and the output format
+/// (`--format text|csv|json`, defaulting to `text`) from the command line
+/// arguments.
+fn parse_args() -> (usize, usize, Option<u64>, Vec<String>, Option<String>, String) {
+    let mut args = env::args().skip(1);
+    let mut positional = Vec::new();
+    let mut names = vec!["silly".to_string()];
+    let mut rules_path = None;
+    let mut format = "text".to_string();
+    while let Some(arg) = args.next() {
+        if arg == "--strategy" {
+            let list = args.next().expect("not given a strategy");
+            names = list.split(',').map(|s| s.to_string()).collect();
+        } else if arg == "--rules" {
+            rules_path = Some(args.next().expect("not given a rules file"));
+        } else if arg == "--format" {
+            format = args.next().expect("not given a format");
+            if format != "text" && format != "csv" && format != "json" {
+                panic!("unknown format `{}`, expected text, csv or json", format);
+            }
+        } else {
+            positional.push(arg);
+        }
+    }
+    let mut positional = positional.into_iter();
+    let games = positional.next().and_then(|s| s.parse().ok())
+        .expect("not given a valid number of games");
+    let players = positional.next().and_then(|s| s.parse().ok())
+        .expect("not given a valid number of players");
+    let seed = positional.next().map(|s| s.parse().expect("not given a valid seed"));
+    (games, players, seed, names, rules_path, format)
+}
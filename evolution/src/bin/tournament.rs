@@ -0,0 +1,44 @@
+extern crate evolution_logger;
+extern crate evolution;
+
+use std::cmp;
+use std::env;
+use evolution_logger::*;
+use evolution::game::*;
+use evolution::silly::*;
+use evolution::tournament::*;
+
+/// The number of worker threads to play games on.
+const THREADS: usize = 4;
+
+fn main() {
+    Logger::init().expect("logger failed to start");
+
+    let (entrants, games, seed, json) = parse_args();
+    let names = (0..entrants).map(|i| format!("silly{}", i + 1)).collect();
+    let players_per_game = cmp::min(entrants, MAX_PLAYERS);
+    let tournament = Tournament::new(names, games, players_per_game, seed)
+        .expect("invalid number of entrants");
+    let standings = tournament.run_parallel(THREADS, |_| Silly)
+        .expect("tournament failed");
+
+    if json {
+        println!("{}", standings.to_json());
+    } else {
+        print!("{}", standings);
+    }
+}
+
+/// Get the number of entrants, games, optionally a seed, and whether to
+/// print the standings as JSON (`--json`) from the command line arguments.
+fn parse_args() -> (usize, usize, u64, bool) {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    let json = args.iter().any(|a| a == "--json");
+    let mut args = args.into_iter().filter(|a| a != "--json");
+    let entrants = args.next().and_then(|s| s.parse().ok())
+        .expect("not given a valid number of entrants");
+    let games = args.next().and_then(|s| s.parse().ok())
+        .expect("not given a valid number of games");
+    let seed = args.next().map(|s| s.parse().expect("not given a valid seed")).unwrap_or(0);
+    (entrants, games, seed, json)
+}
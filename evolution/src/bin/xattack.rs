@@ -26,7 +26,7 @@ impl Situation {
 }
 
 impl wire::FromWire<wire::Situation> for Situation {
-    fn from_wire(wire: wire::Situation) -> Result<Situation, ()> {
+    fn from_wire(wire: wire::Situation) -> Result<Situation, wire::Error> {
         let attacker = try!(Species::from_wire(wire.attacker));
         let defender = try!(Species::from_wire(wire.defender));
         let left = if let Some(l) = wire.left {
@@ -0,0 +1,78 @@
+extern crate serde_json;
+extern crate evolution_wire;
+#[macro_use]
+extern crate evolution;
+
+use std::collections::BTreeMap;
+use serde_json::Value;
+use evolution_wire as wire;
+use evolution::object::*;
+
+test_harness!(situation = wire::Situation => Situation, {
+    println!("{}", situation.explain());
+});
+
+/// An attacking situation between 2 to 4 species.
+#[derive(Debug)]
+struct Situation {
+    attacker: Species,
+    defender: Species,
+    left: Option<Species>,
+    right: Option<Species>,
+}
+
+impl Situation {
+    /// Explain the attack as a JSON object, saying if the attack can happen,
+    /// and listing the traits which block or enable it.
+    fn explain(&self) -> Value {
+        let explanation = self.attacker.explain_attack(&self.defender, self.left.as_ref(), self.right.as_ref());
+        let factors = explanation.factors().iter().map(|factor| {
+            let species = match factor.party {
+                Party::Attacker => "attacker",
+                Party::Target => "defender",
+                Party::Left => "left",
+                Party::Right => "right",
+            };
+            let verdict = match factor.verdict {
+                Verdict::Blocks => "blocks",
+                Verdict::Enables => "enables",
+                Verdict::Outwits => "outwits",
+            };
+            let trait_type: wire::Trait = wire::ToWire::to_wire(&factor.trait_type);
+            let mut object = BTreeMap::new();
+            object.insert("species".to_string(), Value::String(species.to_string()));
+            object.insert("trait".to_string(), serde_json::to_value(&trait_type));
+            object.insert("verdict".to_string(), Value::String(verdict.to_string()));
+            object.insert("reason".to_string(), Value::String(factor.reason.clone()));
+            Value::Object(object)
+        }).collect();
+
+        let mut object = BTreeMap::new();
+        object.insert("attack".to_string(), Value::Bool(explanation.can_attack()));
+        object.insert("factors".to_string(), Value::Array(factors));
+        Value::Object(object)
+    }
+}
+
+impl wire::FromWire<wire::Situation> for Situation {
+    fn from_wire(wire: wire::Situation) -> Result<Situation, wire::Error> {
+        let attacker = try!(Species::from_wire(wire.attacker));
+        let defender = try!(Species::from_wire(wire.defender));
+        let left = if let Some(l) = wire.left {
+            Some(try!(Species::from_wire(l)))
+        } else {
+            None
+        };
+        let right = if let Some(r) = wire.right {
+            Some(try!(Species::from_wire(r)))
+        } else {
+            None
+        };
+        Ok(Situation {
+            attacker: attacker,
+            defender: defender,
+            left: left,
+            right: right,
+        })
+    }
+}
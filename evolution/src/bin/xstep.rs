@@ -7,7 +7,8 @@ use evolution_wire as wire;
 use evolution::game::*;
 use evolution::silly::*;
 
-test_harness!(mut game = wire::Configuration => Game, {
+test_harness!(mut game = wire::Configuration => GameState, {
     step::Feed(&mut game, &mut Auto(&mut Silly)).step().unwrap();
-    println!("{}", json::to_string(&game.to_wire()).unwrap());
+    let configuration: wire::Configuration = game.to_wire();
+    println!("{}", json::to_string(&configuration).unwrap());
 });
@@ -9,29 +9,26 @@ use evolution::interact::*;
 use evolution::silly::*;
 
 test_harness!(StartRound(mut game, mut action_choices) = wire::StartRound => StartRound, {
-    unsafe {
-        let game: *mut Game<Silly> = &mut game;
-
-        for (i, _) in (&*game).players().iter().enumerate() {
-            step::Action(&mut *game, &mut action_choices[i]).step().unwrap();
-        }
+    for i in 0..game.players().len() {
+        step::Action(&mut game, &mut action_choices[i]).step().unwrap();
+    }
 
-        step::Reveal(&mut *game).step().unwrap();
+    step::Reveal(&mut game).step().unwrap();
 
-        while !(&*game).turn_is_over() {
-            step::Feed(&mut *game, &mut Auto(&mut Silly)).step().unwrap();
-        }
+    while !game.turn_is_over() {
+        step::Feed(&mut game, &mut Auto(&mut Silly)).step().unwrap();
     }
 
-    println!("{}", json::to_string(&game.to_wire()).unwrap());
+    let configuration: wire::Configuration = game.to_wire();
+    println!("{}", json::to_string(&configuration).unwrap());
 });
 
-/// The start round wire type is really a `Game`, and a bunch of action choices.
-struct StartRound(Game<Silly>, Vec<ActionChoice>);
+/// The start round wire type is really a `GameState`, and a bunch of action choices.
+struct StartRound(GameState, Vec<ActionChoice>);
 
 impl wire::FromWire<wire::StartRound> for StartRound {
-    fn from_wire(wire: wire::StartRound) -> Result<StartRound, ()> {
-        let game = try!(Game::from_wire(wire.configuration));
+    fn from_wire(wire: wire::StartRound) -> Result<StartRound, wire::Error> {
+        let game = try!(GameState::from_wire(wire.configuration));
         let action_choices = try!(Vec::from_wire(wire.step_actions));
         Ok(StartRound(game, action_choices))
     }
@@ -0,0 +1,220 @@
+use std::error;
+use std::fmt;
+use std::io;
+#[cfg(feature = "wire")]
+use evolution_wire as wire;
+use object::Trait;
+use remote::Phase;
+
+/// Everything that can go wrong while playing a game.
+///
+/// When a player is ejected, or a game can't go on, one of these says why.
+///
+/// # Examples
+///
+/// ```
+/// use evolution::error::{Error, RuleError};
+/// use evolution::object::*;
+///
+/// let mut species = Species::default();
+/// species.evolve(Trait::Carnivore).unwrap();
+/// match species.evolve(Trait::Carnivore) {
+///     Err(Error::Rule(RuleError::DuplicateTrait(Trait::Carnivore))) => {},
+///     _ => panic!("should be a duplicate trait"),
+/// }
+/// ```
+#[derive(Debug)]
+pub enum Error {
+    /// A player's choice was invalid.
+    Choice(ChoiceError),
+    /// A rule of the game would have been broken.
+    Rule(RuleError),
+    /// Data from the wire didn't decode into a game object.
+    Decode(String),
+    /// A player took too long to respond.
+    Timeout,
+    /// Talking to a player failed.
+    Io(io::Error),
+    /// An event log doesn't fit the game being replayed, at the given index.
+    Replay(usize),
+    /// The remote protocol was broken.
+    Protocol(ProtocolError),
+}
+
+/// The ways a player's choice can be invalid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChoiceError {
+    /// A choice was needed, but the player didn't give one.
+    Missing,
+    /// The card index isn't in the player's hand, or was already used.
+    Card(usize),
+    /// The species index isn't in the player's domain.
+    Species(usize),
+    /// The trait index isn't on the species with the given species index,
+    /// in that order.
+    Trait(usize, usize),
+    /// The choice isn't one of the legal choices for the observation.
+    Illegal,
+}
+
+/// The ways the remote protocol can be broken, see `remote::Phase`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProtocolError {
+    /// The named message can't come in the phase.
+    OutOfOrder(Phase, &'static str),
+    /// The connection closed in the phase, part way through a round.
+    Closed(Phase),
+}
+
+/// The rules of the game which a step can break.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RuleError {
+    /// A game can't be played with this many players.
+    PlayerCount(usize),
+    /// The species is already at `MAX_POPULATION`.
+    MaxPopulation,
+    /// The species is already at `MAX_BODY_SIZE`.
+    MaxBodySize,
+    /// The species already has `MAX_TRAITS` traits.
+    MaxTraits,
+    /// The species already has the trait.
+    DuplicateTrait(Trait),
+    /// The species has no trait at the index.
+    NoTrait(usize),
+    /// The species is extinct, and can't lose any more population.
+    Extinct,
+    /// The species can't store that much fat food.
+    FatTissue,
+    /// The deck ran out of cards while dealing.
+    EmptyDeck,
+    /// The rules of the game leave it impossible to play.
+    Unplayable,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Choice(ref e) => write!(f, "invalid choice: {}", e),
+            Error::Rule(ref e) => write!(f, "broken rule: {}", e),
+            Error::Decode(ref reason) => write!(f, "decode error: {}", reason),
+            Error::Timeout => write!(f, "timed out"),
+            Error::Io(ref e) => write!(f, "io error: {}", e),
+            Error::Replay(index) => write!(f, "event {} doesn't fit the replayed game", index),
+            Error::Protocol(ref e) => write!(f, "protocol error: {}", e),
+        }
+    }
+}
+
+impl fmt::Display for ChoiceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ChoiceError::Missing => write!(f, "no choice given"),
+            ChoiceError::Card(i) => write!(f, "card {} isn't in hand", i),
+            ChoiceError::Species(i) => write!(f, "species {} isn't in domain", i),
+            ChoiceError::Trait(s, t) => write!(f, "trait {} isn't on species {}", t, s),
+            ChoiceError::Illegal => write!(f, "not a legal choice"),
+        }
+    }
+}
+
+impl fmt::Display for ProtocolError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ProtocolError::OutOfOrder(phase, message) => {
+                write!(f, "unexpected {} message while {}", message, phase)
+            },
+            ProtocolError::Closed(phase) => write!(f, "connection closed while {}", phase),
+        }
+    }
+}
+
+impl fmt::Display for RuleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            RuleError::PlayerCount(n) => write!(f, "can't play with {} players", n),
+            RuleError::MaxPopulation => write!(f, "population is at the maximum"),
+            RuleError::MaxBodySize => write!(f, "body size is at the maximum"),
+            RuleError::MaxTraits => write!(f, "species has the maximum number of traits"),
+            RuleError::DuplicateTrait(t) => write!(f, "species already has {:?}", t),
+            RuleError::NoTrait(i) => write!(f, "species has no trait {}", i),
+            RuleError::Extinct => write!(f, "species is extinct"),
+            RuleError::FatTissue => write!(f, "species can't store that much fat"),
+            RuleError::EmptyDeck => write!(f, "deck is out of cards"),
+            RuleError::Unplayable => write!(f, "rules can't be played by"),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn description(&self) -> &str {
+        match *self {
+            Error::Choice(_) => "invalid choice",
+            Error::Rule(_) => "broken rule",
+            Error::Decode(_) => "decode error",
+            Error::Timeout => "timed out",
+            Error::Io(ref e) => e.description(),
+            Error::Replay(_) => "invalid replay",
+            Error::Protocol(_) => "protocol error",
+        }
+    }
+}
+
+impl From<ChoiceError> for Error {
+    fn from(error: ChoiceError) -> Self {
+        Error::Choice(error)
+    }
+}
+
+impl From<ProtocolError> for Error {
+    fn from(error: ProtocolError) -> Self {
+        Error::Protocol(error)
+    }
+}
+
+impl From<RuleError> for Error {
+    fn from(error: RuleError) -> Self {
+        Error::Rule(error)
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Error::Io(error)
+    }
+}
+
+#[cfg(feature = "wire")]
+impl From<wire::Error> for Error {
+    fn from(error: wire::Error) -> Self {
+        match error {
+            wire::Error::Timeout => Error::Timeout,
+            wire::Error::Closed => {
+                Error::Io(io::Error::new(io::ErrorKind::UnexpectedEof, "connection closed"))
+            },
+            wire::Error::Io(e) => Error::Io(e),
+            wire::Error::Json(e) => Error::Decode(e),
+            wire::Error::Invalid(e) => Error::Decode(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_is_precise() {
+        let error = Error::from(ChoiceError::Trait(1, 3));
+
+        assert_eq!("invalid choice: trait 3 isn't on species 1", format!("{}", error));
+    }
+
+    #[cfg(feature = "wire")]
+    #[test]
+    fn from_wire_error() {
+        use evolution_wire as wire;
+
+        assert!(match Error::from(wire::Error::Timeout) { Error::Timeout => true, _ => false });
+        assert!(match Error::from(wire::Error::invalid("bad")) { Error::Decode(_) => true, _ => false });
+    }
+}
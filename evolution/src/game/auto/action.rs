@@ -1,20 +1,19 @@
+use error::{Error, ChoiceError};
 use game::*;
 use interact::*;
 
 impl<'a> Choose<ActionObservation, ActionChoice> for Auto<'a, ActionObservation, ActionChoice> {
-    fn choose(&mut self, observation: &ActionObservation) -> Result<Option<ActionChoice>, ()> {
-        let all_choices = &observation.choices();
-        trace!("all_choices@Choose<ActionChoice>.choose: {:?}", all_choices);
-
+    fn choose(&mut self, observation: &ActionObservation) -> Result<Option<ActionChoice>, Error> {
         let external_choice = self.0.choose(observation);
         trace!("external_choice@Choose<ActionChoice>.choose: {:?}", external_choice);
 
+        // The choice is checked against the observed rules, as the game
+        // checks it when the choice is applied.
         if let Ok(Some(ref c)) = external_choice {
             try!(c.validate(&observation));
-            // TODO: <needed> Waiting on proper impl of `Choices for ActionObservation`
-            // if !all_choices.contains(c) {
-            //     return Err(())
-            // }
+            if !observation.is_legal(c) {
+                return Err(Error::Choice(ChoiceError::Illegal))
+            }
         }
         external_choice
     }
@@ -22,6 +21,7 @@ impl<'a> Choose<ActionObservation, ActionChoice> for Auto<'a, ActionObservation,
 
 #[cfg(test)]
 mod tests {
+    use error::{Error, ChoiceError};
     use game::*;
     use interact::*;
     use object::*;
@@ -67,6 +67,41 @@ mod tests {
 
         let auto_choice = Auto(&mut choice).choose(&game.observe());
 
-        assert!(auto_choice.is_err());
+        match auto_choice {
+            Err(Error::Choice(ChoiceError::Species(1))) => {},
+            c => panic!("expected a bad species index, got {:?}", c),
+        }
+    }
+
+    #[test]
+    fn choice_breaking_the_rules_is_error() {
+        let mut game = GameState::with_rules(3, 1, RulesConfig::official()).unwrap();
+        game.players_mut()[0].domain_mut().add(Placement::Right);
+        for _ in 1..rules::OFFICIAL_MAX_POPULATION {
+            game.players_mut()[0].domain_mut()[0].breed().unwrap();
+        }
+        game.players_mut()[0].push_cards(vec![
+            Card::mock(2, Trait::Ambush),
+            Card::mock(1, Trait::Burrowing),
+        ]);
+        let mut choice = ActionChoice {
+            food_card: 0,
+            population_growths: vec![
+                Growth {
+                    species_index: 0,
+                    card_index: 1,
+                },
+            ],
+            body_growths: vec![],
+            boards: vec![],
+            traits: vec![],
+        };
+
+        let auto_choice = Auto(&mut choice).choose(&game.observe());
+
+        match auto_choice {
+            Err(Error::Choice(ChoiceError::Illegal)) => {},
+            c => panic!("expected an illegal choice, got {:?}", c),
+        }
     }
 }
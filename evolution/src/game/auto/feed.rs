@@ -1,8 +1,9 @@
+use error::Error;
 use game::*;
 use interact::*;
 
 impl<'a> Choose<FeedObservation, FeedChoice> for Auto<'a, FeedObservation, FeedChoice> {
-    fn choose(&mut self, observation: &FeedObservation) -> Result<Option<FeedChoice>, ()> {
+    fn choose(&mut self, observation: &FeedObservation) -> Result<Option<FeedChoice>, Error> {
         let all_choices = &observation.choices();
         trace!("all_choices@Choose<FeedChoice>.choose: {:?}", all_choices);
 
@@ -16,14 +17,10 @@ impl<'a> Choose<FeedObservation, FeedChoice> for Auto<'a, FeedObservation, FeedC
         match auto_choice {
             c @ Some(_) => Ok(c),
             None => {
+                // NOTE: The choice is validated by `step::Feed`, which keeps
+                // the offending choice when ejecting the player.
                 let external_choice = self.0.choose(observation);
                 trace!("external_choice@Choose<FeedChoice>.choose: {:?}", external_choice);
-                if let Ok(Some(ref c)) = external_choice {
-                    // TODO: <refactor> Should be `try!(external_choice.validate())`.
-                    if !all_choices.contains(c) {
-                        return Err(())
-                    }
-                }
                 external_choice
             }
         }
@@ -0,0 +1,170 @@
+use std::cmp;
+use std::time::{Duration, Instant};
+use error::Error;
+use interact::*;
+
+/// The number of seconds a remote player has for each choice, when the
+/// game's budget doesn't limit their choices.
+pub const DEFAULT_CHOOSE_SECS: u64 = 2;
+
+/// Limits on how long players may take to make their choices.
+///
+/// A player who takes longer than `per_choose` for a single choice, or who
+/// spends more than `per_game` choosing over a whole game, is ejected with
+/// an `Error::Timeout`.
+///
+/// The default budget is unlimited. Players over a `Channel` are given
+/// `DEFAULT_CHOOSE_SECS` for each choice the budget doesn't limit, so a
+/// client which stops answering can't stall the game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Budget {
+    /// The longest a single choice may take.
+    pub per_choose: Option<Duration>,
+    /// The total time a player may spend choosing in a game.
+    pub per_game: Option<Duration>,
+}
+
+impl Budget {
+    /// A budget without any limits.
+    pub fn unlimited() -> Self {
+        Budget {
+            per_choose: None,
+            per_game: None,
+        }
+    }
+
+    /// Returns the time limit for the next choice of a player who has
+    /// already spent `used` choosing this game.
+    pub fn limit(&self, used: Duration) -> Option<Duration> {
+        let remaining = self.per_game.map(|per_game| {
+            if used >= per_game {
+                Duration::new(0, 0)
+            } else {
+                per_game - used
+            }
+        });
+        match (self.per_choose, remaining) {
+            (Some(a), Some(b)) => Some(cmp::min(a, b)),
+            (a, None) => a,
+            (None, b) => b,
+        }
+    }
+}
+
+impl Default for Budget {
+    fn default() -> Self {
+        Budget::unlimited()
+    }
+}
+
+/// Measures how long a chooser takes to choose, failing any choice which
+/// takes longer than the limit.
+#[derive(Debug)]
+pub struct Timed<'a, C: 'a + Chooser> {
+    chooser: &'a mut C,
+    limit: Option<Duration>,
+    elapsed: Duration,
+}
+
+impl<'a, C: Chooser> Timed<'a, C> {
+    /// Wraps the chooser, giving it `limit` for each choice.
+    pub fn new(chooser: &'a mut C, limit: Option<Duration>) -> Self {
+        Timed {
+            chooser: chooser,
+            limit: limit,
+            elapsed: Duration::new(0, 0),
+        }
+    }
+
+    /// Returns the total time spent choosing so far.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+}
+
+impl<'a, O, Ch, C> Choose<O, Ch> for Timed<'a, C>
+    where O: Observation,
+          Ch: Choice,
+          C: Chooser + Choose<O, Ch>,
+{
+    fn choose(&mut self, observation: &O) -> Result<Option<Ch>, Error> {
+        if self.limit == Some(Duration::new(0, 0)) {
+            return Err(Error::Timeout)
+        }
+        self.chooser.time_limit(self.limit);
+        let start = Instant::now();
+        let result = self.chooser.choose(observation);
+        let elapsed = start.elapsed();
+        self.elapsed = self.elapsed + elapsed;
+        match self.limit {
+            Some(limit) if elapsed > limit => Err(Error::Timeout),
+            _ => result,
+        }
+    }
+}
+
+/// Returns the duration in whole milliseconds.
+pub fn as_millis(duration: Duration) -> u64 {
+    duration.as_secs() * 1000 + (duration.subsec_nanos() / 1000000) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use error::Error;
+    use game::*;
+    use object::*;
+
+    #[test]
+    fn limit_per_choose() {
+        let budget = Budget {
+            per_choose: Some(Duration::from_millis(100)),
+            per_game: None,
+        };
+
+        assert_eq!(Some(Duration::from_millis(100)), budget.limit(Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn limit_per_game() {
+        let budget = Budget {
+            per_choose: Some(Duration::from_millis(100)),
+            per_game: Some(Duration::from_millis(250)),
+        };
+
+        assert_eq!(Some(Duration::from_millis(100)), budget.limit(Duration::from_millis(0)));
+        assert_eq!(Some(Duration::from_millis(50)), budget.limit(Duration::from_millis(200)));
+        assert_eq!(Some(Duration::from_millis(0)), budget.limit(Duration::from_millis(300)));
+    }
+
+    #[test]
+    fn unlimited() {
+        assert_eq!(None, Budget::unlimited().limit(Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn default_is_unlimited() {
+        let game = game_with_players(3, &|_| {});
+
+        assert_eq!(Budget::unlimited(), Budget::default());
+        assert_eq!(Budget::unlimited(), game.budget());
+    }
+
+    #[test]
+    fn spent_game_budget_ejects() {
+        let mut game = game_with_players(3, &|player| {
+            player.domain_mut().add(Placement::Right);
+        });
+        game.board_mut().push_food(FoodToken);
+        game.set_budget(Budget {
+            per_choose: None,
+            per_game: Some(Duration::new(0, 0)),
+        });
+        game.players_mut()[0].domain_mut().add(Placement::Right);
+
+        game.step_feed().unwrap();
+
+        assert_eq!(1, game.kicked().len());
+        assert_eq!(format!("{}", Error::Timeout), game.kicked()[0].reason);
+    }
+}
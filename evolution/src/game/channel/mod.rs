@@ -1,4 +1,7 @@
+use std::time::Duration;
 use evolution_wire::{self as wire, ToWire, FromWire, Channel};
+use error::Error;
+use game::budget::DEFAULT_CHOOSE_SECS;
 use interact::*;
 
 impl Chooser for Channel {
@@ -11,28 +14,30 @@ impl Chooser for Channel {
     fn info(&self) -> Option<&str> {
         Some(self.info())
     }
+
+    fn time_limit(&mut self, limit: Option<Duration>) {
+        // Never wait forever on the other end.
+        let limit = limit.unwrap_or(Duration::from_secs(DEFAULT_CHOOSE_SECS));
+        if let Err(e) = self.set_timeout(Some(limit)) {
+            warn!("failed to set channel timeout: {}", e);
+        }
+    }
 }
 
 impl Choose<ActionObservation, ActionChoice> for Channel {
-    fn choose(&mut self, observation: &ActionObservation) -> Result<Option<ActionChoice>, ()> {
+    fn choose(&mut self, observation: &ActionObservation) -> Result<Option<ActionChoice>, Error> {
         let wire_observation: (wire::remote::LOB, wire::remote::LOB) = observation.to_wire();
         trace!("Channel.choose<(wire::remote::LOB, wire::remote::LOB)>");
-        let wire: wire::remote::Action4 = match self.call(&wire_observation) {
-            Ok(w) => w,
-            Err(_) => return Err(()),
-        };
-        ActionChoice::from_wire(wire).map(|c| Some(c))
+        let wire: wire::remote::Action4 = try!(self.call(&wire_observation));
+        Ok(Some(try!(ActionChoice::from_wire(wire))))
     }
 }
 
 impl Choose<FeedObservation, FeedChoice> for Channel {
-    fn choose(&mut self, observation: &FeedObservation) -> Result<Option<FeedChoice>, ()> {
+    fn choose(&mut self, observation: &FeedObservation) -> Result<Option<FeedChoice>, Error> {
         let wire_observation: wire::remote::State = observation.to_wire();
         trace!("Channel.choose<wire::remote::State>");
-        let wire = match self.call(&wire_observation) {
-            Ok(w) => w,
-            Err(_) => return Err(()),
-        };
-        FeedChoice::from_wire(wire).map(|c| Some(c))
+        let wire = try!(self.call(&wire_observation));
+        Ok(Some(try!(FeedChoice::from_wire(wire))))
     }
 }
@@ -1,30 +1,30 @@
 use std::ops::{Deref, DerefMut};
-use game::Game;
+use game::GameState;
 use game::context::*;
-use interact::*;
 use object::{FoodToken, Trait, Board as RealBoard};
 
 /// A game aware board.
-pub struct Board<'a, C: 'a + Chooser>(&'a mut Game<C>);
+pub struct Board<'a>(&'a mut GameState);
 
-impl<'a, C: Chooser> Board<'a, C> {
-    /// Create a new contextual board instance from a mutable `Game` object
+impl<'a> Board<'a> {
+    /// Create a new contextual board instance from a mutable `GameState` object
     /// reference.
     ///
     /// # Arguments
     ///
-    /// * `game` - A mutable reference to a `Game` instance.
+    /// * `game` - A mutable reference to a `GameState` instance.
     ///
     /// # Returns
     ///
-    /// A contextual `Board` instance referencing the given `Game` instance.
-    pub fn new(game: &'a mut Game<C>) -> Self {
+    /// A contextual `Board` instance referencing the given `GameState` instance.
+    pub fn new(game: &'a mut GameState) -> Self {
         Board(game)
     }
 
     /// Reveal the cards played to the board as food. Doing so will also clear
     /// the cards from the board and add or subtract the appropriate number of
-    /// `FoodToken`s.
+    /// `FoodToken`s. Returns what the species fed or killed by the revealed
+    /// traits.
     ///
     /// # Arguments
     ///
@@ -33,7 +33,7 @@ impl<'a, C: Chooser> Board<'a, C> {
     /// # Panics
     ///
     /// This function panics if there are no cards to reveal.
-    pub fn reveal(&mut self) {
+    pub fn reveal(&mut self) -> Outcome {
         // NOTE: If we had a method to take ownership of these cards,
         //       we could do some really elegant composition of drain
         //       and fold. Maybe even a "take_cards" method yielding a
@@ -60,8 +60,9 @@ impl<'a, C: Chooser> Board<'a, C> {
                 }
             }
         }
+        let max_population = self.context().rules().max_population;
         for (player_idx, species_idx) in fertiles {
-            Species::new(self.context_mut(), (player_idx, species_idx)).breed().ok();
+            Species::new(self.context_mut(), (player_idx, species_idx)).breed_up_to(max_population).ok();
         }
 
         let mut long_necks = vec![];
@@ -72,8 +73,22 @@ impl<'a, C: Chooser> Board<'a, C> {
                 }
             }
         }
+        let mut outcome = Outcome::new();
         for (player_idx, species_idx) in long_necks {
-            Species::new(self.context_mut(), (player_idx, species_idx)).feed();
+            outcome.append(Species::new(self.context_mut(), (player_idx, species_idx)).feed());
+        }
+
+        let mut traits = vec![];
+        for (player_idx, player) in self.context().players().iter().enumerate() {
+            for (species_idx, (species, _, _)) in player.domain().into_iter().enumerate() {
+                for &trait_type in species.traits() {
+                    traits.push((player_idx, species_idx, trait_type));
+                }
+            }
+        }
+        for (player_idx, species_idx, trait_type) in traits {
+            let mut species = Species::new(self.context_mut(), (player_idx, species_idx));
+            outcome.append(trait_type.effect().reveal(&mut species));
         }
 
         let mut all = vec![];
@@ -85,20 +100,21 @@ impl<'a, C: Chooser> Board<'a, C> {
         for (player_idx, species_idx) in all {
             Species::new(self.context_mut(), (player_idx, species_idx)).digest_fat();
         }
+        outcome
     }
 }
 
-impl<'a, C: Chooser> Context<'a, Game<C>> for Board<'a, C> {
-    fn context(&self) -> &Game<C> {
+impl<'a> Context<'a, GameState> for Board<'a> {
+    fn context(&self) -> &GameState {
         &self.0
     }
 
-    fn context_mut(&mut self) -> &mut Game<C> {
+    fn context_mut(&mut self) -> &mut GameState {
         &mut self.0
     }
 }
 
-impl<'a, C: Chooser> Deref for Board<'a, C> {
+impl<'a> Deref for Board<'a> {
     type Target = RealBoard;
 
     fn deref(&self) -> &Self::Target {
@@ -106,7 +122,7 @@ impl<'a, C: Chooser> Deref for Board<'a, C> {
     }
 }
 
-impl<'a, C: Chooser> DerefMut for Board<'a, C> {
+impl<'a> DerefMut for Board<'a> {
     fn deref_mut(&mut self) -> &mut Self::Target {
         self.context_mut().board_mut()
     }
@@ -127,12 +143,12 @@ mod tests {
             Card::mock(1, Trait::Carnivore),
         ]);
 
-        assert_eq!(0, game.board().food().len());
+        assert_eq!(0, game.board().food_count());
         assert_eq!(3, game.board().cards().unwrap().len());
 
         Board::new(&mut game).reveal();
 
-        assert_eq!(3, game.board().food().len());
+        assert_eq!(3, game.board().food_count());
         assert!(game.board().cards().is_none());
     }
 
@@ -174,15 +190,61 @@ mod tests {
             Card::mock(0, Trait::Ambush),
         ]);
 
-        assert_eq!(0, game.board().food().len());
-        assert_eq!(0, game.players()[0].domain()[0].food().len());
-        assert_eq!(0, game.players()[1].domain()[0].food().len());
+        assert_eq!(0, game.board().food_count());
+        assert_eq!(0, game.players()[0].domain()[0].food_count());
+        assert_eq!(0, game.players()[1].domain()[0].food_count());
+
+        Board::new(&mut game).reveal();
+
+        assert_eq!(0, game.board().food_count());
+        assert_eq!(1, game.players()[0].domain()[0].food_count());
+        assert_eq!(0, game.players()[1].domain()[0].food_count());
+    }
+
+    #[test]
+    fn migratory() {
+        let mut game = game_with_players(3, &|player| {
+            player.domain_mut().add(Placement::Right);
+            if player.id() == 1 {
+                player.domain_mut()[0].evolve(Trait::Migratory).unwrap();
+            }
+        });
+        game.board_mut().set_cards(vec![
+            Card::mock(2, Trait::Ambush),
+            Card::mock(0, Trait::Ambush),
+            Card::mock(0, Trait::Ambush),
+        ]);
+
+        Board::new(&mut game).reveal();
 
+        assert_eq!(2, game.players()[0].domain()[0].population());
+        assert_eq!(1, game.players()[1].domain()[0].population());
+
+        game.board_mut().set_cards(vec![Card::mock(0, Trait::Ambush)]);
         Board::new(&mut game).reveal();
 
-        assert_eq!(0, game.board().food().len());
-        assert_eq!(1, game.players()[0].domain()[0].food().len());
-        assert_eq!(0, game.players()[1].domain()[0].food().len());
+        assert_eq!(2, game.players()[0].domain()[0].population());
+    }
+
+    #[test]
+    fn mass_extinction() {
+        let mut game = game_with_players(3, &|player| {
+            player.domain_mut().add(Placement::Right);
+            for _ in 0..player.id() {
+                player.domain_mut()[0].breed().unwrap();
+            }
+            if player.id() == 2 {
+                player.domain_mut()[0].evolve(Trait::MassExtinction).unwrap();
+            }
+        });
+        game.board_mut().set_cards(vec![]);
+
+        let outcome = Board::new(&mut game).reveal();
+
+        assert_eq!(2, game.players()[0].domain()[0].population());
+        assert_eq!(3, game.players()[1].domain()[0].population());
+        assert_eq!(3, game.players()[2].domain()[0].population());
+        assert_eq!(&[Change::Killed { player: 3, species: 0, population: 3, food: 0 }], outcome.changes());
     }
 
     #[test]
@@ -206,17 +268,17 @@ mod tests {
             Card::mock(0, Trait::Ambush),
         ]);
 
-        assert_eq!(0, game.players()[0].domain()[0].food().len());
-        assert_eq!(2, game.players()[0].domain()[0].fat().len());
-        assert_eq!(0, game.players()[1].domain()[0].food().len());
-        assert_eq!(0, game.players()[1].domain()[0].fat().len());
+        assert_eq!(0, game.players()[0].domain()[0].food_count());
+        assert_eq!(2, game.players()[0].domain()[0].fat_count());
+        assert_eq!(0, game.players()[1].domain()[0].food_count());
+        assert_eq!(0, game.players()[1].domain()[0].fat_count());
 
         Board::new(&mut game).reveal();
 
-        assert_eq!(2, game.players()[0].domain()[0].food().len());
-        assert_eq!(0, game.players()[0].domain()[0].fat().len());
-        assert_eq!(0, game.players()[1].domain()[0].food().len());
-        assert_eq!(0, game.players()[1].domain()[0].fat().len());
+        assert_eq!(2, game.players()[0].domain()[0].food_count());
+        assert_eq!(0, game.players()[0].domain()[0].fat_count());
+        assert_eq!(0, game.players()[1].domain()[0].food_count());
+        assert_eq!(0, game.players()[1].domain()[0].fat_count());
     }
 
     #[test]
@@ -232,10 +294,10 @@ mod tests {
         ]);
         game.board_mut().push_foods((0..6).into_iter().map(|_| FoodToken).collect());
 
-        assert_eq!(6, game.board().food().len());
+        assert_eq!(6, game.board().food_count());
 
         Board::new(&mut game).reveal();
 
-        assert_eq!(1, game.board().food().len());
+        assert_eq!(1, game.board().food_count());
     }
 }
@@ -1,6 +1,6 @@
 /// Objects with mutable knowledge of the context they live in.
 ///
-/// We use this to represent a `Species` within the context of a `Game` for
+/// We use this to represent a `Species` within the context of a `GameState` for
 /// example. Here the mutable context is used to implement actions which
 /// effect other objects in the game outside ourselves.
 ///
@@ -27,6 +27,9 @@ pub trait Context<'a, T> {
 mod board;
 pub use self::board::Board;
 
+mod outcome;
+pub use self::outcome::{Outcome, Change};
+
 mod player;
 pub use self::player::Player;
 
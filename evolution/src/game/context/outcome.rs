@@ -0,0 +1,141 @@
+use std::fmt;
+use game::Id;
+use object::Card;
+
+/// A single change made to a game while revealing, feeding or fighting.
+///
+/// Species are named by the id of their player, and their index in that
+/// player's domain at the time of the change. An extinct species is removed
+/// from the domain right after it's `Extinct` change, shifting the species
+/// to it's right.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Change {
+    /// The species ate a food token from the watering hole.
+    Ate {
+        player: Id,
+        species: usize,
+    },
+    /// The species ate a token of meat from the food bank, which never runs
+    /// out.
+    AteMeat {
+        player: Id,
+        species: usize,
+    },
+    /// The species lost one of it's population, leaving `population`, and
+    /// lost the `food` it no longer had the population to hold, counting
+    /// it's fat when it went extinct.
+    Killed {
+        player: Id,
+        species: usize,
+        population: u64,
+        food: u64,
+    },
+    /// The species went extinct.
+    Extinct {
+        player: Id,
+        species: usize,
+    },
+    /// The player was dealt the cards for an extinct species.
+    Refunded {
+        player: Id,
+        cards: Vec<Card>,
+    },
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Change::Ate { player, species } => {
+                write!(f, "player {}'s species {} ate from the watering hole", player, species)
+            },
+            Change::AteMeat { player, species } => {
+                write!(f, "player {}'s species {} ate meat", player, species)
+            },
+            Change::Killed { player, species, population, food } => {
+                write!(f, "player {}'s species {} was killed down to {} population, losing {} food",
+                       player, species, population, food)
+            },
+            Change::Extinct { player, species } => {
+                write!(f, "player {}'s species {} went extinct", player, species)
+            },
+            Change::Refunded { player, ref cards } => {
+                write!(f, "player {} was dealt {} cards", player, cards.len())
+            },
+        }
+    }
+}
+
+/// Everything revealing, feeding or fighting did to a game, as the changes
+/// it made in the order it made them.
+///
+/// # Examples
+///
+/// ```rust
+/// use evolution::game::*;
+/// use evolution::game::context::{Change, Species};
+/// use evolution::object::*;
+///
+/// let mut state = GameState::with_seed(3, 1).unwrap();
+/// state.players_mut()[0].domain_mut().add(Placement::Right);
+/// state.board_mut().push_food(FoodToken);
+///
+/// let outcome = Species::new(&mut state, (0, 0)).feed();
+/// assert_eq!(&[Change::Ate { player: 1, species: 0 }], outcome.changes());
+/// assert_eq!(1, outcome.food_eaten());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Outcome(Vec<Change>);
+
+impl Outcome {
+    /// Creates an outcome without any changes.
+    pub fn new() -> Self {
+        Outcome(Vec::new())
+    }
+
+    /// Returns the changes, in the order they were made.
+    pub fn changes(&self) -> &[Change] {
+        &self.0
+    }
+
+    /// Returns `true` if nothing changed.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Adds a change made after all the others.
+    pub fn push(&mut self, change: Change) {
+        self.0.push(change);
+    }
+
+    /// Adds the changes of an outcome which came after this one.
+    pub fn append(&mut self, mut other: Outcome) {
+        self.0.append(&mut other.0);
+    }
+
+    /// Returns the number of food tokens taken from the watering hole.
+    pub fn food_eaten(&self) -> usize {
+        self.0.iter().filter(|c| match **c { Change::Ate { .. } => true, _ => false }).count()
+    }
+
+    /// Returns the number of meat tokens eaten from the food bank.
+    pub fn meat_eaten(&self) -> usize {
+        self.0.iter().filter(|c| match **c { Change::AteMeat { .. } => true, _ => false }).count()
+    }
+
+    /// Returns the number of food tokens lost by killed species.
+    pub fn food_lost(&self) -> u64 {
+        self.0.iter().map(|c| match *c {
+            Change::Killed { food, .. } => food,
+            _ => 0,
+        }).sum()
+    }
+
+    /// Returns the player id and species index of every species which went
+    /// extinct, in order.
+    pub fn extinctions(&self) -> Vec<(Id, usize)> {
+        self.0.iter().filter_map(|c| match *c {
+            Change::Extinct { player, species } => Some((player, species)),
+            _ => None,
+        }).collect()
+    }
+}
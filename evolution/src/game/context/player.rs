@@ -1,28 +1,31 @@
 use std::ops::{Deref, DerefMut};
 use std::collections::HashMap;
 use ext::AroundFromMut;
-use game::Game;
+use game::{GameState, RulesMode};
 use game::context::*;
-use interact::*;
-use object::{CARDS_PER_EXTINCTION, Card, Trait, Player as RealPlayer};
+use object::{Card, Trait, Player as RealPlayer};
 
 /// A game aware player.
-pub struct Player<'a, C: 'a + Chooser>(&'a mut Game<C>, usize);
+pub struct Player<'a>(&'a mut GameState, usize);
 
-impl<'a, C: Chooser> Player<'a, C> {
-    pub fn new(game: &'a mut Game<C>, player_idx: usize) -> Self {
+impl<'a> Player<'a> {
+    pub fn new(game: &'a mut GameState, player_idx: usize) -> Self {
         Player(game, player_idx)
     }
 
-    /// Give this player `CARDS_PER_EXTINCTION` cards.
-    pub fn refund(&mut self) {
+    /// Give this player the rules' `cards_per_extinction` cards, or what's
+    /// left of the deck.
+    pub fn refund(&mut self) -> Outcome {
         let mut cards = vec![];
-        for _ in 0..CARDS_PER_EXTINCTION {
+        for _ in 0..self.context().rules().cards_per_extinction {
             if let Some(card) = self.context_mut().deal() {
                 cards.push(card);
             }
         }
+        let mut outcome = Outcome::new();
+        outcome.push(Change::Refunded { player: self.id(), cards: cards.clone() });
         self.push_cards(cards);
+        outcome
     }
 
     // Get some cards from a player as a map
@@ -37,8 +40,8 @@ impl<'a, C: Chooser> Player<'a, C> {
     }
 
     /// Trigger scavenging for all applicable species in this game, starting
-    /// with this player.
-    pub fn scavenge(&mut self) {
+    /// with this player, returning what they ate.
+    pub fn scavenge(&mut self) -> Outcome {
         // TODO: The cool iterator is one over a game yielding our Player and Species.
         let player_idx = self.1;
         let mut scavengers = vec![];
@@ -50,23 +53,30 @@ impl<'a, C: Chooser> Player<'a, C> {
             }
         }
 
+        let mode = self.context().rules().mode;
+        let mut outcome = Outcome::new();
         for (player_idx, species_idx) in scavengers {
-            Species::new(self.context_mut(), (player_idx, species_idx)).feed();
+            let mut species = Species::new(self.context_mut(), (player_idx, species_idx));
+            outcome.append(match mode {
+                RulesMode::Course => species.feed(),
+                RulesMode::Official => species.eat_meat(1),
+            });
         }
+        outcome
     }
 }
 
-impl<'a, C: Chooser> Context<'a, Game<C>> for Player<'a, C> {
-    fn context(&self) -> &Game<C> {
+impl<'a> Context<'a, GameState> for Player<'a> {
+    fn context(&self) -> &GameState {
         &self.0
     }
 
-    fn context_mut(&mut self) -> &mut Game<C> {
+    fn context_mut(&mut self) -> &mut GameState {
         &mut self.0
     }
 }
 
-impl<'a, C: Chooser> Deref for Player<'a, C> {
+impl<'a> Deref for Player<'a> {
     type Target = RealPlayer;
 
     fn deref(&self) -> &Self::Target {
@@ -74,7 +84,7 @@ impl<'a, C: Chooser> Deref for Player<'a, C> {
     }
 }
 
-impl<'a, C: Chooser> DerefMut for Player<'a, C> {
+impl<'a> DerefMut for Player<'a> {
     fn deref_mut(&mut self) -> &mut Self::Target {
         &mut self.0.players_mut()[self.1]
     }
@@ -129,14 +139,14 @@ mod tests {
         game.board_mut().push_food(FoodToken);
         game.board_mut().push_food(FoodToken);
 
-        assert_eq!(0, game.players()[0].domain()[0].food().len());
-        assert_eq!(0, game.players()[1].domain()[0].food().len());
-        assert_eq!(0, game.players()[2].domain()[0].food().len());
+        assert_eq!(0, game.players()[0].domain()[0].food_count());
+        assert_eq!(0, game.players()[1].domain()[0].food_count());
+        assert_eq!(0, game.players()[2].domain()[0].food_count());
 
         Player::new(&mut game, 0).scavenge();
 
-        assert_eq!(1, game.players()[0].domain()[0].food().len());
-        assert_eq!(1, game.players()[1].domain()[0].food().len());
-        assert_eq!(0, game.players()[2].domain()[0].food().len());
+        assert_eq!(1, game.players()[0].domain()[0].food_count());
+        assert_eq!(1, game.players()[1].domain()[0].food_count());
+        assert_eq!(0, game.players()[2].domain()[0].food_count());
     }
 }
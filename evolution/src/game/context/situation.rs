@@ -1,75 +1,126 @@
-use game::Game;
+use error::Error;
+use game::{GameState, RulesMode};
 use game::context::*;
-use interact::*;
 use object::Trait;
 
 /// An attack within a game.
-pub struct Situation<'a, C: 'a + Chooser>(&'a mut Game<C>, usize, usize, usize, usize);
+pub struct Situation<'a>(&'a mut GameState, usize, usize, usize, usize);
 
-impl<'a, C: Chooser> Situation<'a, C> {
+impl<'a> Situation<'a> {
     /// Create a new instance.
     ///
     /// # Arguments
-    /// `game`: The `Game` instance to mutate.
+    /// `game`: The `GameState` instance to mutate.
     /// `indices`: A tuple of `(i, j, k, l)` where `i` is the index of the
     ///  currently attacking player, `j` is the carnivorous species belonging
     ///  to that player, `k` is the defending player, and `l` is the
     ///  defending species belonging to that player,
-    pub fn new(game: &'a mut Game<C>, indices: (usize, usize, usize, usize)) -> Self {
+    pub fn new(game: &'a mut GameState, indices: (usize, usize, usize, usize)) -> Self {
         let (us_idx, attacker_idx, them_idx, defender_idx) = indices;
         Situation(game, us_idx, attacker_idx, them_idx, defender_idx)
     }
 
     /// The attacking player.
-    pub fn us(&mut self) -> Player<C> {
+    pub fn us(&mut self) -> Player {
         Player::new(self.0, self.1)
     }
 
     /// The attacking species.
-    pub fn attacker(&mut self) -> Species<C> {
+    pub fn attacker(&mut self) -> Species {
         Species::new(self.0, (self.1, self.2))
     }
 
     /// The defending player.
-    pub fn them(&mut self) -> Player<C> {
+    pub fn them(&mut self) -> Player {
         Player::new(self.0, self.3)
     }
 
     /// The defending species.
-    pub fn defender(&mut self) -> Species<C> {
+    pub fn defender(&mut self) -> Species {
         Species::new(self.0, (self.3, self.4))
     }
 
     /// Apply the effects of an attack of the attacker to the defender to the
-    /// game.
-    pub fn fight(&mut self) -> Result<(), ()> {
-        let attacker_extinct = if self.defender().has_trait(Trait::Horns) {
-            try!(self.attacker().kill())
+    /// game, returning every change the attack made, in order.
+    ///
+    /// A defender with horns kills one of the attacker first. The defender
+    /// loses one of it's population, and it's player is refunded if it went
+    /// extinct. The attacker then eats, or it's player is refunded if it
+    /// went extinct, and finally the scavengers eat.
+    ///
+    /// Under the official rules an attacker with intelligence it didn't need
+    /// to get past a defence negates the defender's horns instead. Since
+    /// scavengers only eat when a carnivore eats, they don't eat when the
+    /// horns made the attacker extinct.
+    pub fn fight(&mut self) -> Result<Outcome, Error> {
+        let mut outcome = Outcome::new();
+        let meat = self.defender().body_size();
+        let mode = self.context().rules().mode;
+        let horns = self.defender().has_trait(Trait::Horns) &&
+                    !(mode == RulesMode::Official && self.outwits_horns());
+        let attacker_extinct = if horns {
+            let killed = try!(self.attacker().kill());
+            let extinct = !killed.extinctions().is_empty();
+            outcome.append(killed);
+            extinct
         } else {
             false
         };
 
-        if try!(self.defender().kill()) {
-            self.them().refund();
+        // Species to the right of an extinct species of the same player
+        // shift left by one.
+        if attacker_extinct && self.1 == self.3 && self.2 < self.4 {
+            self.4 -= 1;
+        }
+
+        let killed = try!(self.defender().kill());
+        let defender_extinct = !killed.extinctions().is_empty();
+        outcome.append(killed);
+        if defender_extinct {
+            outcome.append(self.them().refund());
+            if self.1 == self.3 && self.4 < self.2 {
+                self.2 -= 1;
+            }
         }
 
         if attacker_extinct {
-            self.us().refund();
+            outcome.append(self.us().refund());
         } else {
-            self.attacker().feed();
+            outcome.append(match mode {
+                RulesMode::Course => self.attacker().feed(),
+                RulesMode::Official => self.attacker().eat_meat(meat),
+            });
+        }
+
+        if !(attacker_extinct && mode == RulesMode::Official) {
+            outcome.append(self.us().scavenge());
         }
+        Ok(outcome)
+    }
 
-        self.us().scavenge();
-        Ok(())
+    /// Returns true if the attacker is intelligent and no trait of the
+    /// defender or it's neighbors needs outwitting, leaving the intelligence
+    /// free for the horns.
+    fn outwits_horns(&self) -> bool {
+        let game = self.context();
+        let attacker = &game.players()[self.1].domain()[self.2];
+        let domain = game.players()[self.3].domain();
+        match domain.into_iter().nth(self.4) {
+            Some((defender, left, right)) => {
+                attacker.has_trait(Trait::Intelligent) &&
+                attacker.explain_attack(defender, left, right).blocks().is_empty()
+            },
+            None => false,
+        }
     }
 }
 
-impl<'a, C: Chooser> Context<'a, Game<C>> for Situation<'a, C> {
-    fn context(&self) -> &Game<C> {
+impl<'a> Context<'a, GameState> for Situation<'a> {
+    fn context(&self) -> &GameState {
         &self.0
     }
 
-    fn context_mut(&mut self) -> &mut Game<C> {
+    fn context_mut(&mut self) -> &mut GameState {
         &mut self.0
     }
 }
@@ -96,15 +147,15 @@ mod tests {
         });
         game.board_mut().push_food(FoodToken);
 
-        assert_eq!(1, game.board().food().len());
-        assert_eq!(0, game.players()[0].domain()[0].food().len());
+        assert_eq!(1, game.board().food_count());
+        assert_eq!(0, game.players()[0].domain()[0].food_count());
         assert_eq!(1, game.players()[1].domain().len());
         assert_eq!(0, game.players()[1].hand().len());
 
         Situation::new(&mut game, (0, 0, 1, 0)).fight().unwrap();
 
-        assert_eq!(0, game.board().food().len());
-        assert_eq!(1, game.players()[0].domain()[0].food().len());
+        assert_eq!(0, game.board().food_count());
+        assert_eq!(1, game.players()[0].domain()[0].food_count());
         assert_eq!(0, game.players()[1].domain().len());
         assert_eq!(2, game.players()[1].hand().len());
     }
@@ -126,7 +177,7 @@ mod tests {
         });
         game.board_mut().push_food(FoodToken);
 
-        assert_eq!(1, game.board().food().len());
+        assert_eq!(1, game.board().food_count());
         assert_eq!(1, game.players()[0].domain().len());
         assert_eq!(0, game.players()[0].hand().len());
         assert_eq!(1, game.players()[1].domain().len());
@@ -134,13 +185,49 @@ mod tests {
 
         Situation::new(&mut game, (0, 0, 1, 0)).fight().unwrap();
 
-        assert_eq!(1, game.board().food().len());
+        assert_eq!(1, game.board().food_count());
         assert_eq!(0, game.players()[0].domain().len());
         assert_eq!(2, game.players()[0].hand().len());
         assert_eq!(0, game.players()[1].domain().len());
         assert_eq!(2, game.players()[1].hand().len());
     }
 
+    #[test]
+    fn horns_outcome() {
+        let mut game = game_with_players(3, &|player| {
+            player.domain_mut().add(Placement::Right);
+            match player.id() {
+                1 => {
+                    player.domain_mut()[0].evolve(Trait::Carnivore).unwrap();
+                    player.domain_mut()[0].breed().unwrap();
+                },
+                2 => {
+                    player.domain_mut()[0].evolve(Trait::Horns).unwrap();
+                    player.domain_mut()[0].eat(FoodToken).unwrap();
+                },
+                _ => {
+                    player.domain_mut()[0].evolve(Trait::Scavenger).unwrap();
+                },
+            }
+        });
+        game.board_mut().push_food(FoodToken);
+        game.board_mut().push_food(FoodToken);
+        let refund = game.deck()[0..2].to_vec();
+
+        let outcome = Situation::new(&mut game, (0, 0, 1, 0)).fight().unwrap();
+
+        assert_eq!(&[
+            Change::Killed { player: 1, species: 0, population: 1, food: 0 },
+            Change::Killed { player: 2, species: 0, population: 0, food: 1 },
+            Change::Extinct { player: 2, species: 0 },
+            Change::Refunded { player: 2, cards: refund },
+            Change::Ate { player: 1, species: 0 },
+            Change::Ate { player: 3, species: 0 },
+        ], outcome.changes());
+        assert_eq!(2, outcome.food_eaten());
+        assert_eq!(vec![(2, 0)], outcome.extinctions());
+    }
+
     #[test]
     fn scavenge() {
         let mut game = game_with_players(3, &|player| {
@@ -159,17 +246,176 @@ mod tests {
         game.board_mut().push_food(FoodToken);
         game.board_mut().push_food(FoodToken);
 
-        assert_eq!(3, game.board().food().len());
+        assert_eq!(3, game.board().food_count());
         assert_eq!(2, game.players()[1].domain().len());
         assert_eq!(2, game.players()[2].domain().len());
 
         Situation::new(&mut game, (0, 0, 1, 1)).fight().unwrap();
 
-        assert_eq!(0, game.board().food().len());
+        assert_eq!(0, game.board().food_count());
         assert_eq!(1, game.players()[1].domain().len());
         assert_eq!(2, game.players()[2].domain().len());
-        assert_eq!(1, game.players()[0].domain()[0].food().len());
-        assert_eq!(1, game.players()[1].domain()[0].food().len());
-        assert_eq!(1, game.players()[2].domain()[0].food().len());
+        assert_eq!(1, game.players()[0].domain()[0].food_count());
+        assert_eq!(1, game.players()[1].domain()[0].food_count());
+        assert_eq!(1, game.players()[2].domain()[0].food_count());
+    }
+
+    #[test]
+    fn official_meat() {
+        let mut game = game_with_players(3, &|player| {
+            player.domain_mut().add(Placement::Right);
+            match player.id() {
+                1 => {
+                    player.domain_mut()[0].evolve(Trait::Carnivore).unwrap();
+                    player.domain_mut()[0].breed().unwrap();
+                    player.domain_mut()[0].breed().unwrap();
+                },
+                2 => {
+                    player.domain_mut()[0].breed().unwrap();
+                    player.domain_mut()[0].grow().unwrap();
+                    player.domain_mut()[0].grow().unwrap();
+                },
+                _ => {
+                    player.domain_mut()[0].evolve(Trait::Scavenger).unwrap();
+                },
+            }
+        });
+        game.rules.mode = RulesMode::Official;
+        game.board_mut().push_food(FoodToken);
+
+        Situation::new(&mut game, (0, 0, 1, 0)).fight().unwrap();
+
+        assert_eq!(1, game.board().food_count());
+        assert_eq!(2, game.players()[0].domain()[0].food_count());
+        assert_eq!(1, game.players()[1].domain()[0].population());
+        assert_eq!(1, game.players()[2].domain()[0].food_count());
+    }
+
+    #[test]
+    fn official_horns() {
+        let mut game = game_with_players(3, &|player| {
+            player.domain_mut().add(Placement::Right);
+            match player.id() {
+                1 => player.domain_mut()[0].evolve(Trait::Carnivore).unwrap(),
+                2 => player.domain_mut()[0].evolve(Trait::Horns).unwrap(),
+                _ => player.domain_mut()[0].evolve(Trait::Scavenger).unwrap(),
+            }
+        });
+        game.rules.mode = RulesMode::Official;
+
+        let outcome = Situation::new(&mut game, (0, 0, 1, 0)).fight().unwrap();
+
+        assert_eq!(0, game.players()[0].domain().len());
+        assert_eq!(0, game.players()[1].domain().len());
+        assert_eq!(0, game.players()[2].domain()[0].food_count());
+        assert_eq!(0, outcome.food_eaten());
+    }
+
+    #[test]
+    fn official_intelligence_negates_horns() {
+        let setup = |player: &mut ::object::Player| {
+            player.domain_mut().add(Placement::Right);
+            match player.id() {
+                1 => {
+                    player.domain_mut()[0].evolve(Trait::Carnivore).unwrap();
+                    player.domain_mut()[0].evolve(Trait::Intelligent).unwrap();
+                },
+                2 => {
+                    player.domain_mut()[0].evolve(Trait::Horns).unwrap();
+                    player.domain_mut()[0].breed().unwrap();
+                    player.domain_mut()[0].grow().unwrap();
+                },
+                _ => {},
+            }
+        };
+        let mut course = game_with_players(3, &setup);
+        let mut official = game_with_players(3, &setup);
+        official.rules.mode = RulesMode::Official;
+
+        Situation::new(&mut course, (0, 0, 1, 0)).fight().unwrap();
+        Situation::new(&mut official, (0, 0, 1, 0)).fight().unwrap();
+
+        assert_eq!(0, course.players()[0].domain().len());
+        assert_eq!(1, official.players()[0].domain()[0].population());
+        assert_eq!(1, official.players()[0].domain()[0].food_count());
+        assert_eq!(1, official.players()[1].domain()[0].population());
+    }
+
+    #[test]
+    fn official_horns_when_intelligence_is_needed() {
+        let mut game = game_with_players(3, &|player| {
+            player.domain_mut().add(Placement::Right);
+            match player.id() {
+                1 => {
+                    player.domain_mut()[0].evolve(Trait::Carnivore).unwrap();
+                    player.domain_mut()[0].evolve(Trait::Intelligent).unwrap();
+                    player.domain_mut()[0].breed().unwrap();
+                },
+                2 => {
+                    player.domain_mut()[0].evolve(Trait::Horns).unwrap();
+                    player.domain_mut()[0].evolve(Trait::Climbing).unwrap();
+                    player.domain_mut()[0].breed().unwrap();
+                },
+                _ => {},
+            }
+        });
+        game.rules.mode = RulesMode::Official;
+
+        Situation::new(&mut game, (0, 0, 1, 0)).fight().unwrap();
+
+        assert_eq!(1, game.players()[0].domain()[0].population());
+        assert_eq!(1, game.players()[1].domain()[0].population());
+    }
+
+    #[test]
+    fn official_ambush() {
+        let mut game = game_with_players(3, &|player| {
+            match player.id() {
+                1 => {
+                    player.domain_mut().add(Placement::Right);
+                    player.domain_mut()[0].evolve(Trait::Carnivore).unwrap();
+                    player.domain_mut()[0].evolve(Trait::Ambush).unwrap();
+                    player.domain_mut()[0].breed().unwrap();
+                },
+                2 => {
+                    player.domain_mut().add(Placement::Right);
+                    player.domain_mut().add(Placement::Right);
+                    player.domain_mut()[0].evolve(Trait::WarningCall).unwrap();
+                    player.domain_mut()[1].evolve(Trait::Horns).unwrap();
+                    player.domain_mut()[1].grow().unwrap();
+                },
+                _ => {},
+            }
+        });
+        game.rules.mode = RulesMode::Official;
+
+        {
+            let domain = game.players()[1].domain();
+            let (defender, left, right) = domain.into_iter().nth(1).unwrap();
+            assert!(game.players()[0].domain()[0].can_attack(defender, left, right));
+        }
+        Situation::new(&mut game, (0, 0, 1, 1)).fight().unwrap();
+
+        assert_eq!(1, game.players()[0].domain()[0].population());
+        assert_eq!(1, game.players()[0].domain()[0].food_count());
+        assert_eq!(1, game.players()[1].domain().len());
+    }
+
+    #[test]
+    fn fight_own_species_to_the_left() {
+        let mut game = game_with_players(3, &|player| {
+            if player.id() == 1 {
+                player.domain_mut().add(Placement::Right);
+                player.domain_mut().add(Placement::Right);
+                player.domain_mut()[1].evolve(Trait::Carnivore).unwrap();
+            }
+        });
+        game.board_mut().push_food(FoodToken);
+
+        Situation::new(&mut game, (0, 1, 0, 0)).fight().unwrap();
+
+        assert_eq!(0, game.board().food_count());
+        assert_eq!(1, game.players()[0].domain().len());
+        assert_eq!(1, game.players()[0].domain()[0].food_count());
     }
 }
@@ -1,26 +1,37 @@
 use std::ops::{Deref, DerefMut};
-use game::Game;
+use error::Error;
+use game::{GameState, Id};
 use game::context::*;
-use interact::*;
-use object::{Trait, Placement, Species as RealSpecies};
+use object::{Trait, Placement, FoodToken, Species as RealSpecies};
 
 /// A game aware species.
 #[derive(Debug)]
-pub struct Species<'a, C: 'a + Chooser>(&'a mut Game<C>, usize, usize);
+pub struct Species<'a>(&'a mut GameState, usize, usize);
 
-impl<'a, C: Chooser> Species<'a, C> {
-    pub fn new(game: &'a mut Game<C>, indices: (usize, usize)) -> Self {
+impl<'a> Species<'a> {
+    pub fn new(game: &'a mut GameState, indices: (usize, usize)) -> Self {
         let (player_idx, species_idx) = indices;
         Species(game, player_idx, species_idx)
     }
 
+    /// The indices of this species' player, and of this species within
+    /// their domain.
+    pub fn indices(&self) -> (usize, usize) {
+        (self.1, self.2)
+    }
+
+    /// The id of this species' player.
+    pub fn player_id(&self) -> Id {
+        self.0.players()[self.1].id()
+    }
+
     /// This species' player.
-    pub fn player(&mut self) -> Player<C> {
+    pub fn player(&mut self) -> Player {
         Player::new(self.0, self.1)
     }
 
     /// This species' neighbor on the given placement.
-    pub fn neighbor(&mut self, placement: Placement) -> Option<Species<C>> {
+    pub fn neighbor(&mut self, placement: Placement) -> Option<Species> {
         let player_idx = self.1;
         let neighbor_idx = match placement {
             Placement::Right => self.2 + 1,
@@ -33,20 +44,24 @@ impl<'a, C: Chooser> Species<'a, C> {
         }
     }
 
-    /// Feed this species.
-    pub fn feed(&mut self) {
+    /// Feed this species, returning the food it ate, and the food eaten by
+    /// the species it cooperates with.
+    pub fn feed(&mut self) -> Outcome {
+        let mut outcome = Outcome::new();
         let mut acc = 0;
         for _ in 0..self.eats() {
             if !self.eat() {
                 break;
             } else {
+                outcome.push(Change::Ate { player: self.player_id(), species: self.2 });
                 acc += 1;
             }
         }
 
         for _ in 0..acc {
-            self.cooperate();
+            outcome.append(self.cooperate());
         }
+        outcome
     }
 
     /// Tell this species to eat, returning false if they can't.
@@ -66,13 +81,36 @@ impl<'a, C: Chooser> Species<'a, C> {
         }
     }
 
-    /// Trigger cooperation for this species if applicable.
-    pub fn cooperate(&mut self) {
+    /// Feed this species the given amount of meat from the food bank, which
+    /// unlike the watering hole never runs out. Meat past what the species
+    /// can eat is lost.
+    pub fn eat_meat(&mut self, amount: u64) -> Outcome {
+        let mut outcome = Outcome::new();
+        let mut acc = 0;
+        for _ in 0..amount {
+            if RealSpecies::eat(&mut **self, FoodToken).is_err() {
+                break;
+            } else {
+                outcome.push(Change::AteMeat { player: self.player_id(), species: self.2 });
+                acc += 1;
+            }
+        }
+
+        for _ in 0..acc {
+            outcome.append(self.cooperate());
+        }
+        outcome
+    }
+
+    /// Trigger cooperation for this species if applicable, returning what
+    /// the neighbor ate.
+    pub fn cooperate(&mut self) -> Outcome {
         if self.has_trait(Trait::Cooperation) {
             if let Some(mut neighbor) = self.neighbor(Placement::Right) {
-                neighbor.feed();
+                return neighbor.feed()
             }
         }
+        Outcome::new()
     }
 
     /// Store the given amount of food into this species' fat.
@@ -82,24 +120,39 @@ impl<'a, C: Chooser> Species<'a, C> {
         // TODO: Push food back to board in the case of an error.
     }
 
-    /// Kill a member of this species.
-    pub fn kill(&mut self) -> Result<bool, ()> {
-        let species_idx = self.2;
-        self.player().domain_mut().kill(species_idx)
+    /// Kill a member of this species, removing it from it's player's domain
+    /// if it goes extinct.
+    pub fn kill(&mut self) -> Result<Outcome, Error> {
+        let (player, species_idx) = (self.player_id(), self.2);
+        let food = self.food_count();
+        let fat = self.fat_count();
+        let mut outcome = Outcome::new();
+        if try!(self.player().domain_mut().kill(species_idx)) {
+            outcome.push(Change::Killed { player: player, species: species_idx, population: 0, food: food + fat });
+            outcome.push(Change::Extinct { player: player, species: species_idx });
+        } else {
+            outcome.push(Change::Killed {
+                player: player,
+                species: species_idx,
+                population: self.population(),
+                food: food - self.food_count(),
+            });
+        }
+        Ok(outcome)
     }
 }
 
-impl<'a, C: Chooser> Context<'a, Game<C>> for Species<'a, C> {
-    fn context(&self) -> &Game<C> {
+impl<'a> Context<'a, GameState> for Species<'a> {
+    fn context(&self) -> &GameState {
         &self.0
     }
 
-    fn context_mut(&mut self) -> &mut Game<C> {
+    fn context_mut(&mut self) -> &mut GameState {
         &mut self.0
     }
 }
 
-impl<'a, C: Chooser> Deref for Species<'a, C> {
+impl<'a> Deref for Species<'a> {
     type Target = RealSpecies;
 
     fn deref(&self) -> &Self::Target {
@@ -107,7 +160,7 @@ impl<'a, C: Chooser> Deref for Species<'a, C> {
     }
 }
 
-impl<'a, C: Chooser> DerefMut for Species<'a, C> {
+impl<'a> DerefMut for Species<'a> {
     fn deref_mut(&mut self) -> &mut Self::Target {
         &mut self.0.players_mut()[self.1].domain_mut()[self.2]
     }
@@ -158,13 +211,13 @@ mod tests {
         });
         game.board_mut().push_food(FoodToken);
 
-        assert_eq!(1, game.board().food().len());
-        assert_eq!(0, game.players()[1].domain()[0].food().len());
+        assert_eq!(1, game.board().food_count());
+        assert_eq!(0, game.players()[1].domain()[0].food_count());
 
         Species::new(&mut game, (1, 0)).feed();
 
-        assert_eq!(0, game.board().food().len());
-        assert_eq!(1, game.players()[1].domain()[0].food().len());
+        assert_eq!(0, game.board().food_count());
+        assert_eq!(1, game.players()[1].domain()[0].food_count());
     }
 
     #[test]
@@ -181,13 +234,13 @@ mod tests {
         game.board_mut().push_food(FoodToken);
         game.board_mut().push_food(FoodToken);
 
-        assert_eq!(3, game.board().food().len());
-        assert_eq!(0, game.players()[1].domain()[0].food().len());
+        assert_eq!(3, game.board().food_count());
+        assert_eq!(0, game.players()[1].domain()[0].food_count());
 
         Species::new(&mut game, (1, 0)).feed();
 
-        assert_eq!(1, game.board().food().len());
-        assert_eq!(2, game.players()[1].domain()[0].food().len());
+        assert_eq!(1, game.board().food_count());
+        assert_eq!(2, game.players()[1].domain()[0].food_count());
     }
 
     #[test]
@@ -205,17 +258,22 @@ mod tests {
         game.board_mut().push_food(FoodToken);
         game.board_mut().push_food(FoodToken);
 
-        assert_eq!(3, game.board().food().len());
-        assert_eq!(0, game.players()[1].domain()[0].food().len());
-        assert_eq!(0, game.players()[1].domain()[1].food().len());
-        assert_eq!(0, game.players()[1].domain()[2].food().len());
-
-        Species::new(&mut game, (1, 0)).feed();
-
-        assert_eq!(0, game.board().food().len());
-        assert_eq!(1, game.players()[1].domain()[0].food().len());
-        assert_eq!(1, game.players()[1].domain()[1].food().len());
-        assert_eq!(1, game.players()[1].domain()[2].food().len());
+        assert_eq!(3, game.board().food_count());
+        assert_eq!(0, game.players()[1].domain()[0].food_count());
+        assert_eq!(0, game.players()[1].domain()[1].food_count());
+        assert_eq!(0, game.players()[1].domain()[2].food_count());
+
+        let outcome = Species::new(&mut game, (1, 0)).feed();
+
+        assert_eq!(0, game.board().food_count());
+        assert_eq!(1, game.players()[1].domain()[0].food_count());
+        assert_eq!(1, game.players()[1].domain()[1].food_count());
+        assert_eq!(1, game.players()[1].domain()[2].food_count());
+        assert_eq!(&[
+            Change::Ate { player: 2, species: 0 },
+            Change::Ate { player: 2, species: 1 },
+            Change::Ate { player: 2, species: 2 },
+        ], outcome.changes());
     }
 
     #[test]
@@ -235,19 +293,19 @@ mod tests {
         game.board_mut().push_food(FoodToken);
         game.board_mut().push_food(FoodToken);
 
-        assert_eq!(3, game.board().food().len());
-        assert_eq!(0, game.players()[1].domain()[0].food().len());
-        assert_eq!(0, game.players()[1].domain()[1].food().len());
-        assert_eq!(0, game.players()[1].domain()[2].food().len());
-        assert_eq!(0, game.players()[1].domain()[3].food().len());
+        assert_eq!(3, game.board().food_count());
+        assert_eq!(0, game.players()[1].domain()[0].food_count());
+        assert_eq!(0, game.players()[1].domain()[1].food_count());
+        assert_eq!(0, game.players()[1].domain()[2].food_count());
+        assert_eq!(0, game.players()[1].domain()[3].food_count());
 
         Species::new(&mut game, (1, 0)).feed();
 
-        assert_eq!(0, game.board().food().len());
-        assert_eq!(1, game.players()[1].domain()[0].food().len());
-        assert_eq!(1, game.players()[1].domain()[1].food().len());
-        assert_eq!(1, game.players()[1].domain()[2].food().len());
-        assert_eq!(0, game.players()[1].domain()[3].food().len());
+        assert_eq!(0, game.board().food_count());
+        assert_eq!(1, game.players()[1].domain()[0].food_count());
+        assert_eq!(1, game.players()[1].domain()[1].food_count());
+        assert_eq!(1, game.players()[1].domain()[2].food_count());
+        assert_eq!(0, game.players()[1].domain()[3].food_count());
     }
 
     #[test]
@@ -264,15 +322,15 @@ mod tests {
         });
         game.board_mut().push_foods(vec![FoodToken, FoodToken, FoodToken]);
 
-        assert_eq!(1, game.players()[1].domain()[0].food().len());
-        assert_eq!(0, game.players()[1].domain()[1].food().len());
-        assert_eq!(3, game.board().food().len());
+        assert_eq!(1, game.players()[1].domain()[0].food_count());
+        assert_eq!(0, game.players()[1].domain()[1].food_count());
+        assert_eq!(3, game.board().food_count());
 
         Species::new(&mut game, (1, 0)).feed();
 
-        assert_eq!(2, game.players()[1].domain()[0].food().len());
-        assert_eq!(1, game.players()[1].domain()[1].food().len());
-        assert_eq!(1, game.board().food().len());
+        assert_eq!(2, game.players()[1].domain()[0].food_count());
+        assert_eq!(1, game.players()[1].domain()[1].food_count());
+        assert_eq!(1, game.board().food_count());
     }
 
     #[test]
@@ -284,13 +342,13 @@ mod tests {
         });
         game.board_mut().push_food(FoodToken);
 
-        assert_eq!(1, game.board().food().len());
-        assert_eq!(0, game.players()[1].domain()[0].food().len());
+        assert_eq!(1, game.board().food_count());
+        assert_eq!(0, game.players()[1].domain()[0].food_count());
 
         assert!(Species::new(&mut game, (1, 0)).eat());
 
-        assert_eq!(0, game.board().food().len());
-        assert_eq!(1, game.players()[1].domain()[0].food().len());
+        assert_eq!(0, game.board().food_count());
+        assert_eq!(1, game.players()[1].domain()[0].food_count());
     }
 
     #[test]
@@ -306,15 +364,15 @@ mod tests {
         game.board_mut().push_food(FoodToken);
         game.board_mut().push_food(FoodToken);
 
-        assert_eq!(3, game.board().food().len());
-        assert_eq!(0, game.players()[1].domain()[0].food().len());
-        assert_eq!(0, game.players()[1].domain()[1].food().len());
+        assert_eq!(3, game.board().food_count());
+        assert_eq!(0, game.players()[1].domain()[0].food_count());
+        assert_eq!(0, game.players()[1].domain()[1].food_count());
 
         Species::new(&mut game, (1, 0)).cooperate();
 
-        assert_eq!(2, game.board().food().len());
-        assert_eq!(0, game.players()[1].domain()[0].food().len());
-        assert_eq!(1, game.players()[1].domain()[1].food().len());
+        assert_eq!(2, game.board().food_count());
+        assert_eq!(0, game.players()[1].domain()[0].food_count());
+        assert_eq!(1, game.players()[1].domain()[1].food_count());
     }
 
     #[test]
@@ -331,15 +389,15 @@ mod tests {
         game.board_mut().push_food(FoodToken);
         game.board_mut().push_food(FoodToken);
 
-        assert_eq!(3, game.board().food().len());
-        assert_eq!(0, game.players()[1].domain()[0].food().len());
+        assert_eq!(3, game.board().food_count());
+        assert_eq!(0, game.players()[1].domain()[0].food_count());
         assert_eq!(2, game.players()[1].domain()[0].body_size());
 
         Species::new(&mut game, (1, 0)).store(2);
 
-        assert_eq!(1, game.board().food().len());
-        assert_eq!(0, game.players()[1].domain()[0].food().len());
-        assert_eq!(2, game.players()[1].domain()[0].fat().len());
+        assert_eq!(1, game.board().food_count());
+        assert_eq!(0, game.players()[1].domain()[0].food_count());
+        assert_eq!(2, game.players()[1].domain()[0].fat_count());
     }
 
     #[test]
@@ -357,8 +415,8 @@ mod tests {
         game.board_mut().push_food(FoodToken);
         game.board_mut().push_food(FoodToken);
 
-        assert_eq!(3, game.board().food().len());
-        assert_eq!(0, game.players()[1].domain()[0].food().len());
+        assert_eq!(3, game.board().food_count());
+        assert_eq!(0, game.players()[1].domain()[0].food_count());
         assert_eq!(2, game.players()[1].domain()[0].body_size());
 
         Species::new(&mut game, (1, 0)).store(3);
@@ -375,9 +433,28 @@ mod tests {
 
         assert_eq!(2, game.players()[1].domain()[0].population());
 
-        assert!(!Species::new(&mut game, (1, 0)).kill().unwrap());
+        let outcome = Species::new(&mut game, (1, 0)).kill().unwrap();
+        assert!(outcome.extinctions().is_empty());
         assert_eq!(1, game.players()[1].domain()[0].population());
 
-        assert!(Species::new(&mut game, (1, 0)).kill().unwrap());
+        let outcome = Species::new(&mut game, (1, 0)).kill().unwrap();
+        assert_eq!(vec![(2, 0)], outcome.extinctions());
+        assert_eq!(0, game.players()[1].domain().len());
+    }
+
+    #[test]
+    fn extinction_loses_fat() {
+        let mut game = game_with_players(3, &|player| {
+            if player.id() == 2 {
+                player.domain_mut().add(Placement::Right);
+                player.domain_mut()[0].evolve(Trait::FatTissue).unwrap();
+                player.domain_mut()[0].grow().unwrap();
+                player.domain_mut()[0].store(vec![FoodToken]).unwrap();
+                player.domain_mut()[0].eat(FoodToken).unwrap();
+            }
+        });
+
+        let outcome = Species::new(&mut game, (1, 0)).kill().unwrap();
+        assert_eq!(2, outcome.food_lost());
     }
 }
@@ -0,0 +1,496 @@
+use std::collections::{BTreeMap, HashMap};
+use std::ops::{Deref, DerefMut};
+use std::time::Duration;
+use rand;
+use serde_json::Value;
+// TODO: <refactor> Shouldn't need this if we make new polymorphic.
+use evolution_wire::Channel;
+#[cfg(feature = "wire")]
+use evolution_wire::{self as wire, ToWire, FromWire};
+use error::Error;
+use game::*;
+#[cfg(feature = "wire")]
+use game::snapshot;
+use interact::*;
+use object::*;
+use silly::*;
+
+/// A game of evolution, dealing a `GameState` out to a chooser for each of
+/// its players.
+///
+/// The game asks the chooser of the current player for each choice, and
+/// applies it to the state, which the game dereferences to. The state is
+/// never borrowed by a chooser, so the game can hand out its choosers and
+/// state at once.
+///
+/// All choosers of a game are the same type `C`, but a game of
+/// `Box<Chooser + Send>` can seat choosers of any kind, for example `Silly`
+/// players alongside `Channel` clients.
+///
+/// ### Seeds
+///
+/// Games created with `new` shuffle their deck with a random seed, which is
+/// kept on the game and can be read with `seed`. Passing that seed back into
+/// `with_seed` will create a game with an identical deck, so a game played
+/// with deterministic choosers can be replayed exactly.
+///
+/// ### Time
+///
+/// The time each player spends choosing is measured, and can be limited per
+/// choice and per game with a `Budget`. A player who runs over their budget
+/// is ejected with an `Error::Timeout`.
+///
+/// # Examples
+///
+/// ```rust
+/// use evolution::Game;
+/// use evolution::silly::Silly;
+///
+/// // Create a game.
+/// let mut game = Game::<Silly>::new(4).unwrap();
+/// // Play the game.
+/// game.play().unwrap();
+///
+/// // Replay the same game.
+/// let mut replay = Game::<Silly>::with_seed(4, game.seed().unwrap()).unwrap();
+/// replay.play().unwrap();
+/// ```
+#[derive(Debug)]
+pub struct Game<C: Chooser> {
+    state: GameState,
+    choosers: HashMap<Id, C>,
+    info: HashMap<Id, String>,
+    budget: Budget,
+    clock: HashMap<Id, Duration>,
+}
+
+/// Implementation of `Game` for any kind of chooser.
+impl<C: Chooser> Game<C> {
+    /// Creates a new game with a `Player` for every chooser supplied, and a deck shuffled by the
+    /// given seed. Players are given ids counting up from 1, in the order of the choosers.
+    ///
+    /// # Returns
+    ///
+    /// This function returns an `Err` result if the number of players supplied is not legal to
+    /// start a game with, otherwise, it returns an `Ok` result containing the `Game`.
+    pub fn with_choosers(choosers: Vec<C>, seed: u64) -> Result<Self, Error> {
+        Game::with_rules(choosers, seed, RulesConfig::default())
+    }
+
+    /// Creates a new game exactly like `with_choosers`, but played by the given rules.
+    ///
+    /// # Returns
+    ///
+    /// This function returns an `Err` result if the rules can't be played by, or don't allow a
+    /// game with this many players, otherwise, it returns an `Ok` result containing the `Game`.
+    pub fn with_rules(choosers: Vec<C>, seed: u64, rules: RulesConfig) -> Result<Self, Error> {
+        let state = try!(GameState::with_rules(choosers.len(), seed, rules));
+        let mut chooser_map = HashMap::new();
+        let mut info = HashMap::new();
+
+        let ids: Vec<Id> = state.players().iter().map(|p| p.id()).collect();
+        for (id, chooser) in ids.into_iter().zip(choosers) {
+            // Kept apart from the choosers, which a resumed game only has
+            // for the players still in it.
+            if let Some(i) = chooser.info() {
+                info.insert(id, i.to_string());
+            }
+            chooser_map.insert(id, chooser);
+        }
+
+        Ok(Game {
+            state: state,
+            choosers: chooser_map,
+            info: info,
+            budget: Budget::default(),
+            clock: HashMap::new(),
+        })
+    }
+
+    /// Consumes the game, returning the chooser of every player who started the game, including
+    /// players who have been ejected, by their id.
+    pub fn into_choosers(self) -> HashMap<Id, C> {
+        self.choosers
+    }
+
+    /// Returns the state of this game.
+    pub fn state(&self) -> &GameState {
+        &self.state
+    }
+}
+
+/// Implementation of `Game` that communicates with clients over TCP using `Chanel`s.
+impl Game<Channel> {
+    /// Creates a new instance of a `Game` object. Also allocates a `Player` for every `Channel`
+    /// supplied. The deck is shuffled with a random seed.
+    ///
+    /// # Returns
+    ///
+    /// This function returns an `Err` result if the number of players supplied is not legal to
+    /// start a game with, otherwise, it returns an `Ok` result containing the `Game`.
+    pub fn new(channels: Vec<Channel>) -> Result<Self, Error> {
+        Game::<Channel>::with_seed(channels, rand::random())
+    }
+
+    /// Creates a new instance of a `Game` object exactly like `new`, but with a deck shuffled
+    /// by the given seed.
+    pub fn with_seed(channels: Vec<Channel>, seed: u64) -> Result<Self, Error> {
+        Game::with_choosers(channels, seed)
+    }
+}
+
+/// Implementation of `Game` that communicates internally with `Silly` player instances.
+impl Game<Silly> {
+    /// Creates a new game with the specified number of `Player`s, all represented by the `Silly`
+    /// strategy implementation. The deck is shuffled with a random seed.
+    ///
+    /// # Returns
+    ///
+    /// This function returns an `Err` result if the number of players supplied is not legal to
+    /// start a game with, otherwise, it returns an `Ok` result containing the `Game`.
+    pub fn new(n: usize) -> Result<Self, Error> {
+        Game::<Silly>::with_seed(n, rand::random())
+    }
+
+    /// Creates a new game exactly like `new`, but with a deck shuffled by the given seed.
+    pub fn with_seed(n: usize, seed: u64) -> Result<Self, Error> {
+        Game::with_choosers((0..n).map(|_| Silly).collect(), seed)
+    }
+}
+
+/// Implementation of `Game` that seats any mix of choosers, for example built in strategies
+/// alongside remote clients.
+impl Game<Box<Chooser + Send>> {
+    /// Creates a new game with a `Player` for every boxed chooser supplied, in order. The deck
+    /// is shuffled with a random seed.
+    ///
+    /// # Returns
+    ///
+    /// This function returns an `Err` result if the number of players supplied is not legal to
+    /// start a game with, otherwise, it returns an `Ok` result containing the `Game`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use evolution::Game;
+    /// use evolution::interact::Chooser;
+    /// use evolution::silly::Silly;
+    /// use evolution::strategies::{Greedy, Random};
+    ///
+    /// let choosers: Vec<Box<Chooser + Send>> = vec![
+    ///     Box::new(Silly),
+    ///     Box::new(Random::default()),
+    ///     Box::new(Greedy::default()),
+    /// ];
+    /// let mut game = Game::<Box<Chooser + Send>>::new(choosers).unwrap();
+    /// game.play().unwrap();
+    /// ```
+    pub fn new(choosers: Vec<Box<Chooser + Send>>) -> Result<Self, Error> {
+        Game::<Box<Chooser + Send>>::with_seed(choosers, rand::random())
+    }
+
+    /// Creates a new game exactly like `new`, but with a deck shuffled by the given seed.
+    pub fn with_seed(choosers: Vec<Box<Chooser + Send>>, seed: u64) -> Result<Self, Error> {
+        Game::with_choosers(choosers, seed)
+    }
+}
+
+impl<C: Chooser> Deref for Game<C> {
+    type Target = GameState;
+
+    fn deref(&self) -> &GameState {
+        &self.state
+    }
+}
+
+impl<C: Chooser> DerefMut for Game<C> {
+    fn deref_mut(&mut self) -> &mut GameState {
+        &mut self.state
+    }
+}
+
+/// General functions.
+impl<C: Chooser> Game<C> {
+    /// Returns the results of this game as a JSON object, with the seed,
+    /// the number of rounds played, the ranked players still in the game,
+    /// and the records of all ejected players.
+    pub fn results(&self) -> Value {
+        let mut players_ref: Vec<&Player> = self.players().iter().collect();
+        players_ref.sort_by(|a, b| b.score().cmp(&a.score()));
+        let players = players_ref.into_iter().enumerate().map(|(i, player)| {
+            let mut object = BTreeMap::new();
+            object.insert("rank".to_string(), Value::U64((i + 1) as u64));
+            object.insert("id".to_string(), Value::U64(player.id()));
+            object.insert("info".to_string(), match self.info(player.id()) {
+                Some(info) => Value::String(info.to_string()),
+                None => Value::Null,
+            });
+            object.insert("score".to_string(), Value::U64(player.score()));
+            object.insert("time_ms".to_string(), Value::U64(budget::as_millis(self.time_used(player.id()))));
+            Value::Object(object)
+        }).collect();
+
+        let mut results = BTreeMap::new();
+        results.insert("seed".to_string(), match self.seed() {
+            Some(seed) => Value::U64(seed),
+            None => Value::Null,
+        });
+        results.insert("rounds".to_string(), Value::U64(self.round() as u64));
+        results.insert("players".to_string(), Value::Array(players));
+        results.insert("kicked".to_string(), Value::Array(self.kicked().iter().map(|k| k.to_json(self.info(k.id))).collect()));
+        Value::Object(results)
+    }
+
+    /// Returns the limits on how long players may take to choose.
+    pub fn budget(&self) -> Budget {
+        self.budget
+    }
+
+    /// Sets the limits on how long players may take to choose.
+    pub fn set_budget(&mut self, budget: Budget) {
+        self.budget = budget;
+    }
+
+    /// Returns the total time the player with the given id has spent
+    /// choosing in this game.
+    pub fn time_used(&self, id: Id) -> Duration {
+        self.clock.get(&id).cloned().unwrap_or(Duration::new(0, 0))
+    }
+
+    /// Returns the info of the chooser the player with the given id
+    /// started the game with, if it has any.
+    pub fn info(&self, id: Id) -> Option<&str> {
+        self.info.get(&id).map(|info| &info[..])
+    }
+
+    /// Writes the scores of all players in the game to standard out,
+    /// followed by the records of any ejected players.
+    pub fn print_scores(&self) {
+        if let Some(seed) = self.seed() {
+            println!("seed: {}", seed);
+        }
+        let mut players_ref: Vec<&Player> = self.players().iter().collect();
+        players_ref.sort_by(|a, b| b.score().cmp(&a.score()));
+        for (i, player) in players_ref.into_iter().enumerate() {
+            let time = budget::as_millis(self.time_used(player.id()));
+            let score_message = match self.info(player.id()) {
+                Some(info) => format!("{} player id: {} ({}) score: {} time: {}ms",
+                    i + 1,
+                    player.id(),
+                    info,
+                    player.score(),
+                    time),
+                None => format!("{} player id: {} score: {} time: {}ms", i + 1, player.id(), player.score(), time)
+            };
+            println!("{}", score_message);
+        }
+        for kick in self.kicked().iter() {
+            let info = match self.info(kick.id) {
+                Some(info) => format!(" ({})", info),
+                None => String::new(),
+            };
+            println!("kicked player id: {}{} round: {} step: {} reason: {} score: {}",
+                kick.id,
+                info,
+                kick.round,
+                kick.offense.step(),
+                kick.reason,
+                kick.score);
+        }
+    }
+}
+
+/// Snapshot functions.
+#[cfg(feature = "wire")]
+impl<C: Chooser> Game<C> {
+    /// Returns a snapshot of this game, the snapshot of the game's state, see
+    /// `GameState::to_wire`, along with the `budget`, the time each player has used on the
+    /// `clock`, and the `info` of each player's chooser, including ejected players.
+    ///
+    /// A game played one `step` at a time can be snapshot between any two steps, and resumed
+    /// with `resume` exactly where it left off.
+    pub fn snapshot(&self) -> wire::Snapshot {
+        let mut snapshot: wire::Snapshot = self.state.to_wire();
+        snapshot.budget = Some(wire::snapshot::Budget {
+            per_choose: self.budget.per_choose.map(snapshot::duration_to_wire),
+            per_game: self.budget.per_game.map(snapshot::duration_to_wire),
+        });
+        let mut clock = self.clock.iter().collect::<Vec<_>>();
+        clock.sort_by(|a, b| a.0.cmp(b.0));
+        snapshot.clock = Some(clock.into_iter().map(|(id, used)| {
+            (id.to_wire(), snapshot::duration_to_wire(*used))
+        }).collect());
+        let mut info = self.info.iter().collect::<Vec<_>>();
+        info.sort();
+        snapshot.info = Some(info.into_iter().map(|(id, info)| (id.to_wire(), info.clone())).collect());
+        snapshot
+    }
+
+    /// Resumes the game in a snapshot taken with `snapshot`, played by the given choosers of
+    /// the players still in the game, by their id. A snapshot of a game's state alone is
+    /// resumed with no time limits, and no time used.
+    ///
+    /// The choosers aren't told about a round already in progress, a chooser which needs to be
+    /// started for each round should be resumed from a snapshot taken between rounds.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Decode` error if the snapshot is invalid, see
+    /// `GameState::from_wire`, or if a player still in the game has no chooser.
+    pub fn resume(mut snapshot: wire::Snapshot, choosers: HashMap<Id, C>) -> Result<Self, Error> {
+        let mut budget = Budget::unlimited();
+        if let Some(limits) = snapshot.budget.take() {
+            budget.per_choose = limits.per_choose.map(snapshot::duration_from_wire);
+            budget.per_game = limits.per_game.map(snapshot::duration_from_wire);
+        }
+        let clock = snapshot.clock.take().unwrap_or(vec![]).into_iter().map(|(id, used)| {
+            (*id, snapshot::duration_from_wire(used))
+        }).collect();
+        let info = snapshot.info.take().unwrap_or(vec![]).into_iter().map(|(id, info)| (*id, info)).collect();
+        let state = try!(GameState::from_wire(snapshot));
+        for player in state.players() {
+            if !choosers.contains_key(&player.id()) {
+                return Err(Error::Decode(format!("no chooser for player {}", player.id())))
+            }
+        }
+
+        Ok(Game {
+            state: state,
+            choosers: choosers,
+            info: info,
+            budget: budget,
+            clock: clock,
+        })
+    }
+}
+
+/// Step functions.
+impl<C: Chooser> Game<C> {
+    /// Plays through an entire game of Evolution until one of the stop criteria are met.
+    ///
+    /// # Returns
+    ///
+    /// This function returns the first `Err` result of a step, leaving the game at that step,
+    /// otherwise it returns `Ok` once the game is finished.
+    pub fn play(&mut self) -> Result<(), Error> {
+        while !self.is_finished() {
+            try!(self.step());
+        }
+        Ok(())
+    }
+
+    /// Takes the step named by the game's `stage`, and moves the game on to the stage after it.
+    /// A game which is finished is left as it is.
+    ///
+    /// Stepping a game one step at a time, it can be stopped between any two steps, for example
+    /// to take a snapshot of it.
+    pub fn step(&mut self) -> Result<(), Error> {
+        match self.stage() {
+            Stage::Deal if self.is_finished() => Ok(()),
+            Stage::Deal => {
+                info!("New round the starting player is {:?}.", self.players()[0].id());
+                info!("The rotation is: {:?}", self.players().iter().map(|p| p.id()).collect::<Vec<_>>());
+                self.step_deal()
+            },
+            Stage::Action(_) => self.step_action(),
+            Stage::Reveal => self.step_reveal(),
+            Stage::Feed => self.step_feed(),
+            Stage::Bag => self.step_bag(),
+        }
+    }
+
+    /// Executes the dealing step, providing all players with cards and species boards at the
+    /// start of a round, as specified in the rules of the Evolution game.
+    // TODO: Rename /step_//s
+    pub fn step_deal(&mut self) -> Result<(), Error> {
+        info!("Dealing.");
+        try!(step::Deal(&mut self.state).step());
+        let board = self.board().clone();
+        for player in self.state.players() {
+            let chooser = self.choosers.get_mut(&player.id()).expect("failed to get chooser");
+            chooser.start(&(board.clone(), player.clone()).observe());
+        }
+        let actions = self.players().len();
+        self.state.enter(Stage::Action(actions));
+        Ok(())
+    }
+
+    /// Executes the action step, requesting an exchange of cards from the current player, and
+    /// applying the necessary modifications to the player's species boards.
+    ///
+    /// # Note
+    ///
+    /// Players who submit invalid choices are considered cheaters and will be ejected during this
+    /// step.
+    // TODO: Rename /step_//s
+    pub fn step_action(&mut self) -> Result<(), Error> {
+        info!("Action turn for player {}.", self.current_player().id());
+        let id = self.current_player().id();
+        let limit = self.budget.limit(self.time_used(id));
+        let chooser = self.choosers.get_mut(&id).expect("failed to get chooser");
+        let mut timed = Timed::new(chooser, limit);
+        // NOTE: `step::Action` validates the choice itself.
+        let result = step::Action(&mut self.state, &mut timed).step();
+        let elapsed = timed.elapsed();
+        charge(&mut self.clock, id, elapsed);
+        try!(result);
+        let left = match self.stage() {
+            Stage::Action(left) => left.saturating_sub(1),
+            _ => 0,
+        };
+        self.state.enter(Stage::Action(left));
+        Ok(())
+    }
+
+    /// Executes the reveal step, turning over all cards given to the watering hole to be used as
+    /// food. This step also triggers automatic feed and breed operations associated with certain
+    /// species' traits.
+    // TODO: Rename /step_//s
+    pub fn step_reveal(&mut self) -> Result<(), Error> {
+        info!("Revealing board.");
+        try!(step::Reveal(&mut self.state).step());
+        info!("Food count is now {}", self.board().food_count());
+        self.state.enter(Stage::Feed);
+        Ok(())
+    }
+
+    /// Executes the feeding step, requesting and applying valid feeding choices the current
+    /// player if food is available at the watering hole. Feeding will also automatically select a
+    /// valid choice for a player if (1) it is the player's *only* valid choice, **and** (2) the
+    /// choice is *not* a self-targeted attack.
+    ///
+    /// # Note
+    ///
+    /// Players who submit invalid choices are considered cheaters and will be ejected during this
+    /// step.
+    // TODO: Rename /step_//s
+    pub fn step_feed(&mut self) -> Result<(), Error> {
+        info!("Feed turn for player {}.", self.current_player().id());
+        let id = self.current_player().id();
+        let limit = self.budget.limit(self.time_used(id));
+        let chooser = self.choosers.get_mut(&id).expect("failed to get chooser");
+        let mut timed = Timed::new(chooser, limit);
+        let result = step::Feed(&mut self.state, &mut Auto(&mut timed)).step();
+        let elapsed = timed.elapsed();
+        charge(&mut self.clock, id, elapsed);
+        try!(result);
+        self.state.enter(Stage::Feed);
+        Ok(())
+    }
+
+    /// Moves the food tokens on each species' boards to their owning players' food bag, securing
+    /// that food as part of the respective player's score.
+    // TODO: Rename /step_//s
+    pub fn step_bag(&mut self) -> Result<(), Error> {
+        info!("Bagging.");
+        try!(step::Bag(&mut self.state).step());
+        self.state.enter(Stage::Deal);
+        Ok(())
+    }
+}
+
+/// Adds to the time the player with the given id has spent choosing.
+fn charge(clock: &mut HashMap<Id, Duration>, id: Id, elapsed: Duration) {
+    let used = clock.entry(id).or_insert(Duration::new(0, 0));
+    *used = *used + elapsed;
+}
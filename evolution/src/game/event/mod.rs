@@ -0,0 +1,291 @@
+use error::{Error, ChoiceError};
+use game::*;
+use interact::*;
+use object::*;
+
+/// Something which happened while playing a game.
+///
+/// Every step of the game records events to the game's log as it goes. Some
+/// events are the choices players made, and some are the effects of those
+/// choices, like an attack made by a `FeedChoice`. The log starts with a
+/// `Start` event, when the game has a seed, making it possible to `replay`
+/// the game from it's log alone.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event {
+    /// The game started with the given deck seed, players in round order,
+    /// and rules.
+    Start {
+        seed: u64,
+        players: Vec<Id>,
+        rules: RulesConfig,
+    },
+    /// Each player was dealt the given cards.
+    Deal {
+        hands: Vec<(Id, Vec<Card>)>,
+    },
+    /// The player's action choice was applied, or `None` if they failed to
+    /// give a valid choice.
+    Action {
+        player: Id,
+        choice: Option<ActionChoice>,
+    },
+    /// The cards on the board were revealed, leaving `food` tokens on the
+    /// board.
+    Reveal {
+        cards: Vec<Card>,
+        food: u64,
+    },
+    /// The player's feed choice was applied, or `None` if they failed to
+    /// give a valid choice.
+    Feed {
+        player: Id,
+        choice: Option<FeedChoice>,
+    },
+    /// The player had no choice to make, and is skipped for the rest of the
+    /// turn.
+    Skip {
+        player: Id,
+    },
+    /// The player's attacker attacked the defender of the target player.
+    Attack {
+        player: Id,
+        attacker: usize,
+        target: Id,
+        defender: usize,
+        attacker_extinct: bool,
+        defender_extinct: bool,
+    },
+    /// The player was ejected from the game.
+    Eject {
+        player: Id,
+    },
+    /// Each player's food was bagged, after the given number of their
+    /// species went extinct.
+    Bag {
+        extinctions: Vec<(Id, usize)>,
+    },
+}
+
+/// Rebuild a game from a log of events, returning the game as it was after
+/// the first `index` events.
+///
+/// Only the choices in the log are used to rebuild the game, the effects
+/// of a choice are made by playing it, so an `index` between a choice and
+/// it's effects returns the game after all of those effects.
+///
+/// # Returns
+///
+/// This function returns an `Error::Replay` result with the index of the
+/// offending event if the log doesn't start with a `Start` event, if `index`
+/// is past the end of the log, or if the events don't fit the game being
+/// rebuilt, including attacks and ejections which replaying the choices
+/// didn't make. Errors from the steps themselves are returned as is.
+pub fn replay(events: &[Event], index: usize) -> Result<GameState, Error> {
+    if index > events.len() {
+        return Err(Error::Replay(index))
+    }
+
+    let mut events = events[..index].iter().enumerate();
+    let mut game = match events.next() {
+        Some((_, &Event::Start { seed, ref players, ref rules })) => {
+            let game = try!(GameState::with_rules(players.len(), seed, rules.clone()));
+            if game.players().iter().map(|p| p.id()).collect::<Vec<_>>() != *players {
+                return Err(Error::Replay(0))
+            }
+            game
+        },
+        _ => return Err(Error::Replay(0)),
+    };
+
+    for (i, event) in events {
+        match *event {
+            Event::Start { .. } => {
+                return Err(Error::Replay(i))
+            },
+            Event::Deal { .. } => {
+                try!(step::Deal(&mut game).step());
+            },
+            Event::Action { player, ref choice } => {
+                try!(expect_current_player(&game, player, i));
+                let mut recorded = Recorded(choice.clone().map(Some));
+                try!(step::Action(&mut game, &mut recorded).step());
+            },
+            Event::Reveal { .. } => {
+                try!(step::Reveal(&mut game).step());
+            },
+            Event::Feed { player, choice } => {
+                try!(expect_current_player(&game, player, i));
+                let mut recorded = Recorded(choice.map(Some));
+                try!(step::Feed(&mut game, &mut recorded).step());
+            },
+            Event::Skip { player } => {
+                try!(expect_current_player(&game, player, i));
+                let mut recorded = Recorded(Some(None));
+                try!(step::Feed(&mut game, &mut recorded).step());
+            },
+            Event::Bag { .. } => {
+                try!(step::Bag(&mut game).step());
+            },
+            // Effects of the choices above, which replaying the choice has
+            // already recorded.
+            Event::Attack { .. } | Event::Eject { .. } => {
+                if game.events().get(i) != Some(event) {
+                    return Err(Error::Replay(i))
+                }
+            },
+        }
+    }
+
+    Ok(game)
+}
+
+fn expect_current_player(game: &GameState, id: Id, index: usize) -> Result<(), Error> {
+    match game.current_player {
+        Some(idx) if game.players()[idx].id() == id => Ok(()),
+        _ => Err(Error::Replay(index)),
+    }
+}
+
+/// A previously recorded response to a choice, `None` when the player
+/// failed to give a valid choice.
+#[derive(Debug)]
+struct Recorded<C: Choice>(Option<Option<C>>);
+
+impl<O: Observation, C: Choice> Choose<O, C> for Recorded<C> {
+    fn choose(&mut self, _: &O) -> Result<Option<C>, Error> {
+        self.0.clone().ok_or(Error::Choice(ChoiceError::Missing))
+    }
+}
+
+#[cfg(feature = "wire")]
+mod wire;
+#[cfg(feature = "wire")]
+pub use self::wire::{write_events, read_events};
+
+#[cfg(test)]
+mod tests {
+    use error::Error;
+    use game::*;
+    use silly::*;
+    use strategies::Random;
+
+    fn played() -> Game<Silly> {
+        let mut game = Game::<Silly>::with_seed(4, 11).unwrap();
+        game.play().unwrap();
+        game
+    }
+
+    fn assert_same_players(a: &GameState, b: &GameState) {
+        assert_eq!(a.players().len(), b.players().len());
+        for (a, b) in a.players().iter().zip(b.players().iter()) {
+            assert_eq!(a.id(), b.id());
+            assert_eq!(a.domain(), b.domain());
+            assert_eq!(a.hand(), b.hand());
+            assert_eq!(a.bag_count(), b.bag_count());
+        }
+    }
+
+    #[test]
+    fn log_starts_with_start() {
+        let game = Game::<Silly>::with_seed(3, 5).unwrap();
+
+        assert_eq!(vec![Event::Start { seed: 5, players: vec![1, 2, 3], rules: RulesConfig::default() }],
+                   game.events());
+    }
+
+    #[test]
+    fn play_records_every_step() {
+        let game = played();
+
+        assert!(game.events().iter().any(|e| if let Event::Deal { .. } = *e { true } else { false }));
+        assert!(game.events().iter().any(|e| if let Event::Action { .. } = *e { true } else { false }));
+        assert!(game.events().iter().any(|e| if let Event::Reveal { .. } = *e { true } else { false }));
+        assert!(game.events().iter().any(|e| if let Event::Feed { .. } = *e { true } else { false }));
+        assert!(game.events().iter().any(|e| if let Event::Bag { .. } = *e { true } else { false }));
+    }
+
+    #[test]
+    fn replay_whole_game() {
+        let game = played();
+
+        let replayed = event::replay(game.events(), game.events().len()).unwrap();
+
+        assert_same_players(&game, &replayed);
+        assert_eq!(game.deck(), replayed.deck());
+        assert_eq!(game.events(), replayed.events());
+    }
+
+    #[test]
+    fn replay_first_round() {
+        let game = played();
+        let index = game.events().iter().position(|e| {
+            if let Event::Bag { .. } = *e { true } else { false }
+        }).unwrap() + 1;
+
+        let replayed = event::replay(game.events(), index).unwrap();
+
+        assert_eq!(&game.events()[..index], replayed.events());
+    }
+
+    #[test]
+    fn replay_by_the_recorded_rules() {
+        let mut rules = RulesConfig::official();
+        rules.max_population = 4;
+        let mut game = Game::with_rules((0..4).map(|_| Silly).collect(), 11, rules.clone()).unwrap();
+        game.play().unwrap();
+
+        let replayed = event::replay(game.events(), game.events().len()).unwrap();
+
+        assert_eq!(rules, replayed.rules);
+        assert_same_players(&game, &replayed);
+        assert_eq!(game.events(), replayed.events());
+    }
+
+    #[test]
+    fn replay_checks_attacks() {
+        let mut game = Game::with_choosers((0..4).map(|_| Random::new(4)).collect(), 4).unwrap();
+        game.play().unwrap();
+        let index = game.events().iter().position(|e| {
+            if let Event::Attack { .. } = *e { true } else { false }
+        }).unwrap();
+        let mut events = game.events().to_vec();
+        if let Event::Attack { ref mut defender_extinct, .. } = events[index] {
+            *defender_extinct = !*defender_extinct;
+        }
+
+        match event::replay(&events, events.len()) {
+            Err(Error::Replay(i)) => assert_eq!(index, i),
+            _ => panic!("expected a replay error"),
+        }
+    }
+
+    #[test]
+    fn replay_checks_ejections() {
+        let game = played();
+        let mut events = game.events().to_vec();
+        events.insert(2, Event::Eject { player: 1 });
+
+        match event::replay(&events, events.len()) {
+            Err(Error::Replay(2)) => {},
+            _ => panic!("expected a replay error"),
+        }
+    }
+
+    #[test]
+    fn replay_without_start() {
+        let game = played();
+
+        assert!(event::replay(&game.events()[1..], 3).is_err());
+        match event::replay(game.events(), 0) {
+            Err(Error::Replay(0)) => {},
+            _ => panic!("expected a replay error"),
+        }
+    }
+
+    #[test]
+    fn replay_past_end() {
+        let game = played();
+
+        assert!(event::replay(game.events(), game.events().len() + 1).is_err());
+    }
+}
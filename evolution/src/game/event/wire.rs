@@ -0,0 +1,163 @@
+use std::io::{self, BufRead, Write};
+use serde_json as json;
+use evolution_wire as wire;
+use evolution_wire::{ToWire, FromWire};
+use error::Error;
+use game::*;
+use interact::*;
+use object::*;
+
+impl wire::ToWire<wire::Event> for Event {
+    fn to_wire(&self) -> wire::Event {
+        match *self {
+            Event::Start { seed, ref players, ref rules } => {
+                wire::Event::Start(seed, players.iter().map(|id| {
+                    ToWire::<wire::NaturalPlus>::to_wire(id)
+                }).collect(), rules.to_wire())
+            },
+            Event::Deal { ref hands } => {
+                wire::Event::Deal(hands.iter().map(|&(id, ref cards)| {
+                    (ToWire::<wire::NaturalPlus>::to_wire(&id), cards.as_slice().to_wire())
+                }).collect())
+            },
+            Event::Action { player, ref choice } => {
+                wire::Event::Action(player.to_wire(), choice.as_ref().map(|c| c.to_wire()))
+            },
+            Event::Reveal { ref cards, food } => {
+                wire::Event::Reveal(cards.as_slice().to_wire(), food.to_wire())
+            },
+            Event::Feed { player, ref choice } => {
+                wire::Event::Feed(player.to_wire(), choice.as_ref().map(|c| c.to_wire()))
+            },
+            Event::Skip { player } => {
+                wire::Event::Skip(player.to_wire())
+            },
+            Event::Attack { player, attacker, target, defender, attacker_extinct, defender_extinct } => {
+                wire::Event::Attack(player.to_wire(),
+                                    attacker.to_wire(),
+                                    target.to_wire(),
+                                    defender.to_wire(),
+                                    attacker_extinct,
+                                    defender_extinct)
+            },
+            Event::Eject { player } => {
+                wire::Event::Eject(player.to_wire())
+            },
+            Event::Bag { ref extinctions } => {
+                wire::Event::Bag(extinctions.iter().map(|&(id, count)| {
+                    (ToWire::<wire::NaturalPlus>::to_wire(&id), ToWire::<wire::Natural>::to_wire(&count))
+                }).collect())
+            },
+        }
+    }
+}
+
+impl wire::FromWire<wire::Event> for Event {
+    fn from_wire(wire: wire::Event) -> Result<Self, wire::Error> {
+        let event = match wire {
+            wire::Event::Start(seed, players, rules) => {
+                Event::Start {
+                    seed: seed,
+                    players: players.into_iter().map(|id| *id).collect(),
+                    rules: try!(RulesConfig::from_wire(rules)),
+                }
+            },
+            wire::Event::Deal(hands) => {
+                let mut vec = Vec::new();
+                for (id, cards) in hands {
+                    vec.push((*id, try!(Vec::<Card>::from_wire(cards))));
+                }
+                Event::Deal { hands: vec }
+            },
+            wire::Event::Action(player, choice) => {
+                let choice = match choice {
+                    Some(choice) => Some(try!(ActionChoice::from_wire(choice))),
+                    None => None,
+                };
+                Event::Action { player: *player, choice: choice }
+            },
+            wire::Event::Reveal(cards, food) => {
+                Event::Reveal {
+                    cards: try!(Vec::<Card>::from_wire(cards)),
+                    food: *food,
+                }
+            },
+            wire::Event::Feed(player, choice) => {
+                let choice = match choice {
+                    Some(choice) => Some(try!(FeedChoice::from_wire(choice))),
+                    None => None,
+                };
+                Event::Feed { player: *player, choice: choice }
+            },
+            wire::Event::Skip(player) => {
+                Event::Skip { player: *player }
+            },
+            wire::Event::Attack(player, attacker, target, defender, attacker_extinct, defender_extinct) => {
+                Event::Attack {
+                    player: *player,
+                    attacker: *attacker as usize,
+                    target: *target,
+                    defender: *defender as usize,
+                    attacker_extinct: attacker_extinct,
+                    defender_extinct: defender_extinct,
+                }
+            },
+            wire::Event::Eject(player) => {
+                Event::Eject { player: *player }
+            },
+            wire::Event::Bag(extinctions) => {
+                Event::Bag {
+                    extinctions: extinctions.into_iter().map(|(id, count)| {
+                        (*id, *count as usize)
+                    }).collect(),
+                }
+            },
+        };
+        Ok(event)
+    }
+}
+
+/// Write the given events as JSON lines, one event per line.
+pub fn write_events<W: Write>(events: &[Event], writer: &mut W) -> io::Result<()> {
+    for event in events {
+        let wire_event: wire::Event = event.to_wire();
+        try!(json::to_writer(&mut *writer, &wire_event).map_err(|e| {
+            io::Error::new(io::ErrorKind::Other, e)
+        }));
+        try!(writer.write_all(b"\n"));
+    }
+    Ok(())
+}
+
+/// Read events written by `write_events`, skipping blank lines.
+pub fn read_events<R: BufRead>(reader: R) -> Result<Vec<Event>, Error> {
+    let mut events = Vec::new();
+    for line in reader.lines() {
+        let line = try!(line);
+        if line.trim().is_empty() {
+            continue
+        }
+        let wire_event = try!(json::from_str::<wire::Event>(&line).map_err(wire::Error::from));
+        events.push(try!(Event::from_wire(wire_event)));
+    }
+    Ok(events)
+}
+
+#[cfg(test)]
+mod tests {
+    use game::*;
+    use silly::*;
+
+    #[test]
+    fn events_round_trip() {
+        let mut game = Game::<Silly>::with_seed(3, 3).unwrap();
+        game.play().unwrap();
+
+        let mut buffer = Vec::new();
+        event::write_events(game.events(), &mut buffer).unwrap();
+        let events = event::read_events(buffer.as_slice()).unwrap();
+
+        assert_eq!(game.events().len(), buffer.split(|b| *b == b'\n').filter(|l| !l.is_empty()).count());
+        assert_eq!(game.events(), events.as_slice());
+    }
+}
@@ -0,0 +1,74 @@
+use std::collections::BTreeMap;
+use serde_json::Value;
+use game::*;
+use interact::*;
+
+/// A record of a player ejected from a game.
+///
+/// Ejected players are removed from the game's players, but not forgotten,
+/// the game keeps one of these for each of them saying when and why they
+/// were ejected. Only the rules decide these, the info of the player's
+/// chooser is kept by the `Game` which dealt to them.
+#[derive(Debug)]
+pub struct Kick {
+    /// The id of the ejected player.
+    pub id: Id,
+    /// The round the player was ejected in, counting from 1.
+    pub round: usize,
+    /// The step the player was ejected during, and the offending choice.
+    pub offense: Offense,
+    /// Why the player was ejected, as the message of the error which
+    /// ejected them, so it's kept by snapshots.
+    pub reason: String,
+    /// The player's score when they were ejected.
+    pub score: u64,
+}
+
+/// The step a player was ejected during, with the choice they gave, or
+/// `None` if they failed to give one.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Offense {
+    Action(Option<ActionChoice>),
+    Feed(Option<FeedChoice>),
+}
+
+impl Offense {
+    /// Returns the name of the step the player was ejected during.
+    pub fn step(&self) -> &'static str {
+        match *self {
+            Offense::Action(_) => "action",
+            Offense::Feed(_) => "feed",
+        }
+    }
+
+    /// Returns the offending choice, formatted for reading, if there was
+    /// one.
+    pub fn choice(&self) -> Option<String> {
+        match *self {
+            Offense::Action(ref c) => c.as_ref().map(|c| format!("{:?}", c)),
+            Offense::Feed(ref c) => c.as_ref().map(|c| format!("{:?}", c)),
+        }
+    }
+}
+
+impl Kick {
+    /// Returns this record as a JSON object, with the given info of the
+    /// ejected player's chooser.
+    pub fn to_json(&self, info: Option<&str>) -> Value {
+        let mut object = BTreeMap::new();
+        object.insert("id".to_string(), Value::U64(self.id));
+        object.insert("info".to_string(), match info {
+            Some(info) => Value::String(info.to_string()),
+            None => Value::Null,
+        });
+        object.insert("round".to_string(), Value::U64(self.round as u64));
+        object.insert("step".to_string(), Value::String(self.offense.step().to_string()));
+        object.insert("choice".to_string(), match self.offense.choice() {
+            Some(choice) => Value::String(choice),
+            None => Value::Null,
+        });
+        object.insert("reason".to_string(), Value::String(self.reason.clone()));
+        object.insert("score".to_string(), Value::U64(self.score));
+        Value::Object(object)
+    }
+}
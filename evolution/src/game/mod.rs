@@ -1,10 +1,8 @@
-use std::collections::{HashMap, HashSet};
-// TODO: <refactor> Shouldn't need this if we make new polymorphic.
-use evolution_wire::Channel;
+use std::cmp;
+use std::collections::HashSet;
+use error::{Error, RuleError};
 use ext::Dequeue;
-use interact::*;
 use object::*;
-use silly::*;
 
 /// A unique identifier of a player in the game.
 pub type Id = u64;
@@ -15,8 +13,14 @@ pub const MIN_PLAYERS: usize = 3;
 /// The maximum number of players allowed to play evolution at once.
 pub const MAX_PLAYERS: usize = 8;
 
-/// A game of evolution that contains all the players, the board, and the
-/// deck of cards.
+/// The state of a game of evolution, all the players, the board, and the
+/// deck of cards, without anyone to make choices for the players.
+///
+/// A game state only changes through its transition functions,
+/// `apply_action`, `apply_feed`, `reveal` and `bag`, and the `step::Deal`
+/// step. Each is given everything it needs, so a game state can be copied
+/// and stepped freely, see `detached`. A `Game` is what deals a game state
+/// out to its players' choosers.
 ///
 /// All player ids must be unique. When a game is created the players must
 /// have an empty domain, hand, and food bag.
@@ -40,123 +44,207 @@ pub const MAX_PLAYERS: usize = 8;
 /// The deck must conatin no more than 17 carnivore trait cards and no more
 /// than 7 of each non-carnivore trait card.
 ///
+/// ### Ejections
+///
+/// Players who fail to make a valid choice are ejected from the game. They
+/// no longer play, but a `Kick` record is kept for each of them, saying
+/// when and why they were ejected, which can be read with `kicked`.
+///
 /// # Examples
 ///
 /// ```rust
-/// use evolution::Game;
+/// use evolution::game::*;
+/// use evolution::interact::*;
 /// use evolution::silly::Silly;
 ///
-/// // Create a game.
-/// let mut game = Game::<Silly>::new(4).unwrap();
-/// // Play the game.
-/// game.play();
+/// let mut state = GameState::with_seed(3, 1).unwrap();
+/// step::Deal(&mut state).step().unwrap();
+/// // Every player takes `Silly`'s action.
+/// for _ in 0..state.players().len() {
+///     let observation: ActionObservation = state.observe();
+///     let choice = Silly.choose(&observation).unwrap().unwrap();
+///     state.apply_action(choice).unwrap();
+/// }
+/// state.reveal();
 /// ```
 #[derive(Debug)]
-pub struct Game<C: Chooser> {
+pub struct GameState {
     players: Vec<Player>,
-    choosers: HashMap<Id, C>,
     current_player: Option<usize>,
     skip_set: HashSet<usize>,
     board: Board,
     deck: Vec<Card>,
+    seed: Option<u64>,
+    events: Vec<Event>,
+    round: usize,
+    kicked: Vec<Kick>,
+    rules: RulesConfig,
+    stage: Stage,
+}
+
+/// The stage of a round a game is in, naming the step a `Game` takes next.
+///
+/// Each round deals, asks every player for an action, reveals the cards
+/// given to the watering hole, feeds until the turn is over, and bags. A
+/// stage with no one left to take it is skipped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stage {
+    /// Dealing the next round, if the game isn't over.
+    Deal,
+    /// Asking for actions, with the number of actions left this round.
+    Action(usize),
+    /// Revealing the watering hole.
+    Reveal,
+    /// Feeding the current player.
+    Feed,
+    /// Bagging the food on every species.
+    Bag,
 }
 
-/// Implementation of `Game` that communicates with clients over TCP using `Chanel`s.
-impl Game<Channel> {
-    /// Creates a new instance of a `Game` object. Also allocates a `Player` for every `Channel`
-    /// supplied.
+impl GameState {
+    /// Creates the state of a new game with the given number of players, and a deck shuffled by
+    /// the given seed. Players are given ids counting up from 1.
     ///
     /// # Returns
     ///
-    /// This function returns an `Err` result if the number of players supplied is not legal to
-    /// start a game with, otherwise, it returns an `Ok` result containing the `Game`.
-    pub fn new(channels: Vec<Channel>) -> Result<Self, ()> {
-        let mut players = Vec::new();
-        let mut choosers = HashMap::new();
-
-        for (i, channel) in channels.into_iter().enumerate() {
-            let id = (i + 1) as u64;
-            players.push(Player::new(id));
-            choosers.insert(id, channel);
-        }
+    /// This function returns an `Err` result if the number of players is not legal to start a
+    /// game with, otherwise, it returns an `Ok` result containing the `GameState`.
+    pub fn with_seed(n: usize, seed: u64) -> Result<Self, Error> {
+        GameState::with_rules(n, seed, RulesConfig::default())
+    }
 
-        if players.len() < MIN_PLAYERS || players.len() > MAX_PLAYERS {
-            return Err(())
+    /// Creates the state of a new game exactly like `with_seed`, but played by the given rules.
+    ///
+    /// # Returns
+    ///
+    /// This function returns an `Err` result if the rules can't be played by, or don't allow a
+    /// game with this many players, otherwise, it returns an `Ok` result containing the
+    /// `GameState`.
+    pub fn with_rules(n: usize, seed: u64, rules: RulesConfig) -> Result<Self, Error> {
+        try!(rules.validate());
+        if !rules.allows_players(n) {
+            return Err(Error::Rule(RuleError::PlayerCount(n)))
         }
 
-        let current_player = Some(0);
+        let players: Vec<Player> = (1..(n as u64 + 1)).map(Player::new).collect();
+        let events = vec![Event::Start {
+            seed: seed,
+            players: players.iter().map(|p| p.id()).collect(),
+            rules: rules.clone(),
+        }];
 
-        Ok(Game {
+        Ok(GameState {
             board: Board::default(),
             players: players,
-            choosers: choosers,
-            current_player: current_player,
+            current_player: Some(0),
             skip_set: HashSet::default(),
-            deck: Card::deck(),
+            deck: rules.deck(seed),
+            seed: Some(seed),
+            events: events,
+            round: 0,
+            kicked: Vec::new(),
+            rules: rules,
+            stage: Stage::Deal,
         })
     }
-}
 
-/// Implementation of `Game` that communicates internally with `Silly` player instances.
-impl Game<Silly> {
-    /// Creates a new game with the specified number of `Player`s, all represented by the `Silly`
-    /// strategy implementation.
+    /// Creates the state of a game of the given players, board and deck, where the player at
+    /// index `current` is the current player. The game is played by the given rules.
     ///
-    /// # Returns
-    ///
-    /// This function returns an `Err` result if the number of players supplied is not legal to
-    /// start a game with, otherwise, it returns an `Ok` result containing the `Game`.
-    pub fn new(n: usize) -> Result<Self, ()> {
-        let mut players = Vec::new();
-        let mut choosers = HashMap::new();
-
-        for i in 0..n {
-            let id = (i + 1) as u64;
-            players.push(Player::new(id));
-            choosers.insert(id, Silly);
+    /// This is useful for finding out what a choice would do.
+    pub fn from_parts(players: Vec<Player>,
+                      board: Board,
+                      deck: Vec<Card>,
+                      current: usize,
+                      rules: RulesConfig)
+        -> Self
+    {
+        GameState {
+            board: board,
+            players: players,
+            current_player: Some(current),
+            skip_set: HashSet::new(),
+            deck: deck,
+            seed: None,
+            events: Vec::new(),
+            round: 0,
+            kicked: Vec::new(),
+            rules: rules,
+            stage: Stage::Deal,
         }
+    }
+}
 
-        if players.len() < MIN_PLAYERS || players.len() > MAX_PLAYERS {
-            return Err(())
-        }
+/// General functions.
+impl GameState {
+    /// Returns the seed used to shuffle this game's deck, or `None` if the
+    /// deck was given to the game directly.
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
 
-        let current_player = Some(0);
+    /// Returns the rules this game is played by.
+    pub fn rules(&self) -> &RulesConfig {
+        &self.rules
+    }
 
-        Ok(Game {
-            board: Board::default(),
-            players: players,
-            choosers: choosers,
-            current_player: current_player,
-            skip_set: HashSet::default(),
-            deck: Card::deck(),
-        })
+    /// Returns the current round number, counting from 1, or 0 before the
+    /// first deal.
+    pub fn round(&self) -> usize {
+        self.round
     }
-}
 
-/// General functions.
-impl<C: Chooser> Game<C> {
-    /// Writes the scores of all players in the game to standard out.
-    pub fn print_scores(&self) {
-        let mut players_ref: Vec<&Player> = self.players.iter().collect();
-        players_ref.sort_by(|a, b| b.score().cmp(&a.score()));
-        for (i, player) in players_ref.into_iter().enumerate() {
-            let chooser = self.choosers.get(&player.id()).expect("failed to get chooser");
-            let score_message = match chooser.info() {
-                Some(info) => format!("{} player id: {} ({}) score: {}",
-                    i + 1,
-                    player.id(),
-                    info,
-                    player.score()),
-                None => format!("{} player id: {} score: {}", i + 1, player.id(), player.score())
-            };
-            println!("{}", score_message);
-            // println!("{} player id: {} score {}", i + 1, player.id(), player.score());
+    /// Returns the stage of the round this game is in.
+    pub fn stage(&self) -> Stage {
+        self.stage
+    }
+
+    /// Returns `true` if this game is between rounds, and there are no more
+    /// rounds to play, see `is_over`.
+    pub fn is_finished(&self) -> bool {
+        self.stage == Stage::Deal && self.is_over()
+    }
+
+    /// Moves this game on to the given stage, or past it, when there is no
+    /// one left to take it.
+    fn enter(&mut self, stage: Stage) {
+        self.stage = match stage {
+            Stage::Action(0) => Stage::Reveal,
+            Stage::Action(_) if !self.has_current_player() => Stage::Reveal,
+            Stage::Feed if self.turn_is_over() || !self.has_current_player() => Stage::Bag,
+            stage => stage,
+        };
+    }
+
+    /// Returns the records of every player ejected from this game, in the
+    /// order they were ejected.
+    pub fn kicked(&self) -> &[Kick] {
+        &self.kicked
+    }
+
+    /// Returns a copy of this game state without its events or ejected players.
+    ///
+    /// The copy is cheap to make, and can be stepped with choices given to the steps directly to
+    /// find out what they would do. See `from_parts`.
+    pub fn detached(&self) -> GameState {
+        GameState {
+            board: self.board.clone(),
+            players: self.players.clone(),
+            current_player: self.current_player,
+            skip_set: self.skip_set.clone(),
+            deck: self.deck.clone(),
+            seed: self.seed,
+            events: Vec::new(),
+            round: self.round,
+            kicked: Vec::new(),
+            rules: self.rules.clone(),
+            stage: self.stage,
         }
     }
 }
 
 /// Board functions.
-impl<C: Chooser> Game<C> {
+impl GameState {
     /// Returns a reference to the game board.
     pub fn board(&self) -> &Board {
         &self.board
@@ -169,7 +257,7 @@ impl<C: Chooser> Game<C> {
 }
 
 /// Player functions.
-impl<C: Chooser> Game<C> {
+impl GameState {
     /// Returns the players of this game in the order they are playing.
     pub fn players(&self) -> &[Player] {
         &self.players
@@ -192,6 +280,19 @@ impl<C: Chooser> Game<C> {
         self.current_player.expect("no current player")
     }
 
+    /// Returns `true` if there is a current player, which there isn't once every player has been
+    /// skipped.
+    pub fn has_current_player(&self) -> bool {
+        self.current_player.is_some()
+    }
+
+    /// Returns the indices of the players skipped for the rest of this turn, in order.
+    pub fn skipped(&self) -> Vec<usize> {
+        let mut skipped = self.skip_set.iter().cloned().collect::<Vec<_>>();
+        skipped.sort();
+        skipped
+    }
+
     /// Shifts the current starting player to the last position, setting the
     /// next player immediately after to be the new starting player.
     /// This function is intended to be called at round start, so it will
@@ -243,29 +344,51 @@ impl<C: Chooser> Game<C> {
         }
     }
 
-    /// Ejects the current player from the game.
-    pub fn eject_current_player(&mut self) {
-        warn!("Ejecting player {}.", self.current_player().id());
+    /// Ejects the current player from the game, keeping a `Kick` record of
+    /// the offense and the reason for it.
+    pub fn eject_current_player(&mut self, offense: Offense, reason: Error) {
+        warn!("Ejecting player {} during {}: {}.", self.current_player().id(), offense.step(), reason);
         let idx = self.current_player_idx();
-        self.players.remove(idx);
+        let player = self.players.remove(idx);
+        self.record(Event::Eject { player: player.id() });
+        let kick = Kick {
+            id: player.id(),
+            round: self.round,
+            offense: offense,
+            reason: format!("{}", reason),
+            score: player.score(),
+        };
+        self.kicked.push(kick);
     }
 
     /// Returns `true` if not players are in the skip set.
     pub fn turn_is_over(&self) -> bool {
-        self.board().food().len() == 0 ||
+        self.board().food_count() == 0 ||
         self.skip_set.len() >= self.players.len()
     }
 
     /// Returns `true` if there are no longer enough cards remaining to deal to all remaining
-    /// players, or if every player has been kicked from the game.
+    /// players, if every player has been kicked from the game, or if the rules' last round has
+    /// been played.
+    ///
+    /// Under the official rules the deck only needs a card for every player, a short deck is
+    /// dealt out as the final round.
     pub fn is_over(&self) -> bool {
-        self.deck().len() < (self.players().len() * 4) as usize ||
-        self.players().len() <= 1
+        let needed = match self.rules.mode {
+            // Players without a species are dealt one first.
+            RulesMode::Course => self.players().iter().fold(0, |a, p| {
+                a + self.rules.cards_for(cmp::max(1, p.domain().len()))
+            }),
+            RulesMode::Official => self.players().len(),
+        };
+        self.deck().len() < needed ||
+        self.players().len() <= 1 ||
+        self.rules.max_rounds.map_or(false, |max| self.round >= max)
     }
 }
 
 /// Deck functions.
-impl<C: Chooser> Game<C> {
+impl GameState {
     /// Returns a reference to the deck of this game.
     pub fn deck(&self) -> &[Card] {
         &self.deck
@@ -303,116 +426,17 @@ impl<C: Chooser> Game<C> {
     }
 }
 
-/// Step functions.
-impl<C: Chooser> Game<C> {
-    /// Plays through an entire game of Evolution until one of the stop criteria are met.
-    pub fn play(&mut self) {
-        unsafe {
-            let game: *mut Game<C> = self;
-
-            'game: while !(&*game).is_over() {
-                info!("New round the starting player is {:?}.", (*game).players()[0].id());
-                info!("The rotation is: {:?}", (*game).players().iter().map(|p| p.id()).collect::<Vec<_>>());
-
-                self.step_deal().expect("deal failed");
-
-                'action_steps: for _ in (&*game).players() {
-                    if (*game).current_player.is_some() {
-                        self.step_action().expect("action failed");
-                    } else {
-                        break 'action_steps
-                    }
-                }
-
-                self.step_reveal().expect("reveal failed");
-
-                'feed_steps: while !(&*game).turn_is_over() {
-                    if (*game).current_player.is_some() {
-                        self.step_feed().expect("feed failed");
-                    } else {
-                        break 'feed_steps
-                    }
-                }
-
-                self.step_bag().expect("bag failed");
-            }
-        }
-    }
-
-    /// Executes the dealing step, providing all players with cards and species boards at the
-    /// start of a round, as specified in the rules of the Evolution game.
-    // TODO: Rename /step_//s
-    pub fn step_deal(&mut self) -> Result<(), ()> {
-        info!("Dealing.");
-        try!(step::Deal(self).step());
-        // NOTE: I need to learn to deal with mutable loops in Rust.
-        for i in 0..self.players().len() {
-            let player = self.players()[i].clone(); // HACK: clone to get around self borrow.
-            let board = self.board().clone();
-            let chooser = self.choosers.get_mut(&player.id()).expect("failed to get chooser");
-            chooser.start(&(board, player).observe());
-        }
-        Ok(())
-    }
-
-    /// Executes the action step, requesting an exchange of cards from the current player, and
-    /// applying the necessary modifications to the player's species boards.
-    ///
-    /// # Note
-    ///
-    /// Players who submit invalid choices are considered cheaters and will be ejected during this
-    /// step.
-    // TODO: Rename /step_//s
-    pub fn step_action(&mut self) -> Result<(), ()> {
-        info!("Action turn for player {}.", self.current_player().id());
-        // HACK: <refactor> We really want a dealer and a game state, to
-        // seperate these components. The game could do this all, but we'd
-        // need to figure out another way to pass an arbitrary `Choose` to a
-        // step for test harnesses.
-        let mut chooser = unsafe {
-            let id = self.current_player().id();
-            &mut *(self.choosers.get_mut(&id).expect("failed to get chooser") as *mut C)
-        };
-        step::Action(self, &mut Auto(chooser)).step()
-    }
-
-    /// Executes the reveal step, turning over all cards given to the watering hole to be used as
-    /// food. This step also triggers automatic feed and breed operations associated with certain
-    /// species' traits.
-    // TODO: Rename /step_//s
-    pub fn step_reveal(&mut self) -> Result<(), ()> {
-        info!("Revealing board.");
-        let result = step::Reveal(self).step();
-        info!("Food count is now {}", self.board().food().len());
-        result
-    }
-
-    /// Executes the feeding step, requesting and applying valid feeding choices the current
-    /// player if food is available at the watering hole. Feeding will also automatically select a
-    /// valid choice for a player if (1) it is the player's *only* valid choice, **and** (2) the
-    /// choice is *not* a self-targeted attack.
-    ///
-    /// # Note
-    ///
-    /// Players who submit invalid choices are considered cheaters and will be ejected during this
-    /// step.
-    // TODO: Rename /step_//s
-    pub fn step_feed(&mut self) -> Result<(), ()> {
-        info!("Feed turn for player {}.", self.current_player().id());
-        // HACK: <refactor> See comment in `step_action`.
-        let mut chooser = unsafe {
-            let id = self.current_player().id();
-            &mut *(self.choosers.get_mut(&id).expect("failed to get chooser") as *mut C)
-        };
-        step::Feed(self, &mut Auto(chooser)).step()
+/// Event functions.
+impl GameState {
+    /// Returns the log of events that have happened in this game, in the
+    /// order they happened.
+    pub fn events(&self) -> &[Event] {
+        &self.events
     }
 
-    /// Moves the food tokens on each species' boards to their owning players' food bag, securing
-    /// that food as part of the respective player's score.
-    // TODO: Rename /step_//s
-    pub fn step_bag(&mut self) -> Result<(), ()> {
-        info!("Bagging.");
-        step::Bag(self).step()
+    fn record(&mut self, event: Event) {
+        debug!("event: {:?}", event);
+        self.events.push(event);
     }
 }
 
@@ -431,15 +455,39 @@ pub use self::context::Context;
 pub mod step;
 pub use self::step::Step;
 
+/// Dealing a game out to the choosers of its players.
+mod dealer;
+pub use self::dealer::Game;
+
+/// Events recorded while playing the game.
+pub mod event;
+pub use self::event::Event;
+
+/// Records of ejected players.
+mod kick;
+pub use self::kick::{Kick, Offense};
+
+/// Limits on the time players take to choose.
+pub mod budget;
+pub use self::budget::{Budget, Timed};
+
+/// The rules a game is played by.
+pub mod rules;
+pub use self::rules::{RulesConfig, RulesMode};
+
 #[cfg(feature = "wire")]
 mod wire;
 
+/// Snapshots of whole games, which can be saved and resumed.
+#[cfg(feature = "wire")]
+mod snapshot;
+
 /// Return a game with the given number of players, where each player was
 /// given to a function for setting them up.
 // TODO: Delete this weird test helper.
 #[cfg(test)]
-pub fn game_with_players(players: usize, each_player: &Fn(&mut Player)) -> Game<Silly> {
-    let mut game = Game::<Silly>::new(players).expect("given invalid number of players");
+pub fn game_with_players(players: usize, each_player: &Fn(&mut Player)) -> Game<::silly::Silly> {
+    let mut game = Game::<::silly::Silly>::new(players).expect("given invalid number of players");
     for player in game.players_mut() {
         each_player(player);
     }
@@ -448,12 +496,20 @@ pub fn game_with_players(players: usize, each_player: &Fn(&mut Player)) -> Game<
 
 #[cfg(test)]
 mod tests {
+    use serde_json::Value;
+    use error::{Error, ChoiceError, RuleError};
     use game::*;
+    use interact::*;
+    use object::*;
     use silly::*;
+    use strategies::Random;
 
     #[test]
     fn too_few_players() {
-        assert!(Game::<Silly>::new(2).is_err());
+        match Game::<Silly>::new(2) {
+            Err(Error::Rule(RuleError::PlayerCount(2))) => {},
+            _ => panic!("expected a player count error"),
+        }
     }
 
     #[test]
@@ -461,6 +517,27 @@ mod tests {
         assert!(Game::<Silly>::new(9).is_err());
     }
 
+    #[test]
+    fn new_stores_seed() {
+        let game = Game::<Silly>::with_seed(3, 42).unwrap();
+
+        assert_eq!(Some(42), game.seed());
+        assert_eq!(Card::shuffled_deck(42).as_slice(), game.deck());
+    }
+
+    #[test]
+    fn same_seed_same_game() {
+        let mut a = Game::<Silly>::with_seed(4, 7).unwrap();
+        let mut b = Game::<Silly>::with_seed(4, 7).unwrap();
+        a.play().unwrap();
+        b.play().unwrap();
+
+        let scores = |game: &Game<Silly>| {
+            game.players().iter().map(|p| (p.id(), p.score())).collect::<Vec<_>>()
+        };
+        assert_eq!(scores(&a), scores(&b));
+    }
+
     #[test]
     fn current_player() {
         let game = Game::<Silly>::new(3).unwrap();
@@ -559,7 +636,7 @@ mod tests {
         assert_eq!(4, game.players().len());
         assert!(game.players().iter().map(|p| p.id()).collect::<Vec<_>>().contains(&1));
 
-        game.eject_current_player();
+        game.eject_current_player(Offense::Feed(None), Error::Timeout);
 
         assert_eq!(3, game.players().len());
         assert!(!game.players().iter().map(|p| p.id()).collect::<Vec<_>>().contains(&1));
@@ -572,7 +649,7 @@ mod tests {
         assert_eq!(4, game.players().len());
         assert_eq!(1, game.current_player().id());
 
-        game.eject_current_player();
+        game.eject_current_player(Offense::Feed(None), Error::Timeout);
 
         assert_eq!(3, game.players().len());
         assert_eq!(2, game.current_player().id());
@@ -587,23 +664,169 @@ mod tests {
         assert_eq!(3, game.players().len());
         assert_eq!(3, game.current_player().id());
 
-        game.eject_current_player();
+        game.eject_current_player(Offense::Feed(None), Error::Timeout);
 
         assert_eq!(2, game.players.len());
         assert_eq!(1, game.current_player().id());
 
-        game.eject_current_player();
+        game.eject_current_player(Offense::Feed(None), Error::Timeout);
 
         assert_eq!(1, game.players().len());
         assert_eq!(2, game.current_player().id());
     }
 
+    #[test]
+    fn eject_current_player_keeps_a_kick() {
+        let mut game = Game::<Silly>::new(3).unwrap();
+        game.players_mut()[0].domain_mut().add(Placement::Right);
+        step::Deal(&mut game).step().unwrap();
+
+        game.eject_current_player(Offense::Feed(Some(FeedChoice::Feed(7))), Error::Choice(ChoiceError::Illegal));
+
+        assert_eq!(1, game.kicked().len());
+        let kick = &game.kicked()[0];
+        assert_eq!(1, kick.id);
+        assert_eq!(1, kick.round);
+        assert_eq!(Offense::Feed(Some(FeedChoice::Feed(7))), kick.offense);
+        assert_eq!("feed", kick.offense.step());
+        assert_eq!(1, kick.score);
+        assert_eq!(format!("{}", Error::Choice(ChoiceError::Illegal)), kick.reason);
+    }
+
+    #[test]
+    fn results_include_kicked() {
+        let mut game = Game::<Silly>::with_seed(3, 1).unwrap();
+        game.eject_current_player(Offense::Action(None), Error::Timeout);
+
+        let results = game.results();
+        let results = results.as_object().unwrap();
+        assert_eq!(Some(&Value::U64(1)), results.get("seed"));
+        assert_eq!(2, results.get("players").unwrap().as_array().unwrap().len());
+        let kicked = results.get("kicked").unwrap().as_array().unwrap();
+        assert_eq!(1, kicked.len());
+        let kick = kicked[0].as_object().unwrap();
+        assert_eq!(Some(&Value::U64(1)), kick.get("id"));
+        assert_eq!(Some(&Value::String("action".to_string())), kick.get("step"));
+        assert_eq!(Some(&Value::String("timed out".to_string())), kick.get("reason"));
+    }
+
+    #[test]
+    fn detached_steps_alone() {
+        let mut game = Game::<Silly>::with_seed(3, 1).unwrap();
+        game.step_deal().unwrap();
+
+        let mut copy = game.detached();
+        step::Action(&mut copy, &mut Silly).step().unwrap();
+
+        assert_eq!(game.deck(), copy.deck());
+        assert_eq!(0, game.board().cards().map_or(0, |c| c.len()));
+        assert_eq!(1, copy.board().cards().map_or(0, |c| c.len()));
+        assert_eq!(0, game.current_player_idx());
+        assert_eq!(1, copy.current_player_idx());
+    }
+
+    #[test]
+    fn stages_follow_the_round() {
+        let mut game = Game::<Silly>::with_seed(3, 1).unwrap();
+        assert_eq!(Stage::Deal, game.stage());
+
+        game.step().unwrap();
+        assert_eq!(Stage::Action(3), game.stage());
+        for left in (0..3).rev() {
+            game.step().unwrap();
+            assert_eq!(if left > 0 { Stage::Action(left) } else { Stage::Reveal }, game.stage());
+        }
+        game.step().unwrap();
+        while game.stage() == Stage::Feed {
+            game.step().unwrap();
+        }
+        assert_eq!(Stage::Bag, game.stage());
+        game.step().unwrap();
+        assert_eq!(Stage::Deal, game.stage());
+        assert_eq!(1, game.round());
+    }
+
+    #[test]
+    fn house_rules_play() {
+        let rules = RulesConfig {
+            carnivore_cards: -2..3,
+            cards_per_species: 2,
+            max_rounds: Some(2),
+            ..RulesConfig::default()
+        };
+        let mut game = Game::with_rules(vec![Silly, Silly, Silly], 1, rules).unwrap();
+
+        step::Deal(&mut game).step().unwrap();
+        assert_eq!(5, game.players()[0].hand().len());
+
+        game.play().unwrap();
+        assert_eq!(2, game.round());
+    }
+
+    #[test]
+    fn official_rules_play_out_the_deck() {
+        let mut game = Game::with_rules(vec![Silly, Silly, Silly], 1, RulesConfig::official()).unwrap();
+
+        game.play().unwrap();
+
+        assert!(game.is_over());
+        assert!(game.deck().len() < game.players().len());
+    }
+
+    #[test]
+    fn house_rules_player_count() {
+        let rules = RulesConfig {
+            min_players: 2,
+            max_players: 2,
+            ..RulesConfig::default()
+        };
+
+        assert!(GameState::with_rules(2, 1, rules.clone()).is_ok());
+        assert!(GameState::with_rules(3, 1, rules).is_err());
+    }
+
+    #[test]
+    fn boxed_choosers_play() {
+        let choosers: Vec<Box<Chooser + Send>> = vec![
+            Box::new(Silly),
+            Box::new(Random::new(1)),
+            Box::new(Silly),
+        ];
+        let mut game = Game::<Box<Chooser + Send>>::with_seed(choosers, 3).unwrap();
+
+        game.play().unwrap();
+
+        assert_eq!(Some(3), game.seed());
+        assert!(game.kicked().is_empty());
+        assert!(game.round() > 0);
+    }
+
     #[test]
     #[ignore]
     fn turn_is_over() {
         unimplemented!()
     }
 
+    #[test]
+    fn is_over_counts_every_players_species() {
+        let mut game = game_with_players(3, &|player| {
+            if player.id() == 1 {
+                for _ in 0..3 {
+                    player.domain_mut().add(Placement::Right);
+                }
+            }
+        });
+        // Three cards and one for each species, or for the species the
+        // others are dealt.
+        let needed = 6 + 4 + 4;
+        let extra = game.deck().len() - needed;
+        game.deals(extra);
+
+        assert!(!game.is_over());
+        game.deals(1);
+        assert!(game.is_over());
+    }
+
     #[test]
     fn deals_correct_number_of_cards() {
         let mut game = game_with_players(3, &|_| {});
@@ -0,0 +1,251 @@
+use std::ops::Range;
+use error::{Error, RuleError};
+use game::{MIN_PLAYERS, MAX_PLAYERS};
+use object::*;
+
+/// The rules a game is played by.
+///
+/// The default rules are the course rules, given by the constants in
+/// `game` and `object`. Changing them gives house rules, for example a
+/// smaller deck, more traits per species, or more cards dealt for each
+/// species. `RulesConfig::official()` gives the rules of the published
+/// board game instead, see `RulesMode`.
+///
+/// Rules can also be read from JSON, see `read_rules`.
+///
+/// # Examples
+///
+/// ```rust
+/// use evolution::game::{GameState, RulesConfig};
+///
+/// // A quick game, with fewer cards of each trait, and only two rounds.
+/// let rules = RulesConfig {
+///     carnivore_cards: -2..3,
+///     trait_cards: 0..1,
+///     max_rounds: Some(2),
+///     ..RulesConfig::default()
+/// };
+/// let state = GameState::with_rules(3, 1, rules).unwrap();
+/// assert_eq!(5 + 15, state.deck().len());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RulesConfig {
+    /// The rules the game is played by, where the course and the board game
+    /// differ.
+    pub mode: RulesMode,
+    /// The fewest players a game can be played with.
+    pub min_players: usize,
+    /// The most players a game can be played with.
+    pub max_players: usize,
+    /// The largest population a species can have.
+    pub max_population: u64,
+    /// The largest body size a species can have.
+    pub max_body_size: u64,
+    /// The most traits a species can have.
+    pub max_traits: usize,
+    /// The food values of the carnivore cards in the deck, one card of each.
+    pub carnivore_cards: Range<i64>,
+    /// The food values of the cards of every other trait in the deck, one
+    /// card of each.
+    pub trait_cards: Range<i64>,
+    /// The food values of the cards of every expansion trait in the deck,
+    /// one card of each. There are no expansion cards by default.
+    pub expansion_cards: Range<i64>,
+    /// The cards dealt to each player at the start of every round.
+    pub cards_per_round: usize,
+    /// The extra cards dealt to each player for every species they have.
+    pub cards_per_species: usize,
+    /// The cards given to a player for each of their species going extinct.
+    pub cards_per_extinction: usize,
+    /// The most rounds to play, the game otherwise ends when the deck runs
+    /// out.
+    pub max_rounds: Option<usize>,
+}
+
+impl RulesConfig {
+    /// Returns the rules of the published board game, played with the
+    /// official mode and limits.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use evolution::game::{RulesConfig, RulesMode};
+    ///
+    /// let rules = RulesConfig::official();
+    /// assert_eq!(RulesMode::Official, rules.mode);
+    /// assert_eq!(6, rules.max_population);
+    /// ```
+    pub fn official() -> Self {
+        RulesConfig {
+            mode: RulesMode::Official,
+            max_population: OFFICIAL_MAX_POPULATION,
+            max_body_size: OFFICIAL_MAX_BODY_SIZE,
+            ..RulesConfig::default()
+        }
+    }
+
+    /// Returns an `Err` saying what's wrong if a game can't be played by
+    /// these rules.
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.min_players < 2 || self.min_players > self.max_players {
+            return Err(Error::Rule(RuleError::PlayerCount(self.min_players)))
+        }
+        if self.max_population == 0 ||
+           self.max_traits == 0 ||
+           self.max_rounds == Some(0) ||
+           self.cards_per_round + self.cards_per_species == 0
+        {
+            return Err(Error::Rule(RuleError::Unplayable))
+        }
+        Ok(())
+    }
+
+    /// Returns true if a game can be played with `n` players.
+    pub fn allows_players(&self, n: usize) -> bool {
+        n >= self.min_players && n <= self.max_players
+    }
+
+    /// Returns a new deck shuffled by the given seed, as described by
+    /// `carnivore_cards`, `trait_cards` and `expansion_cards`.
+    pub fn deck(&self, seed: u64) -> Vec<Card> {
+        let mut cards = Card::deck_within(self.carnivore_cards.clone(), self.trait_cards.clone());
+        cards.extend(Card::expansion_within(self.expansion_cards.clone()));
+        cards.sort();
+        Card::shuffled(cards, seed)
+    }
+
+    /// Returns the number of cards dealt to a player with the given number
+    /// of species at the start of a round.
+    pub fn cards_for(&self, species: usize) -> usize {
+        self.cards_per_round + self.cards_per_species * species
+    }
+}
+
+impl Default for RulesConfig {
+    fn default() -> Self {
+        RulesConfig {
+            mode: RulesMode::Course,
+            min_players: MIN_PLAYERS,
+            max_players: MAX_PLAYERS,
+            max_population: MAX_POPULATION,
+            max_body_size: MAX_BODY_SIZE,
+            max_traits: MAX_TRAITS,
+            carnivore_cards: CARNIVORE_CARD_BOUNDS,
+            trait_cards: NON_CARNIVORE_CARD_BOUNDS,
+            expansion_cards: 0..0,
+            cards_per_round: 3,
+            cards_per_species: 1,
+            cards_per_extinction: CARDS_PER_EXTINCTION,
+            max_rounds: None,
+        }
+    }
+}
+
+/// The largest population a species can have in the board game.
+pub const OFFICIAL_MAX_POPULATION: u64 = 6;
+
+/// The largest body size a species can have in the board game.
+pub const OFFICIAL_MAX_BODY_SIZE: u64 = 6;
+
+/// Which of the two rule books a game follows, where they differ.
+///
+/// The course rules are the CS4500 rules this project was written for. The
+/// official rules follow the published board game as closely as `Trait`
+/// allows. Under the official rules:
+///
+/// - The game doesn't end as soon as the deck can't deal a full round.
+///   When it runs short the remaining cards are dealt one at a time, and
+///   that round is the last.
+/// - A carnivore eats meat from the food bank equal to the body size of the
+///   species it attacked, rather than a single token from the watering
+///   hole. Scavengers eat one meat from the food bank.
+/// - Scavengers only eat when a carnivore eats, so they go hungry when
+///   horns make the attacker extinct.
+/// - An intelligent attacker which doesn't need it's intelligence to get
+///   past a defence negates the defender's horns instead. Intelligence is a
+///   card of the board game, but only an expansion trait here, so it's only
+///   dealt with expansion cards.
+///
+/// Ambush is played by it's card in both, it overcomes warning calls and
+/// nothing else, and ambushing a horned species still costs the attacker.
+/// `Player::score` is the board game's scoring in both, food in the bag,
+/// population and trait cards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RulesMode {
+    Course,
+    Official,
+}
+
+#[cfg(feature = "wire")]
+mod wire;
+#[cfg(feature = "wire")]
+pub use self::wire::read_rules;
+
+#[cfg(test)]
+mod tests {
+    use error::{Error, RuleError};
+    use game::*;
+    use object::*;
+
+    #[test]
+    fn default_is_course() {
+        let rules = RulesConfig::default();
+
+        assert!(rules.validate().is_ok());
+        assert_eq!(Card::shuffled_deck(7), rules.deck(7));
+        assert_eq!(4, rules.cards_for(1));
+        assert!(!rules.allows_players(MIN_PLAYERS - 1));
+        assert!(rules.allows_players(MAX_PLAYERS));
+        assert!(!rules.allows_players(MAX_PLAYERS + 1));
+    }
+
+    #[test]
+    fn official() {
+        let rules = RulesConfig::official();
+
+        assert!(rules.validate().is_ok());
+        assert_eq!(RulesMode::Official, rules.mode);
+        assert_eq!(RulesConfig::default().deck(7), rules.deck(7));
+        assert_eq!(6, rules.max_body_size);
+    }
+
+    #[test]
+    fn expansion() {
+        let rules = RulesConfig {
+            expansion_cards: EXPANSION_CARD_BOUNDS,
+            ..RulesConfig::default()
+        };
+        let mut deck = rules.deck(7);
+        deck.sort();
+
+        assert_eq!(Card::expansion_deck(), deck);
+    }
+
+    #[test]
+    fn validate_player_bounds() {
+        let rules = RulesConfig {
+            min_players: 5,
+            max_players: 4,
+            ..RulesConfig::default()
+        };
+
+        assert!(match rules.validate() {
+            Err(Error::Rule(RuleError::PlayerCount(5))) => true,
+            _ => false,
+        });
+    }
+
+    #[test]
+    fn validate_dealing() {
+        let rules = RulesConfig {
+            cards_per_round: 0,
+            cards_per_species: 0,
+            ..RulesConfig::default()
+        };
+
+        assert!(match rules.validate() {
+            Err(Error::Rule(RuleError::Unplayable)) => true,
+            _ => false,
+        });
+    }
+}
@@ -0,0 +1,141 @@
+use std::io::Read;
+use serde_json as json;
+use evolution_wire as wire;
+use evolution_wire::{ToWire, FromWire};
+use error::Error;
+use game::{RulesConfig, RulesMode};
+
+impl wire::FromWire<wire::Rules> for RulesConfig {
+    fn from_wire(wire: wire::Rules) -> Result<Self, wire::Error> {
+        let mut rules = match wire.mode {
+            Some(wire::Mode::Official) => RulesConfig::official(),
+            Some(wire::Mode::Course) | None => RulesConfig::default(),
+        };
+        if let Some((min, max)) = wire.players {
+            rules.min_players = *min as usize;
+            rules.max_players = *max as usize;
+        }
+        if let Some(population) = wire.population {
+            rules.max_population = *population;
+        }
+        if let Some(body_size) = wire.body_size {
+            rules.max_body_size = *body_size;
+        }
+        if let Some(traits) = wire.traits {
+            rules.max_traits = *traits as usize;
+        }
+        // The wire bounds are inclusive.
+        if let Some((low, high)) = wire.carnivore_cards {
+            rules.carnivore_cards = *low..(*high + 1);
+        }
+        if let Some((low, high)) = wire.trait_cards {
+            rules.trait_cards = *low..(*high + 1);
+        }
+        if let Some((low, high)) = wire.expansion_cards {
+            rules.expansion_cards = *low..(*high + 1);
+        }
+        if let Some(cards) = wire.cards_per_round {
+            rules.cards_per_round = *cards as usize;
+        }
+        if let Some(cards) = wire.cards_per_species {
+            rules.cards_per_species = *cards as usize;
+        }
+        if let Some(cards) = wire.cards_per_extinction {
+            rules.cards_per_extinction = *cards as usize;
+        }
+        if let Some(rounds) = wire.rounds {
+            rules.max_rounds = Some(*rounds as usize);
+        }
+
+        try!(rules.validate().map_err(|e| wire::Error::invalid(format!("{}", e))));
+        Ok(rules)
+    }
+}
+
+impl wire::ToWire<wire::Rules> for RulesConfig {
+    fn to_wire(&self) -> wire::Rules {
+        let mode = match self.mode {
+            RulesMode::Course => wire::Mode::Course,
+            RulesMode::Official => wire::Mode::Official,
+        };
+        wire::Rules {
+            mode: Some(mode),
+            players: Some((self.min_players.to_wire(), self.max_players.to_wire())),
+            population: Some(self.max_population.to_wire()),
+            body_size: Some(self.max_body_size.to_wire()),
+            traits: Some(self.max_traits.to_wire()),
+            carnivore_cards: Some((self.carnivore_cards.start.to_wire(),
+                                   (self.carnivore_cards.end - 1).to_wire())),
+            trait_cards: Some((self.trait_cards.start.to_wire(),
+                               (self.trait_cards.end - 1).to_wire())),
+            expansion_cards: if self.expansion_cards.start < self.expansion_cards.end {
+                Some((self.expansion_cards.start.to_wire(),
+                      (self.expansion_cards.end - 1).to_wire()))
+            } else {
+                None
+            },
+            cards_per_round: Some(self.cards_per_round.to_wire()),
+            cards_per_species: Some(self.cards_per_species.to_wire()),
+            cards_per_extinction: Some(self.cards_per_extinction.to_wire()),
+            rounds: self.max_rounds.and_then(|rounds| wire::NaturalPlus::new(rounds as u64).ok()),
+        }
+    }
+}
+
+/// Read rules from a JSON object, see `wire::Rules`. Rules missing from the
+/// object are left as the rules of its mode, the course rules by default.
+pub fn read_rules<R: Read>(reader: R) -> Result<RulesConfig, Error> {
+    let wire_rules = try!(json::from_reader::<_, wire::Rules>(reader).map_err(wire::Error::from));
+    Ok(try!(RulesConfig::from_wire(wire_rules)))
+}
+
+#[cfg(test)]
+mod tests {
+    use evolution_wire::{self as wire, FromWire, ToWire};
+    use game::*;
+    use super::read_rules;
+
+    #[test]
+    fn to_and_from_wire() {
+        let rules = RulesConfig {
+            max_traits: 5,
+            trait_cards: -1..2,
+            expansion_cards: 0..2,
+            max_rounds: Some(4),
+            ..RulesConfig::default()
+        };
+        let wire_rules: wire::Rules = rules.to_wire();
+
+        assert_eq!(rules, RulesConfig::from_wire(wire_rules).unwrap());
+    }
+
+    #[test]
+    fn read() {
+        let json = r#"{"traits": 4, "carnivore_cards": [-2, 2], "rounds": 3}"#;
+        let rules = read_rules(json.as_bytes()).unwrap();
+
+        assert_eq!(RulesConfig {
+            max_traits: 4,
+            carnivore_cards: -2..3,
+            max_rounds: Some(3),
+            ..RulesConfig::default()
+        }, rules);
+    }
+
+    #[test]
+    fn read_official() {
+        let json = r#"{"mode": "official", "population": 7}"#;
+        let rules = read_rules(json.as_bytes()).unwrap();
+
+        assert_eq!(RulesConfig {
+            max_population: 7,
+            ..RulesConfig::official()
+        }, rules);
+    }
+
+    #[test]
+    fn read_unplayable() {
+        assert!(read_rules(r#"{"players": [4, 3]}"#.as_bytes()).is_err());
+        assert!(read_rules(r#"{"traits": 0}"#.as_bytes()).is_err());
+    }
+}
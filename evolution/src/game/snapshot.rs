@@ -0,0 +1,353 @@
+use std::collections::HashSet;
+use std::time::Duration;
+use evolution_wire::{self as wire, ToWire, FromWire};
+use error::Error;
+use game::*;
+use interact::*;
+use object::*;
+
+/// A snapshot of a game state holds the rules, the seed, the round and its
+/// `Stage`, the players in round order with their hands and species, the
+/// current player and the skipped players by their index in round order,
+/// the watering hole with any cards face down on it, the deck, the event
+/// log, and the records of ejected players. `from_wire` restores it exactly,
+/// at any stage of a round.
+///
+/// The budget, clock and chooser info are left out, they are kept by the
+/// `Game`, see `Game::snapshot`.
+impl ToWire<wire::Snapshot> for GameState {
+    fn to_wire(&self) -> wire::Snapshot {
+        let mut skipped = self.skip_set.iter().cloned().collect::<Vec<_>>();
+        skipped.sort();
+        wire::Snapshot {
+            rules: self.rules.to_wire(),
+            seed: self.seed.map(wire::Natural),
+            round: self.round.to_wire(),
+            stage: self.stage.to_wire(),
+            players: self.players.iter().map(player_to_wire).collect(),
+            current_player: self.current_player.map(|index| index.to_wire()),
+            skipped: skipped.iter().map(|index| index.to_wire()).collect(),
+            food: self.board.food_count().to_wire(),
+            board_cards: self.board.cards().map(|cards| cards_to_wire(cards)),
+            deck: cards_to_wire(&self.deck),
+            events: (&self.events[..]).to_wire(),
+            kicked: (&self.kicked[..]).to_wire(),
+            budget: None,
+            clock: None,
+            info: None,
+        }
+    }
+}
+
+/// Restoring a snapshot returns an error if it holds a game which breaks
+/// its own rules, for example a species larger than the rules allow, or a
+/// current player who isn't playing.
+impl FromWire<wire::Snapshot> for GameState {
+    fn from_wire(wire: wire::Snapshot) -> Result<Self, wire::Error> {
+        let rules = try!(RulesConfig::from_wire(wire.rules));
+
+        let mut players = Vec::new();
+        for player in wire.players {
+            players.push(try!(player_from_wire(player, &rules)));
+        }
+        let ids = players.iter().map(|p| p.id()).collect::<HashSet<_>>();
+        if ids.len() != players.len() {
+            return Err(wire::Error::invalid("players don't have unique ids"))
+        }
+        let current_player = match wire.current_player {
+            Some(index) => Some(try!(index_from_wire(index, players.len(), "current player"))),
+            None => None,
+        };
+        let mut skip_set = HashSet::new();
+        for index in wire.skipped {
+            skip_set.insert(try!(index_from_wire(index, players.len(), "skipped player")));
+        }
+
+        let mut board = Board::default();
+        board.push_foods((0..*wire.food).map(|_| FoodToken).collect());
+        if let Some(cards) = wire.board_cards {
+            board.set_cards(try!(cards_from_wire(cards)));
+        }
+
+        Ok(GameState {
+            players: players,
+            current_player: current_player,
+            skip_set: skip_set,
+            board: board,
+            deck: try!(cards_from_wire(wire.deck)),
+            seed: wire.seed.map(|seed| *seed),
+            events: try!(Vec::from_wire(wire.events)),
+            round: *wire.round as usize,
+            kicked: try!(Vec::from_wire(wire.kicked)),
+            rules: rules,
+            stage: try!(Stage::from_wire(wire.stage)),
+        })
+    }
+}
+
+/// Returns a duration as a wire number of nanoseconds.
+pub fn duration_to_wire(duration: Duration) -> wire::Natural {
+    wire::Natural(duration.as_secs() * 1_000_000_000 + duration.subsec_nanos() as u64)
+}
+
+/// Reads a duration written by `duration_to_wire`.
+pub fn duration_from_wire(nanos: wire::Natural) -> Duration {
+    Duration::new(*nanos / 1_000_000_000, (*nanos % 1_000_000_000) as u32)
+}
+
+/// Returns the index into something of the given length.
+fn index_from_wire(index: wire::Natural, len: usize, what: &str) -> Result<usize, wire::Error> {
+    let index = *index as usize;
+    if index < len {
+        Ok(index)
+    } else {
+        Err(wire::Error::invalid(format!("{} {} is out of bounds", what, index)))
+    }
+}
+
+impl ToWire<wire::snapshot::Stage> for Stage {
+    fn to_wire(&self) -> wire::snapshot::Stage {
+        match *self {
+            Stage::Deal => wire::snapshot::Stage::Deal,
+            Stage::Action(left) => wire::snapshot::Stage::Action(left.to_wire()),
+            Stage::Reveal => wire::snapshot::Stage::Reveal,
+            Stage::Feed => wire::snapshot::Stage::Feed,
+            Stage::Bag => wire::snapshot::Stage::Bag,
+        }
+    }
+}
+
+impl FromWire<wire::snapshot::Stage> for Stage {
+    fn from_wire(wire: wire::snapshot::Stage) -> Result<Self, wire::Error> {
+        Ok(match wire {
+            wire::snapshot::Stage::Deal => Stage::Deal,
+            wire::snapshot::Stage::Action(left) => Stage::Action(*left as usize),
+            wire::snapshot::Stage::Reveal => Stage::Reveal,
+            wire::snapshot::Stage::Feed => Stage::Feed,
+            wire::snapshot::Stage::Bag => Stage::Bag,
+        })
+    }
+}
+
+fn cards_to_wire(cards: &[Card]) -> Vec<wire::snapshot::Card> {
+    cards.iter().map(|card| (card.food_value().to_wire(), card.trait_type().to_wire())).collect()
+}
+
+fn cards_from_wire(cards: Vec<wire::snapshot::Card>) -> Result<Vec<Card>, wire::Error> {
+    let mut vec = Vec::new();
+    for (food_value, trait_type) in cards {
+        vec.push(Card(*food_value, try!(Trait::from_wire(trait_type))));
+    }
+    Ok(vec)
+}
+
+fn species_to_wire(species: &Species) -> wire::snapshot::Species {
+    wire::snapshot::Species {
+        population: species.population().to_wire(),
+        body_size: species.body_size().to_wire(),
+        traits: species.traits().iter().map(|t| t.to_wire()).collect(),
+        food: species.food_count().to_wire(),
+        fat: species.fat_count().to_wire(),
+    }
+}
+
+/// Builds the species up from a default species, so it keeps to the rules.
+fn species_from_wire(wire: wire::snapshot::Species, rules: &RulesConfig) -> Result<Species, Error> {
+    let food = *wire.food;
+    let mut species = Species::default();
+    if *wire.population == 0 {
+        try!(species.kill());
+    }
+    while species.population() < *wire.population {
+        try!(species.breed_up_to(rules.max_population));
+    }
+    while species.body_size() < *wire.body_size {
+        try!(species.grow_up_to(rules.max_body_size));
+    }
+    for t in wire.traits {
+        try!(species.evolve_up_to(try!(Trait::from_wire(t)), rules.max_traits));
+    }
+    for _ in 0..food {
+        try!(species.eat(FoodToken).map_err(|_| {
+            Error::Decode(format!("species can't eat {} food", food))
+        }));
+    }
+    try!(species.store((0..*wire.fat).map(|_| FoodToken).collect()));
+    Ok(species)
+}
+
+fn player_to_wire(player: &Player) -> wire::snapshot::Player {
+    wire::snapshot::Player {
+        id: player.id().to_wire(),
+        bag: player.bag_count().to_wire(),
+        hand: cards_to_wire(player.hand()),
+        domain: player.domain().iter().map(species_to_wire).collect(),
+    }
+}
+
+fn player_from_wire(wire: wire::snapshot::Player, rules: &RulesConfig) -> Result<Player, wire::Error> {
+    let mut domain = Vec::new();
+    for species in wire.domain {
+        domain.push(try!(species_from_wire(species, rules).map_err(|e| {
+            wire::Error::invalid(format!("{}", e))
+        })));
+    }
+
+    let mut player = Player::new(*wire.id);
+    player.push_bag((0..*wire.bag).map(|_| FoodToken).collect());
+    player.push_cards(try!(cards_from_wire(wire.hand)));
+    *player.domain_mut() = domain.into();
+    Ok(player)
+}
+
+impl ToWire<wire::snapshot::Kick> for Kick {
+    fn to_wire(&self) -> wire::snapshot::Kick {
+        let offense = match self.offense {
+            Offense::Action(ref choice) => {
+                wire::snapshot::Offense::Action(choice.as_ref().map(|c| c.to_wire()))
+            },
+            Offense::Feed(ref choice) => {
+                wire::snapshot::Offense::Feed(choice.as_ref().map(|c| c.to_wire()))
+            },
+        };
+        wire::snapshot::Kick {
+            id: self.id.to_wire(),
+            round: self.round.to_wire(),
+            offense: offense,
+            reason: self.reason.clone(),
+            score: self.score.to_wire(),
+        }
+    }
+}
+
+impl FromWire<wire::snapshot::Kick> for Kick {
+    fn from_wire(wire: wire::snapshot::Kick) -> Result<Self, wire::Error> {
+        let offense = match wire.offense {
+            wire::snapshot::Offense::Action(None) => Offense::Action(None),
+            wire::snapshot::Offense::Action(Some(choice)) => {
+                Offense::Action(Some(try!(ActionChoice::from_wire(choice))))
+            },
+            wire::snapshot::Offense::Feed(None) => Offense::Feed(None),
+            wire::snapshot::Offense::Feed(Some(choice)) => {
+                Offense::Feed(Some(try!(FeedChoice::from_wire(choice))))
+            },
+        };
+        Ok(Kick {
+            id: *wire.id,
+            round: *wire.round as usize,
+            offense: offense,
+            reason: wire.reason,
+            score: *wire.score,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use serde_json::{self as json, Value};
+    use evolution_wire::{self as wire, ToWire, FromWire};
+    use error::{Error, ChoiceError};
+    use game::*;
+    use interact::*;
+    use silly::*;
+    use strategies::Strategy;
+
+    fn silly_choosers(game: &GameState) -> HashMap<Id, Silly> {
+        game.players().iter().map(|p| (p.id(), Silly)).collect()
+    }
+
+    fn snapshot(game: &GameState) -> Value {
+        json::to_value(&ToWire::<wire::Snapshot>::to_wire(game))
+    }
+
+    fn restore(snapshot: Value) -> Result<GameState, Error> {
+        let snapshot = try!(json::from_value::<wire::Snapshot>(snapshot).map_err(wire::Error::from));
+        Ok(try!(GameState::from_wire(snapshot)))
+    }
+
+    #[test]
+    fn round_trips_at_every_step() {
+        let mut game = Game::<Silly>::with_seed(4, 7).unwrap();
+        while !game.is_finished() {
+            let snapshot = snapshot(game.state());
+            let restored = restore(snapshot.clone()).unwrap();
+
+            assert_eq!(snapshot, self::snapshot(&restored));
+            assert_eq!(game.stage(), restored.stage());
+            game.step().unwrap();
+        }
+    }
+
+    #[test]
+    fn resumed_game_plays_the_same() {
+        let mut whole = Game::<Silly>::with_seed(3, 11).unwrap();
+        whole.play().unwrap();
+
+        let mut game = Game::<Silly>::with_seed(3, 11).unwrap();
+        for _ in 0..20 {
+            game.step().unwrap();
+        }
+        assert!(game.stage() != Stage::Deal);
+        let snapshot = json::to_value(&game.snapshot());
+        let mut resumed = Game::resume(json::from_value(snapshot.clone()).unwrap(), silly_choosers(&game)).unwrap();
+        assert_eq!(snapshot, json::to_value(&resumed.snapshot()));
+        resumed.play().unwrap();
+
+        assert_eq!(whole.events(), resumed.events());
+        assert_eq!(whole.round(), resumed.round());
+        for (a, b) in whole.players().iter().zip(resumed.players()) {
+            assert_eq!((a.id(), a.score()), (b.id(), b.score()));
+        }
+    }
+
+    #[test]
+    fn round_trips_kicks() {
+        let mut game = Game::<Silly>::with_seed(3, 1).unwrap();
+        game.step().unwrap();
+        game.eject_current_player(Offense::Feed(Some(FeedChoice::Attack(0, 1, 0))), Error::Choice(ChoiceError::Illegal));
+
+        let restored = restore(snapshot(game.state())).unwrap();
+        let kick = &restored.kicked()[0];
+        assert_eq!(1, kick.id);
+        assert_eq!(Offense::Feed(Some(FeedChoice::Attack(0, 1, 0))), kick.offense);
+        assert_eq!(game.kicked()[0].reason, kick.reason);
+    }
+
+    #[test]
+    fn resume_keeps_the_info_of_ejected_players() {
+        let choosers = (0..3).map(|_| Strategy::from_name("greedy").unwrap()).collect();
+        let mut game = Game::with_choosers(choosers, 1).unwrap();
+        game.step().unwrap();
+        game.eject_current_player(Offense::Action(None), Error::Timeout);
+
+        let choosers = game.players().iter().map(|p| (p.id(), Strategy::from_name("greedy").unwrap())).collect();
+        let resumed = Game::resume(game.snapshot(), choosers).unwrap();
+        assert_eq!(Some("greedy"), resumed.info(1));
+        assert_eq!(game.results(), resumed.results());
+    }
+
+    #[test]
+    fn rejects_a_missing_current_player() {
+        let game = Game::<Silly>::with_seed(3, 1).unwrap();
+        let mut snapshot = snapshot(game.state());
+        snapshot.as_object_mut().unwrap().insert("current_player".to_string(), Value::U64(3));
+
+        assert!(match restore(snapshot) {
+            Err(Error::Decode(_)) => true,
+            _ => false,
+        });
+    }
+
+    #[test]
+    fn resume_needs_every_chooser() {
+        let game = Game::<Silly>::with_seed(3, 1).unwrap();
+        let mut choosers = silly_choosers(&game);
+        choosers.remove(&2);
+
+        assert!(match Game::resume(game.snapshot(), choosers) {
+            Err(Error::Decode(_)) => true,
+            _ => false,
+        });
+    }
+}
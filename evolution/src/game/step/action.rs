@@ -1,14 +1,54 @@
 use std::ops::{Deref, DerefMut};
+use error::{Error, ChoiceError};
 use game::*;
 use object::*;
 use interact::*;
 
 /// Players **must** give a card as food, and can choose to give any number
 /// more cards to upgrade their species.
-pub struct Action<'a, C: 'a + Chooser>(pub &'a mut Game<C>, pub &'a mut Choose<ActionObservation, ActionChoice>);
+pub struct Action<'a>(pub &'a mut GameState, pub &'a mut Choose<ActionObservation, ActionChoice>);
+
+/// Transition functions.
+impl GameState {
+    /// Takes the action of the current player, recording it and advancing
+    /// the current player.
+    ///
+    /// # Returns
+    ///
+    /// This function returns an `Error::Choice` result, leaving the game as
+    /// it was, if the choice isn't valid for the current player, or breaks
+    /// the rules.
+    pub fn apply_action(&mut self, choice: ActionChoice) -> Result<(), Error> {
+        let observation: ActionObservation = self.observe();
+        try!(choice.validate(&observation).map_err(Error::Choice));
+        if !observation.is_legal(&choice) {
+            return Err(Error::Choice(ChoiceError::Illegal))
+        }
+        let player = self.current_player().id();
+        debug!("applying choice: {:?}", choice);
+        // Trading only touches the current player and the board, so they're
+        // put back if it fails part way.
+        let idx = self.current_player_idx();
+        let before = (self.players[idx].clone(), self.board.clone());
+        if let Err(e) = self.trade_cards(choice.clone()) {
+            self.players[idx] = before.0;
+            self.board = before.1;
+            return Err(e)
+        }
+        self.record(Event::Action { player: player, choice: Some(choice) });
+        self.advance_current_player();
+        Ok(())
+    }
 
-impl<'a, C: Chooser> Action<'a, C> {
-    fn apply(&mut self, choice: ActionChoice) {
+    /// Trades the current player's cards as the valid choice says, without
+    /// recording it or advancing the current player.
+    ///
+    /// # Returns
+    ///
+    /// This function returns an error if a card or species the choice uses
+    /// isn't there, or the trade breaks a rule, which `apply_action` checks
+    /// for before trading.
+    fn trade_cards(&mut self, choice: ActionChoice) -> Result<(), Error> {
         let ActionChoice {
             food_card,
             population_growths,
@@ -33,12 +73,24 @@ impl<'a, C: Chooser> Action<'a, C> {
         }
 
         let idx = self.current_player_idx();
-
+        let rules = self.rules().clone();
+
+        let hand = self.current_player().hand().len();
+        let mut checked = indices.clone();
+        checked.sort();
+        for (i, &card_index) in checked.iter().enumerate() {
+            if card_index >= hand || (i > 0 && checked[i - 1] == card_index) {
+                return Err(Error::Choice(ChoiceError::Card(card_index)))
+            }
+        }
         let mut card_map = {
             context::Player::new(self, idx).get_cards(indices.as_mut_slice())
         };
+        let mut take = |index: usize| {
+            card_map.remove(&index).ok_or(Error::Choice(ChoiceError::Card(index)))
+        };
 
-        let food_card = card_map.remove(&food_card).expect("couldn't get food card");
+        let food_card = try!(take(food_card));
         self.board_mut().add_card(food_card);
 
         let mut player = context::Player::new(self, idx);
@@ -48,79 +100,83 @@ impl<'a, C: Chooser> Action<'a, C> {
         for BoardTrade { trait_card_indeces, .. } in boards {
             let mut species = player.domain_mut()
                                     .add(Placement::Right);
-            for ref trait_card_index in trait_card_indeces {
-                species.evolve(card_map.remove(trait_card_index).expect("couldn't get trait card").trait_type()).expect("couldn't evolve");
+            for trait_card_index in trait_card_indeces {
+                let card = try!(take(trait_card_index));
+                try!(species.evolve_up_to(card.trait_type(), rules.max_traits));
             }
         }
         for Growth { card_index, species_index } in population_growths {
-            card_map.remove(&card_index).expect("couldn't get population card");
-            player.domain_mut()[species_index].breed().expect("couldn't breed");
+            try!(take(card_index));
+            try!(check_species(&player, species_index));
+            try!(player.domain_mut()[species_index].breed_up_to(rules.max_population));
         }
         for Growth { card_index, species_index } in body_growths {
-            card_map.remove(&card_index).expect("couldn't get body card");
-            player.domain_mut()[species_index].grow().expect("couldn't grow");
+            try!(take(card_index));
+            try!(check_species(&player, species_index));
+            try!(player.domain_mut()[species_index].grow_up_to(rules.max_body_size));
         }
         for TraitTrade { species_index, trait_index, replacement_index } in traits {
-            let new_trait = card_map.remove(&replacement_index).expect("couldn't get replacement trait").trait_type();
-            player.domain_mut()[species_index].exchange_trait(trait_index, new_trait).expect("couldn't swap trait");
+            let new_trait = try!(take(replacement_index)).trait_type();
+            try!(check_species(&player, species_index));
+            try!(player.domain_mut()[species_index].exchange_trait(trait_index, new_trait));
         }
+        Ok(())
+    }
+}
+
+/// Returns an error if the player has no species at the given index.
+fn check_species(player: &Player, species_index: usize) -> Result<(), Error> {
+    if species_index < player.domain().len() {
+        Ok(())
+    } else {
+        Err(Error::Choice(ChoiceError::Species(species_index)))
     }
 }
 
-impl<'a, C: Chooser> step::Step<C> for Action<'a, C> {
-    fn step(&mut self) -> Result<(), ()> {
+impl<'a> step::Step for Action<'a> {
+    fn step(&mut self) -> Result<(), Error> {
         let observation = self.observe();
-        match self.1.choose(&observation) {
+        let player = self.current_player().id();
+        // `apply_action` validates the choice too, so any chooser can be
+        // given to this step, and the offending choice is known when
+        // ejecting.
+        let (offense, reason) = match self.1.choose(&observation) {
             Ok(Some(c)) => {
-                debug!("applying choice: {:?}", c);
-                self.apply(c);
-                // TODO: <needed> Should be:
-                // match self.apply(c) {
-                //     Ok(...) => { ... }
-                //     Err(_) => { panic!("<blame game>") }
-                // }
-                self.advance_current_player();
-                Ok(())
-            }
-            Ok(None) | Err(_) => {
-                self.eject_current_player();
-                Ok(())
+                match self.0.apply_action(c.clone()) {
+                    Ok(()) => return Ok(()),
+                    Err(e) => (Offense::Action(Some(c)), e),
+                }
             }
-        }
+            Ok(None) => (Offense::Action(None), Error::Choice(ChoiceError::Missing)),
+            Err(e) => (Offense::Action(None), e),
+        };
+        self.record(Event::Action { player: player, choice: None });
+        self.eject_current_player(offense, reason);
+        Ok(())
     }
 }
 
-impl<'a, C: Chooser> Deref for Action<'a, C> {
-    type Target = Game<C>;
+impl<'a> Deref for Action<'a> {
+    type Target = GameState;
 
-    fn deref(&self) -> &Game<C> {
+    fn deref(&self) -> &GameState {
         &self.0
     }
 }
 
-impl<'a, C: Chooser> DerefMut for Action<'a, C> {
-    fn deref_mut(&mut self) -> &mut Game<C> {
+impl<'a> DerefMut for Action<'a> {
+    fn deref_mut(&mut self) -> &mut GameState {
         &mut self.0
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use error::{Error, ChoiceError};
     use game::*;
     use interact::*;
     use object::*;
 
-    // TODO: Move the apply logic into it's own function.
-    fn todo() -> ActionChoice {
-        ActionChoice {
-            food_card: 0,
-            population_growths: vec![],
-            body_growths: vec![],
-            boards: vec![],
-            traits: vec![],
-        }
-    }
-
     #[test]
     fn apply_puts_food_card_on_board() {
         let mut game = game_with_players(3, &|player| {
@@ -131,13 +187,13 @@ mod tests {
 
         assert_eq!(1, game.players()[0].hand().len());
 
-        step::Action(&mut game, &mut todo()).apply(ActionChoice {
+        game.trade_cards(ActionChoice {
             food_card: 0,
             population_growths: vec![],
             body_growths: vec![],
             boards: vec![],
             traits: vec![],
-        });
+        }).unwrap();
 
         assert_eq!(0, game.players()[0].hand().len());
         assert_eq!(1, game.board().cards().expect("no cards on board").len());
@@ -157,7 +213,7 @@ mod tests {
 
         assert_eq!(2, game.players()[0].hand().len());
 
-        step::Action(&mut game, &mut todo()).apply(ActionChoice {
+        game.trade_cards(ActionChoice {
             food_card: 0,
             population_growths: vec![
                 Growth {
@@ -168,12 +224,94 @@ mod tests {
             body_growths: vec![],
             boards: vec![],
             traits: vec![],
-        });
+        }).unwrap();
 
         assert_eq!(0, game.players()[0].hand().len());
         assert_eq!(2, game.players()[0].domain()[0].population());
     }
 
+    #[test]
+    fn trade_missing_cards_or_species_is_error() {
+        let mut game = game_with_players(3, &|player| {
+            if player.id() == 1 {
+                player.push_cards(vec![
+                    Card::mock(1, Trait::Scavenger),
+                    Card::mock(1, Trait::Scavenger),
+                ]);
+            }
+        });
+
+        let result = game.trade_cards(ActionChoice {
+            food_card: 0,
+            population_growths: vec![],
+            body_growths: vec![
+                Growth {
+                    species_index: 0,
+                    card_index: 2,
+                },
+            ],
+            boards: vec![],
+            traits: vec![],
+        });
+        assert!(match result {
+            Err(Error::Choice(ChoiceError::Card(2))) => true,
+            _ => false,
+        });
+
+        let result = game.trade_cards(ActionChoice {
+            food_card: 0,
+            population_growths: vec![],
+            body_growths: vec![
+                Growth {
+                    species_index: 0,
+                    card_index: 1,
+                },
+            ],
+            boards: vec![],
+            traits: vec![],
+        });
+        assert!(match result {
+            Err(Error::Choice(ChoiceError::Species(0))) => true,
+            _ => false,
+        });
+    }
+
+    #[test]
+    fn apply_action_breaking_rules_is_illegal() {
+        let mut game = game_with_players(3, &|player| {
+            if player.id() == 1 {
+                player.push_cards(vec![
+                    Card::mock(1, Trait::Scavenger),
+                    Card::mock(1, Trait::Scavenger),
+                ]);
+                player.domain_mut().add(Placement::Right);
+                player.domain_mut()[0].evolve(Trait::Scavenger).unwrap();
+                player.domain_mut()[0].evolve(Trait::Ambush).unwrap();
+            }
+        });
+
+        // Swapping the ambush for a second scavenger.
+        let result = game.apply_action(ActionChoice {
+            food_card: 0,
+            population_growths: vec![],
+            body_growths: vec![],
+            boards: vec![],
+            traits: vec![
+                TraitTrade {
+                    species_index: 0,
+                    trait_index: 1,
+                    replacement_index: 1,
+                },
+            ],
+        });
+
+        assert!(match result {
+            Err(Error::Choice(ChoiceError::Illegal)) => true,
+            _ => false,
+        });
+        assert_eq!(2, game.players()[0].hand().len());
+    }
+
     #[test]
     fn apply_grows_body() {
         let mut game = game_with_players(3, &|player| {
@@ -188,7 +326,7 @@ mod tests {
 
         assert_eq!(2, game.players()[0].hand().len());
 
-        step::Action(&mut game, &mut todo()).apply(ActionChoice {
+        game.trade_cards(ActionChoice {
             food_card: 0,
             population_growths: vec![],
             body_growths: vec![
@@ -199,7 +337,7 @@ mod tests {
             ],
             boards: vec![],
             traits: vec![],
-        });
+        }).unwrap();
 
         assert_eq!(0, game.players()[0].hand().len());
         assert_eq!(1, game.players()[0].domain()[0].body_size());
@@ -222,7 +360,7 @@ mod tests {
 
         assert_eq!(5, game.players()[0].hand().len());
 
-        step::Action(&mut game, &mut todo()).apply(ActionChoice{
+        game.trade_cards(ActionChoice{
             food_card: 0,
             population_growths: vec![],
             body_growths: vec![],
@@ -233,7 +371,7 @@ mod tests {
                 }
             ],
             traits: vec![],
-        });
+        }).unwrap();
 
         assert_eq!(0, game.players()[0].hand().len());
         assert!(game.players()[0].domain()[1].has_trait(Trait::Carnivore));
@@ -257,7 +395,7 @@ mod tests {
         assert_eq!(2, game.players()[0].hand().len());
         assert!(game.players()[0].domain()[0].has_trait(Trait::Carnivore));
 
-        step::Action(&mut game, &mut todo()).apply(ActionChoice{
+        game.trade_cards(ActionChoice{
             food_card: 0,
             population_growths: vec![],
             body_growths: vec![],
@@ -269,13 +407,56 @@ mod tests {
                     replacement_index: 1,
                 }
             ],
-        });
+        }).unwrap();
 
         assert_eq!(0, game.players()[0].hand().len());
         assert!(game.players()[0].domain()[0].has_trait(Trait::Burrowing));
         assert!(!game.players()[0].domain()[0].has_trait(Trait::Carnivore));
     }
 
+    #[test]
+    fn apply_action_leaves_invalid_choices() {
+        let mut game = game_with_players(3, &|player| {
+            if player.id() == 1 {
+                player.push_card(Card::mock(4, Trait::Burrowing));
+            }
+        });
+        let choice = ActionChoice {
+            food_card: 1,
+            population_growths: vec![],
+            body_growths: vec![],
+            boards: vec![],
+            traits: vec![],
+        };
+
+        assert!(game.apply_action(choice).is_err());
+        assert_eq!(1, game.players()[0].hand().len());
+        assert_eq!(0, game.current_player_idx());
+        assert!(game.kicked().is_empty());
+    }
+
+    #[test]
+    fn apply_action_advances() {
+        let mut game = game_with_players(3, &|player| {
+            if player.id() == 1 {
+                player.push_card(Card::mock(4, Trait::Burrowing));
+            }
+        });
+        let choice = ActionChoice {
+            food_card: 0,
+            population_growths: vec![],
+            body_growths: vec![],
+            boards: vec![],
+            traits: vec![],
+        };
+
+        game.apply_action(choice).unwrap();
+
+        assert_eq!(0, game.players()[0].hand().len());
+        assert_eq!(1, game.current_player_idx());
+        assert_eq!(2, game.events().len());
+    }
+
     #[test]
     fn step_kicks_on_invalid_actions() {
         let mut game = game_with_players(3, &|player| {
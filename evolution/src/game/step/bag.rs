@@ -1,40 +1,52 @@
 use std::ops::{Deref, DerefMut};
-use interact::*;
+use error::Error;
 use game::*;
 
-/// cull all of the species in the game, giving 2 cards to each player
-/// per species that went extinct. Then bag the food for all players.
-pub struct Bag<'a, C: 'a + Chooser>(pub &'a mut Game<C>);
-
-impl<'a, C: Chooser> step::Step<C> for Bag<'a, C> {
-    fn step(&mut self) -> Result<(), ()> {
-        trace!("@Bag.step");
-
-        let game: *mut Game<C> = self.0;
-        unsafe {
-            for player in (*game).players_mut() {
-                let extinctions = try!(player.domain_mut().cull());
-                let cards = (*game).deals(extinctions * 2);
-                player.push_cards(cards);
-                player.bag_food();
-            }
+/// cull all of the species in the game, giving the rules'
+/// `cards_per_extinction` cards to each player per species that went
+/// extinct. Then bag the food for all players.
+pub struct Bag<'a>(pub &'a mut GameState);
+
+/// Transition functions.
+impl GameState {
+    /// Culls every species, dealing each player the rules'
+    /// `cards_per_extinction` cards per species that went extinct, then
+    /// moves the food on their species into their bags, and starts the next
+    /// round with the next starting player.
+    pub fn bag(&mut self) -> Result<(), Error> {
+        let mut all_extinctions = vec![];
+        for i in 0..self.players.len() {
+            let extinctions = try!(self.players[i].domain_mut().cull());
+            let cards = self.deals(self.rules.cards_per_extinction * extinctions);
+            let player = &mut self.players[i];
+            player.push_cards(cards);
+            player.bag_food();
+            all_extinctions.push((player.id(), extinctions));
         }
+        self.record(Event::Bag { extinctions: all_extinctions });
 
         self.advance_starting_player();
         Ok(())
     }
 }
 
-impl<'a, C: Chooser> Deref for Bag<'a, C> {
-    type Target = Game<C>;
+impl<'a> step::Step for Bag<'a> {
+    fn step(&mut self) -> Result<(), Error> {
+        trace!("@Bag.step");
+        self.0.bag()
+    }
+}
+
+impl<'a> Deref for Bag<'a> {
+    type Target = GameState;
 
-    fn deref(&self) -> &Game<C> {
+    fn deref(&self) -> &GameState {
         &self.0
     }
 }
 
-impl<'a, C: Chooser> DerefMut for Bag<'a, C> {
-    fn deref_mut(&mut self) -> &mut Game<C> {
+impl<'a> DerefMut for Bag<'a> {
+    fn deref_mut(&mut self) -> &mut GameState {
         &mut self.0
     }
 }
@@ -54,7 +66,7 @@ mod tests {
 
         for player in game.players() {
             assert_eq!(2, player.domain()[0].population());
-            assert_eq!(1, player.domain()[0].food().len());
+            assert_eq!(1, player.domain()[0].food_count());
         }
 
         step::Bag(&mut game).step().unwrap();
@@ -83,6 +95,22 @@ mod tests {
         }
     }
 
+    #[test]
+    fn bag_deals_the_rules_cards_per_extinction() {
+        let mut game = game_with_players(3, &|player| {
+            player.domain_mut().add(Placement::Right);
+            player.domain_mut().add(Placement::Right);
+        });
+        game.rules.cards_per_extinction = 3;
+
+        step::Bag(&mut game).step().unwrap();
+
+        for player in game.players() {
+            assert_eq!(0, player.domain().len());
+            assert_eq!(6, player.hand().len());
+        }
+    }
+
     #[test]
     fn bag_moves_food_tokens_to_players_bags() {
         let mut game = game_with_players(3, &|player| {
@@ -93,17 +121,17 @@ mod tests {
         });
 
         for player in game.players() {
-            assert_eq!(0, player.bag().len());
-            assert_eq!(1, player.domain()[0].food().len());
-            assert_eq!(1, player.domain()[1].food().len());
+            assert_eq!(0, player.bag_count());
+            assert_eq!(1, player.domain()[0].food_count());
+            assert_eq!(1, player.domain()[1].food_count());
         }
 
         step::Bag(&mut game).step().unwrap();
 
         for player in game.players() {
-            assert_eq!(2, player.bag().len());
-            assert_eq!(0, player.domain()[0].food().len());
-            assert_eq!(0, player.domain()[1].food().len());
+            assert_eq!(2, player.bag_count());
+            assert_eq!(0, player.domain()[0].food_count());
+            assert_eq!(0, player.domain()[1].food_count());
         }
     }
 
@@ -116,9 +144,9 @@ mod tests {
     //
     //     for player in game.players() {
     //         assert_eq!(1, player.domain()[0].population());
-    //         assert_eq!(0, player.domain()[0].food().len());
+    //         assert_eq!(0, player.domain()[0].food_count());
     //         assert_eq!(1, player.domain()[1].population());
-    //         assert_eq!(0, player.domain()[1].food().len());
+    //         assert_eq!(0, player.domain()[1].food_count());
     //         assert_eq!(0, player.hand().len());
     //     }
     //
@@ -1,12 +1,12 @@
 use std::ops::{Deref, DerefMut};
+use error::{Error, RuleError};
 use game::*;
-use interact::*;
 use object::*;
 
-pub struct Deal<'a, C: 'a + Chooser>(pub &'a mut Game<C>);
+pub struct Deal<'a>(pub &'a mut GameState);
 
-impl<'a, C: Chooser> Deal<'a, C> {
-    pub fn deal_species(&mut self) -> Result<(), ()> {
+impl<'a> Deal<'a> {
+    pub fn deal_species(&mut self) -> Result<(), Error> {
         for player in self.players_mut() {
             if player.domain().len() == 0 {
                 debug!("giving player {} a species board", player.id());
@@ -16,49 +16,79 @@ impl<'a, C: Chooser> Deal<'a, C> {
         Ok(())
     }
 
-    pub fn deal_cards(&mut self) -> Result<(), ()> {
+    pub fn deal_cards(&mut self) -> Result<(), Error> {
         let mut amounts = vec![];
         for player in self.players() {
-            amounts.push(3 + player.domain().len())
+            amounts.push(self.rules().cards_for(player.domain().len()))
         }
-        let mut players_cards = vec![];
-        for amount in amounts {
-            let cards = self.deals(amount);
-            if cards.len() != amount {
-                return Err(())
+        let total = amounts.iter().fold(0, |a, n| a + n);
+        let players_cards = if total > self.deck().len() &&
+                               self.rules().mode == RulesMode::Official
+        {
+            self.deal_short(&amounts)
+        } else {
+            let mut players_cards = vec![];
+            for amount in amounts {
+                let cards = self.deals(amount);
+                if cards.len() != amount {
+                    return Err(Error::Rule(RuleError::EmptyDeck))
+                }
+                players_cards.push(cards);
             }
-            players_cards.push(cards);
-        }
+            players_cards
+        };
+        let mut hands = vec![];
         for (player, cards) in self.players_mut()
                                    .iter_mut()
                                    .zip(players_cards.into_iter())
         {
             debug!("giving player {} cards: {}", player.id(), cards.len());
+            hands.push((player.id(), cards.clone()));
             player.push_cards(cards);
         }
+        self.record(Event::Deal { hands: hands });
         Ok(())
     }
+
+    /// Deals what's left of a deck too short for the given amounts, one
+    /// card at a time around the players, so the shortage is shared.
+    fn deal_short(&mut self, amounts: &[usize]) -> Vec<Vec<Card>> {
+        let mut players_cards = amounts.iter().map(|_| vec![]).collect::<Vec<_>>();
+        let most = amounts.iter().cloned().max().unwrap_or(0);
+        for i in 0..most {
+            for (cards, &amount) in players_cards.iter_mut().zip(amounts.iter()) {
+                if i < amount {
+                    match self.deal() {
+                        Some(card) => cards.push(card),
+                        None => return players_cards,
+                    }
+                }
+            }
+        }
+        players_cards
+    }
 }
 
-impl<'a, C: Chooser> step::Step<C> for Deal<'a, C> {
-    fn step(&mut self) -> Result<(), ()> {
+impl<'a> step::Step for Deal<'a> {
+    fn step(&mut self) -> Result<(), Error> {
         trace!("@Deal.step");
+        self.round += 1;
         try!(self.deal_species());
         try!(self.deal_cards());
         Ok(())
     }
 }
 
-impl<'a, C: Chooser> Deref for Deal<'a, C> {
-    type Target = Game<C>;
+impl<'a> Deref for Deal<'a> {
+    type Target = GameState;
 
-    fn deref(&self) -> &Game<C> {
+    fn deref(&self) -> &GameState {
         &self.0
     }
 }
 
-impl<'a, C: Chooser> DerefMut for Deal<'a, C> {
-    fn deref_mut(&mut self) -> &mut Game<C> {
+impl<'a> DerefMut for Deal<'a> {
+    fn deref_mut(&mut self) -> &mut GameState {
         &mut self.0
     }
 }
@@ -165,6 +195,26 @@ mod tests {
         }
     }
 
+    #[test]
+    fn official_deals_a_short_deck_around() {
+        let mut game = game_with_players(3, &|player| {
+            for _ in 0..player.id() {
+                player.domain_mut().add(Placement::Right);
+            }
+        });
+        game.rules.mode = RulesMode::Official;
+        let keep = game.deck.len() - 13;
+        game.deals(keep);
+
+        step::Deal(&mut game).step().unwrap();
+
+        assert_eq!(4, game.players()[0].hand().len());
+        assert_eq!(5, game.players()[1].hand().len());
+        assert_eq!(4, game.players()[2].hand().len());
+        assert!(game.deck().is_empty());
+        assert!(game.is_over());
+    }
+
     #[test]
     fn deal_cards_breaks_after_cards_exhausted() {
         let mut game = game_with_players(3, &|player| {
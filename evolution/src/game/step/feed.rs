@@ -1,6 +1,7 @@
 use std::ops::{Deref, DerefMut};
+use error::{Error, ChoiceError};
 use game::*;
-use game::context::{Species, Situation};
+use game::context::{Species, Situation, Outcome};
 use interact::*;
 
 /// A single step of feeding in the game. This step is in charge of getting
@@ -9,71 +10,116 @@ use interact::*;
 ///
 /// Both `Feed` and `Attack` calls a species `feed` method, which triggers
 /// other actions based on the traits of the species in the game.
-pub struct Feed<'a, C: 'a + Chooser>(pub &'a mut Game<C>, pub &'a mut Choose<FeedObservation, FeedChoice>);
+pub struct Feed<'a>(pub &'a mut GameState, pub &'a mut Choose<FeedObservation, FeedChoice>);
+
+/// Transition functions.
+impl GameState {
+    /// Feeds with the choice of the current player, recording it and
+    /// advancing the current player. A player whose feeding fails is
+    /// ejected.
+    ///
+    /// # Returns
+    ///
+    /// This function returns an `Error::Choice` result, leaving the game as
+    /// it was, if the choice isn't one of the current player's legal choices,
+    /// otherwise it returns what the feeding changed. Storing food as fat,
+    /// abstaining and being ejected change nothing an `Outcome` records.
+    pub fn apply_feed(&mut self, choice: FeedChoice) -> Result<Outcome, Error> {
+        let observation: FeedObservation = self.observe();
+        if !observation.choices().contains(&choice) {
+            return Err(Error::Choice(ChoiceError::Illegal))
+        }
+        let player = self.current_player().id();
+        debug!("applying choice: {:?}", choice);
+        self.record(Event::Feed { player: player, choice: Some(choice) });
+        let outcome = match self.feed_species(choice) {
+            Ok(outcome) => outcome,
+            Err(e) => {
+                self.eject_current_player(Offense::Feed(Some(choice)), e);
+                Outcome::new()
+            }
+        };
+        self.advance_current_player();
+        Ok(outcome)
+    }
 
-impl<'a, C: Chooser> Feed<'a, C> {
-    fn apply(&mut self, mut choice: FeedChoice) -> Result<(), ()> {
+    /// Feeds the current player's species as the legal choice says, without
+    /// recording the choice or advancing the current player.
+    fn feed_species(&mut self, mut choice: FeedChoice) -> Result<Outcome, Error> {
         choice.internalize(self);
         let idx = self.current_player_idx();
         match choice {
             FeedChoice::Abstain => {
                 self.skip_advance_current_player();
-                Ok(())
+                Ok(Outcome::new())
             }
             FeedChoice::Feed(sx) => {
-                Species::new(self, (idx, sx)).feed();
-                Ok(())
+                let outcome = Species::new(self, (idx, sx)).feed();
+                debug!("feeding changed: {:?}", outcome.changes());
+                Ok(outcome)
             }
             FeedChoice::Store(sx, amount) => {
                 Species::new(self, (idx, sx)).store(amount);
-                Ok(())
+                Ok(Outcome::new())
             }
             FeedChoice::Attack(sx, tx, dx) => {
-                Situation::new(self, (idx, sx, tx, dx)).fight()
+                let player = self.players()[idx].id();
+                let target = self.players()[tx].id();
+                let outcome = try!(Situation::new(self, (idx, sx, tx, dx)).fight());
+                debug!("attack changed: {:?}", outcome.changes());
+                // The attacker is killed by horns before the defender is
+                // killed, so it's the first to go extinct if it does.
+                let extinctions = outcome.extinctions();
+                let attacker_extinct = extinctions.first() == Some(&(player, sx));
+                let defender_extinct = extinctions.len() > if attacker_extinct { 1 } else { 0 };
+                self.record(Event::Attack {
+                    player: player,
+                    attacker: sx,
+                    target: target,
+                    defender: dx,
+                    attacker_extinct: attacker_extinct,
+                    defender_extinct: defender_extinct,
+                });
+                Ok(outcome)
             }
         }
     }
 }
 
-impl<'a, C: Chooser> step::Step<C> for Feed<'a, C> {
-    fn step(&mut self) -> Result<(), ()> {
+impl<'a> step::Step for Feed<'a> {
+    fn step(&mut self) -> Result<(), Error> {
         let observation = self.observe();
-        match self.1.choose(&observation) {
+        let player = self.current_player().id();
+        let (offense, reason) = match self.1.choose(&observation) {
             Ok(Some(c)) => {
-                debug!("applying choice: {:?}", c);
-                match self.apply(c) {
-                    Ok(_) => {}
-                    Err(_) => {
-                        warn!("ejecting playing during choice application");
-                        self.eject_current_player()
-                    },
+                match self.0.apply_feed(c) {
+                    Ok(_) => return Ok(()),
+                    Err(e) => (Offense::Feed(Some(c)), e),
                 }
-                self.advance_current_player();
-                Ok(())
             }
             Ok(None) => {
+                self.record(Event::Skip { player: player });
                 self.skip_advance_current_player();
-                Ok(())
+                return Ok(())
             }
-            Err(_) => {
-                warn!("error getting player choice");
-                self.eject_current_player();
-                Ok(())
-            }
-        }
+            Err(e) => (Offense::Feed(None), e),
+        };
+        self.record(Event::Feed { player: player, choice: None });
+        self.eject_current_player(offense, reason);
+        Ok(())
     }
 }
 
-impl<'a, C: Chooser> Deref for Feed<'a, C> {
-    type Target = Game<C>;
+impl<'a> Deref for Feed<'a> {
+    type Target = GameState;
 
-    fn deref(&self) -> &Game<C> {
+    fn deref(&self) -> &GameState {
         &self.0
     }
 }
 
-impl<'a, C: Chooser> DerefMut for Feed<'a, C> {
-    fn deref_mut(&mut self) -> &mut Game<C> {
+impl<'a> DerefMut for Feed<'a> {
+    fn deref_mut(&mut self) -> &mut GameState {
         &mut self.0
     }
 }
@@ -84,11 +130,6 @@ mod tests {
     use interact::*;
     use object::*;
 
-    // TODO: Move the apply logic into it's own function.
-    fn todo() -> FeedChoice {
-        FeedChoice::Abstain
-    }
-
     #[test]
     fn apply_abstain() {
         let mut game = game_with_players(3, &|player| {
@@ -98,13 +139,13 @@ mod tests {
         });
         game.board_mut().push_food(FoodToken);
 
-        assert_eq!(1, game.board().food().len());
-        assert_eq!(0, game.players()[0].domain()[0].food().len());
+        assert_eq!(1, game.board().food_count());
+        assert_eq!(0, game.players()[0].domain()[0].food_count());
 
-        step::Feed(&mut game, &mut todo()).apply(FeedChoice::Abstain).unwrap();
+        game.feed_species(FeedChoice::Abstain).unwrap();
 
-        assert_eq!(1, game.board().food().len());
-        assert_eq!(0, game.players()[0].domain()[0].food().len());
+        assert_eq!(1, game.board().food_count());
+        assert_eq!(0, game.players()[0].domain()[0].food_count());
     }
 
     #[test]
@@ -116,13 +157,13 @@ mod tests {
         });
         game.board_mut().push_food(FoodToken);
 
-        assert_eq!(1, game.board().food().len());
-        assert_eq!(0, game.players()[0].domain()[0].food().len());
+        assert_eq!(1, game.board().food_count());
+        assert_eq!(0, game.players()[0].domain()[0].food_count());
 
-        step::Feed(&mut game, &mut todo()).apply(FeedChoice::Feed(0)).unwrap();
+        game.feed_species(FeedChoice::Feed(0)).unwrap();
 
-        assert_eq!(0, game.board().food().len());
-        assert_eq!(1, game.players()[0].domain()[0].food().len());
+        assert_eq!(0, game.board().food_count());
+        assert_eq!(1, game.players()[0].domain()[0].food_count());
     }
 
     #[test]
@@ -137,13 +178,13 @@ mod tests {
         });
         game.board_mut().push_foods(vec![FoodToken, FoodToken, FoodToken]);
 
-        assert_eq!(3, game.board().food().len());
-        assert_eq!(0, game.players()[0].domain()[0].fat().len());
+        assert_eq!(3, game.board().food_count());
+        assert_eq!(0, game.players()[0].domain()[0].fat_count());
 
-        step::Feed(&mut game, &mut todo()).apply(FeedChoice::Store(0, 2)).unwrap();
+        game.feed_species(FeedChoice::Store(0, 2)).unwrap();
 
-        assert_eq!(1, game.board().food().len());
-        assert_eq!(2, game.players()[0].domain()[0].fat().len());
+        assert_eq!(1, game.board().food_count());
+        assert_eq!(2, game.players()[0].domain()[0].fat_count());
     }
 
     #[test]
@@ -162,16 +203,16 @@ mod tests {
         });
         game.board_mut().push_food(FoodToken);
 
-        assert_eq!(1, game.board().food().len());
+        assert_eq!(1, game.board().food_count());
         assert_eq!(1, game.players()[0].domain().len());
-        assert_eq!(0, game.players()[0].domain()[0].food().len());
+        assert_eq!(0, game.players()[0].domain()[0].food_count());
         assert_eq!(1, game.players()[1].domain().len());
 
-        step::Feed(&mut game, &mut todo()).apply(FeedChoice::Attack(0, 0, 0)).unwrap();
+        game.feed_species(FeedChoice::Attack(0, 0, 0)).unwrap();
 
-        assert_eq!(0, game.board().food().len());
+        assert_eq!(0, game.board().food_count());
         assert_eq!(1, game.players()[0].domain().len());
-        assert_eq!(1, game.players()[0].domain()[0].food().len());
+        assert_eq!(1, game.players()[0].domain()[0].food_count());
         assert_eq!(0, game.players()[1].domain().len());
     }
 
@@ -186,13 +227,13 @@ mod tests {
         });
         game.board_mut().push_foods(vec![FoodToken, FoodToken]);
 
-        assert_eq!(2, game.board().food().len());
-        assert_eq!(0, game.players()[0].domain()[0].food().len());
+        assert_eq!(2, game.board().food_count());
+        assert_eq!(0, game.players()[0].domain()[0].food_count());
 
-        step::Feed(&mut game, &mut todo()).apply(FeedChoice::Feed(0)).unwrap();
+        game.feed_species(FeedChoice::Feed(0)).unwrap();
 
-        assert_eq!(0, game.board().food().len());
-        assert_eq!(2, game.players()[0].domain()[0].food().len());
+        assert_eq!(0, game.board().food_count());
+        assert_eq!(2, game.players()[0].domain()[0].food_count());
     }
 
     #[test]
@@ -218,13 +259,13 @@ mod tests {
         game.board_mut().push_foods(vec![FoodToken, FoodToken, FoodToken]);
 
         assert_eq!(2, game.current_player_idx());
-        assert_eq!(3, game.board().food().len());
-        assert_eq!(0, game.players()[2].domain()[1].food().len());
+        assert_eq!(3, game.board().food_count());
+        assert_eq!(0, game.players()[2].domain()[1].food_count());
 
-        step::Feed(&mut game, &mut todo()).apply(FeedChoice::Attack(0, 0, 0)).unwrap();
+        game.feed_species(FeedChoice::Attack(0, 0, 0)).unwrap();
 
-        assert_eq!(0, game.board().food().len());
-        assert_eq!(2, game.players()[2].domain()[1].food().len());
+        assert_eq!(0, game.board().food_count());
+        assert_eq!(2, game.players()[2].domain()[1].food_count());
     }
 
     #[test]
@@ -239,13 +280,13 @@ mod tests {
         });
         game.board_mut().push_foods(vec![FoodToken, FoodToken]);
 
-        assert_eq!(2, game.board().food().len());
-        assert_eq!(0, game.players()[0].domain()[0].food().len());
+        assert_eq!(2, game.board().food_count());
+        assert_eq!(0, game.players()[0].domain()[0].food_count());
 
-        step::Feed(&mut game, &mut todo()).apply(FeedChoice::Attack(0, 1, 0)).unwrap();
+        game.feed_species(FeedChoice::Attack(0, 1, 0)).unwrap();
 
-        assert_eq!(0, game.board().food().len());
-        assert_eq!(2, game.players()[0].domain()[0].food().len());
+        assert_eq!(0, game.board().food_count());
+        assert_eq!(2, game.players()[0].domain()[0].food_count());
     }
 
     #[test]
@@ -262,17 +303,17 @@ mod tests {
         });
         game.board_mut().push_foods(vec![FoodToken, FoodToken, FoodToken, FoodToken]);
 
-        assert_eq!(4, game.board().food().len());
-        assert_eq!(0, game.players()[0].domain()[0].food().len());
-        assert_eq!(0, game.players()[0].domain()[1].food().len());
-        assert_eq!(0, game.players()[0].domain()[2].food().len());
+        assert_eq!(4, game.board().food_count());
+        assert_eq!(0, game.players()[0].domain()[0].food_count());
+        assert_eq!(0, game.players()[0].domain()[1].food_count());
+        assert_eq!(0, game.players()[0].domain()[2].food_count());
 
-        step::Feed(&mut game, &mut todo()).apply(FeedChoice::Feed(0)).unwrap();
+        game.feed_species(FeedChoice::Feed(0)).unwrap();
 
-        assert_eq!(1, game.board().food().len());
-        assert_eq!(1, game.players()[0].domain()[0].food().len());
-        assert_eq!(1, game.players()[0].domain()[1].food().len());
-        assert_eq!(1, game.players()[0].domain()[2].food().len());
+        assert_eq!(1, game.board().food_count());
+        assert_eq!(1, game.players()[0].domain()[0].food_count());
+        assert_eq!(1, game.players()[0].domain()[1].food_count());
+        assert_eq!(1, game.players()[0].domain()[2].food_count());
     }
 
     #[test]
@@ -297,14 +338,14 @@ mod tests {
         game.board_mut().push_foods(vec![FoodToken, FoodToken, FoodToken]);
 
         assert_eq!(2, game.current_player_idx());
-        assert_eq!(3, game.board().food().len());
-        assert_eq!(0, game.players()[2].domain()[1].food().len());
+        assert_eq!(3, game.board().food_count());
+        assert_eq!(0, game.players()[2].domain()[1].food_count());
 
-        step::Feed(&mut game, &mut todo()).apply(FeedChoice::Attack(0, 1, 0)).unwrap();
+        game.feed_species(FeedChoice::Attack(0, 1, 0)).unwrap();
 
-        assert_eq!(1, game.board().food().len());
+        assert_eq!(1, game.board().food_count());
         assert_eq!(0, game.players()[1].domain().len());
-        assert_eq!(1, game.players()[2].domain()[1].food().len());
+        assert_eq!(1, game.players()[2].domain()[1].food_count());
     }
 
     #[test]
@@ -327,15 +368,62 @@ mod tests {
         game.board_mut().push_foods(vec![FoodToken, FoodToken, FoodToken]);
 
         assert_eq!(2, game.current_player_idx());
-        assert_eq!(3, game.board().food().len());
+        assert_eq!(3, game.board().food_count());
 
-        step::Feed(&mut game, &mut todo()).apply(FeedChoice::Attack(0, 1, 0)).unwrap();
+        game.feed_species(FeedChoice::Attack(0, 1, 0)).unwrap();
 
-        assert_eq!(3, game.board().food().len());
+        assert_eq!(3, game.board().food_count());
         assert_eq!(0, game.players()[2].domain().len());
         assert_eq!(0, game.players()[1].domain().len());
     }
 
+    #[test]
+    fn attack_event_records_extinctions() {
+        let mut game = game_with_players(3, &|player| {
+            match player.id() {
+                1 => {
+                    player.domain_mut().add(Placement::Right);
+                    player.domain_mut()[0].evolve(Trait::Carnivore).unwrap();
+                },
+                2 => {
+                    player.domain_mut().add(Placement::Right);
+                    player.domain_mut()[0].breed().unwrap();
+                },
+                3 => {
+                    player.domain_mut().add(Placement::Right);
+                    player.domain_mut()[0].evolve(Trait::Horns).unwrap();
+                },
+                _ => {},
+            }
+        });
+        game.board_mut().push_foods(vec![FoodToken, FoodToken]);
+
+        game.feed_species(FeedChoice::Attack(0, 0, 0)).unwrap();
+        game.players_mut()[0].domain_mut()[0].take_food();
+        game.feed_species(FeedChoice::Attack(0, 1, 0)).unwrap();
+
+        assert_eq!(0, game.players()[0].domain().len());
+        assert_eq!(0, game.players()[2].domain().len());
+        assert_eq!(&[
+            Event::Attack {
+                player: 1,
+                attacker: 0,
+                target: 2,
+                defender: 0,
+                attacker_extinct: false,
+                defender_extinct: false,
+            },
+            Event::Attack {
+                player: 1,
+                attacker: 0,
+                target: 3,
+                defender: 0,
+                attacker_extinct: true,
+                defender_extinct: true,
+            },
+        ], &game.events()[1..]);
+    }
+
     #[test]
     fn apply_remove_extinct() {
         let mut game = game_with_players(3, &|player| {
@@ -357,9 +445,23 @@ mod tests {
         assert_eq!(2, game.current_player_idx());
         assert_eq!(0, game.players()[1].hand().len());
 
-        step::Feed(&mut game, &mut todo()).apply(FeedChoice::Attack(0, 1, 0)).unwrap();
+        game.feed_species(FeedChoice::Attack(0, 1, 0)).unwrap();
 
         assert_eq!(0, game.players()[1].domain().len());
         assert_eq!(2, game.players()[1].hand().len());
     }
+
+    #[test]
+    fn step_ejects_illegal_choice() {
+        let mut game = game_with_players(3, &|player| {
+            player.domain_mut().add(Placement::Right);
+        });
+        game.board_mut().push_foods(vec![FoodToken]);
+
+        step::Feed(&mut game, &mut FeedChoice::Feed(3)).step().unwrap();
+
+        assert_eq!(2, game.players().len());
+        assert_eq!(1, game.kicked()[0].id);
+        assert_eq!(Offense::Feed(Some(FeedChoice::Feed(3))), game.kicked()[0].offense);
+    }
 }
@@ -1,11 +1,14 @@
 use std::ops::{Deref, DerefMut};
-use game::Game;
-use interact::*;
+use error::Error;
+use game::GameState;
 
-/// Represents some mutable effect on a `Game`.
-pub trait Step<C: Chooser>: Deref<Target=Game<C>> + DerefMut {
+/// Represents some mutable effect on a `GameState`.
+pub trait Step: Deref<Target=GameState> + DerefMut {
     /// Take one step, mutating the game for future steps.
-    fn step(&mut self) -> Result<(), ()>;
+    ///
+    /// A player failing to make a valid choice is not an error of the step,
+    /// they are ejected instead. An `Err` means the game itself can't go on.
+    fn step(&mut self) -> Result<(), Error>;
 }
 
 mod deal;
@@ -1,27 +1,41 @@
 use std::ops::{Deref, DerefMut};
-use interact::*;
+use error::Error;
 use game::*;
 
-pub struct Reveal<'a, C: 'a + Chooser>(pub &'a mut Game<C>);
+pub struct Reveal<'a>(pub &'a mut GameState);
+
+/// Transition functions.
+impl GameState {
+    /// Turns the cards given to the watering hole into food, and triggers
+    /// the traits which act when food is revealed, recording what was
+    /// revealed. Returns what the triggered traits changed.
+    pub fn reveal(&mut self) -> context::Outcome {
+        let cards = self.board().cards().map(|c| c.to_vec()).unwrap_or(vec![]);
+        let outcome = context::Board::new(self).reveal();
+        let food = self.board().food_count();
+        self.record(Event::Reveal { cards: cards, food: food });
+        outcome
+    }
+}
 
-impl<'a, C: Chooser> step::Step<C> for Reveal<'a, C> {
-    fn step(&mut self) -> Result<(), ()> {
+impl<'a> step::Step for Reveal<'a> {
+    fn step(&mut self) -> Result<(), Error> {
         trace!("@Reveal.step");
-        context::Board::new(self).reveal();
+        self.0.reveal();
         Ok(())
     }
 }
 
-impl<'a, C: Chooser> Deref for Reveal<'a, C> {
-    type Target = Game<C>;
+impl<'a> Deref for Reveal<'a> {
+    type Target = GameState;
 
-    fn deref(&self) -> &Game<C> {
+    fn deref(&self) -> &GameState {
         &self.0
     }
 }
 
-impl<'a, C: Chooser> DerefMut for Reveal<'a, C> {
-    fn deref_mut(&mut self) -> &mut Game<C> {
+impl<'a> DerefMut for Reveal<'a> {
+    fn deref_mut(&mut self) -> &mut GameState {
         &mut self.0
     }
 }
@@ -55,12 +69,12 @@ mod tests {
         ];
         game.board_mut().set_cards(food_cards);
 
-        assert_eq!(0, game.board().food().len());
+        assert_eq!(0, game.board().food_count());
         assert_eq!(3, game.board().cards().unwrap().len());
 
         step::Reveal(&mut game).step().unwrap();
 
-        assert_eq!(9, game.board().food().len());
+        assert_eq!(9, game.board().food_count());
     }
 
     #[test]
@@ -74,11 +88,11 @@ mod tests {
         game.board_mut().push_food(FoodToken);
         game.board_mut().set_cards(food_cards);
 
-        assert_eq!(1, game.board().food().len());
+        assert_eq!(1, game.board().food_count());
 
         step::Reveal(&mut game).step().unwrap();
 
-        assert_eq!(0, game.board().food().len());
+        assert_eq!(0, game.board().food_count());
     }
 
     #[test]
@@ -117,17 +131,17 @@ mod tests {
         let cards = game.deals(3);
         game.board_mut().set_cards(cards);
 
-        assert_eq!(0, game.players()[0].domain()[0].food().len());
-        assert_eq!(0, game.players()[1].domain()[0].food().len());
-        assert_eq!(0, game.players()[1].domain()[1].food().len());
-        assert_eq!(0, game.players()[2].domain()[0].food().len());
+        assert_eq!(0, game.players()[0].domain()[0].food_count());
+        assert_eq!(0, game.players()[1].domain()[0].food_count());
+        assert_eq!(0, game.players()[1].domain()[1].food_count());
+        assert_eq!(0, game.players()[2].domain()[0].food_count());
 
         step::Reveal(&mut game).step().unwrap();
 
-        assert_eq!(0, game.players()[0].domain()[0].food().len());
-        assert_eq!(1, game.players()[1].domain()[0].food().len());
-        assert_eq!(1, game.players()[1].domain()[1].food().len());
-        assert_eq!(0, game.players()[2].domain()[0].food().len());
+        assert_eq!(0, game.players()[0].domain()[0].food_count());
+        assert_eq!(1, game.players()[1].domain()[0].food_count());
+        assert_eq!(1, game.players()[1].domain()[1].food_count());
+        assert_eq!(0, game.players()[2].domain()[0].food_count());
     }
 
     #[test]
@@ -144,15 +158,15 @@ mod tests {
         let cards = game.deals(3);
         game.board_mut().set_cards(cards);
 
-        assert_eq!(0, game.players()[0].domain()[0].food().len());
-        assert_eq!(0, game.players()[1].domain()[0].food().len());
-        assert_eq!(0, game.players()[2].domain()[0].food().len());
+        assert_eq!(0, game.players()[0].domain()[0].food_count());
+        assert_eq!(0, game.players()[1].domain()[0].food_count());
+        assert_eq!(0, game.players()[2].domain()[0].food_count());
 
         step::Reveal(&mut game).step().unwrap();
 
-        assert_eq!(0, game.players()[0].domain()[0].food().len());
-        assert_eq!(1, game.players()[1].domain()[0].food().len());
-        assert_eq!(1, game.players()[1].domain()[0].fat().len());
-        assert_eq!(0, game.players()[2].domain()[0].food().len());
+        assert_eq!(0, game.players()[0].domain()[0].food_count());
+        assert_eq!(1, game.players()[1].domain()[0].food_count());
+        assert_eq!(1, game.players()[1].domain()[0].fat_count());
+        assert_eq!(0, game.players()[2].domain()[0].food_count());
     }
 }
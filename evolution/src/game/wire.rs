@@ -1,13 +1,12 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 use evolution_wire as wire;
 use game::*;
 use object::*;
-use silly::*;
 
-// TODO: `Game::new` should do validation.
+// TODO: `GameState::from_parts` should do validation.
 
-impl wire::FromWire<wire::Configuration> for Game<Silly> {
-    fn from_wire(wire: wire::Configuration) -> Result<Self, ()> {
+impl wire::FromWire<wire::Configuration> for GameState {
+    fn from_wire(wire: wire::Configuration) -> Result<Self, wire::Error> {
         let players = try!(Vec::<Player>::from_wire(wire.players));
         let board = try!(Board::from_wire(wire.watering_hole));
         let deck = try!(Vec::<Card>::from_wire(wire.deck));
@@ -15,7 +14,7 @@ impl wire::FromWire<wire::Configuration> for Game<Silly> {
         if players.len() < MIN_PLAYERS ||
            players.len() > MAX_PLAYERS
         {
-            return Err(())
+            return Err(wire::Error::invalid(format!("can't play with {} players", players.len())))
         }
 
         let mut validation_map : HashMap<Trait, usize> = HashMap::new();
@@ -37,28 +36,20 @@ impl wire::FromWire<wire::Configuration> for Game<Silly> {
                 _ => NUM_VEGITARIAN_CARDS,
             };
             if count > maximum_count {
-                return Err(())
+                return Err(wire::Error::invalid(format!("too many {:?} cards", trait_type)))
             }
         }
 
-        Ok(Game {
-            players: players,
-            // HACK: <refactor> It's interesting that we don't actually need
-            // to fill in the `choosers`.
-            choosers: HashMap::new(),
-            skip_set: HashSet::new(),
-            current_player: Some(0),
-            board: board,
-            deck: deck,
-        })
+        // Configurations are played by the course rules.
+        Ok(GameState::from_parts(players, board, deck, 0, RulesConfig::default()))
     }
 }
 
-impl wire::ToWire<wire::Configuration> for Game<Silly> {
+impl wire::ToWire<wire::Configuration> for GameState {
     fn to_wire(&self) -> wire::Configuration {
         wire::Configuration {
             players: self.players().to_wire(),
-            watering_hole: (self.board().food().len() as u64).to_wire(),
+            watering_hole: self.board().food_count().to_wire(),
             deck: self.deck.as_slice().to_wire(),
         }
     }
@@ -98,13 +89,13 @@ mod tests {
             deck: vec![],
         };
 
-        let game = Game::from_wire(configuration).unwrap();
+        let game = GameState::from_wire(configuration).unwrap();
 
         assert_eq!(3, game.players().len());
         assert_eq!(1, game.players()[0].id());
         assert_eq!(2, game.players()[1].id());
         assert_eq!(3, game.players()[2].id());
-        assert_eq!(100, game.board().food().len());
+        assert_eq!(100, game.board().food_count());
         assert_eq!(0, game.deck().len());
     }
 
@@ -117,10 +108,10 @@ mod tests {
         assert_eq!(1, game.players()[0].id());
         assert_eq!(2, game.players()[1].id());
         assert_eq!(3, game.players()[2].id());
-        assert_eq!(2, game.board().food().len());
+        assert_eq!(2, game.board().food_count());
         assert_eq!(122, game.deck().len());
 
-        let configuration = game.to_wire();
+        let configuration: wire::Configuration = game.to_wire();
 
         assert_eq!(3, configuration.players.len());
         assert_eq!(1, *configuration.players[0].id);
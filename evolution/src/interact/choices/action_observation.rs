@@ -1,16 +1,376 @@
+use error::Error;
+use game::RulesConfig;
+use object::*;
 use interact::*;
 
 impl Choices<ActionChoice> for ActionObservation {
     fn choices(&self) -> Vec<ActionChoice> {
-        vec![]
+        let mut choices = vec![];
+        self.visit_action_choices(&mut |c| {
+            choices.push(c);
+            true
+        });
+        choices
+    }
+}
+
+impl ActionObservation {
+    /// Visits every legal `ActionChoice` for the current player under the
+    /// observed rules, until the visitor returns false. Returns false if the
+    /// visitor stopped the search.
+    ///
+    /// Each combination of cards is visited once, with the trades of the
+    /// choice listed in the order of their cards, and each trait replaced at
+    /// most once. The number of choices grows exponentially with the size of
+    /// the hand, so a search of a full hand should stop early.
+    pub fn visit_action_choices(&self, visitor: &mut FnMut(ActionChoice) -> bool) -> bool {
+        let hand = self.current_player.hand().len();
+        for food_card in 0..hand {
+            let rest = (0..hand).filter(|&i| i != food_card).collect::<Vec<_>>();
+            let mut search = Search {
+                observation: self,
+                rules: &self.rules,
+                food_card: food_card,
+                payments: vec![],
+                cards: vec![],
+                roles: vec![],
+            };
+            if !search.pay(&rest, visitor) {
+                return false
+            }
+        }
+        true
+    }
+
+    /// Returns true if the choice is valid for this observation, and can be
+    /// played under the observed rules without breaking any of them.
+    pub fn is_legal(&self, choice: &ActionChoice) -> bool {
+        choice.validate(self).is_ok() && self.simulate(choice, &self.rules).is_ok()
+    }
+
+    /// Plays the valid choice on a copy of the current player's domain, the
+    /// same way the game trades cards.
+    fn simulate(&self, choice: &ActionChoice, rules: &RulesConfig) -> Result<(), Error> {
+        let hand = self.current_player.hand();
+        let mut domain = self.current_player.domain().clone();
+        for board in choice.boards.iter() {
+            let species = domain.add(Placement::Right);
+            for &card_index in board.trait_card_indeces.iter() {
+                try!(species.evolve_up_to(hand[card_index].trait_type(), rules.max_traits));
+            }
+        }
+        for growth in choice.population_growths.iter() {
+            try!(domain[growth.species_index].breed_up_to(rules.max_population));
+        }
+        for growth in choice.body_growths.iter() {
+            try!(domain[growth.species_index].grow_up_to(rules.max_body_size));
+        }
+        for trade in choice.traits.iter() {
+            let new_trait = hand[trade.replacement_index].trait_type();
+            try!(domain[trade.species_index].exchange_trait(trade.trait_index, new_trait));
+        }
+        Ok(())
+    }
+}
+
+/// What a card, other than the food card or a board's payment, is used for.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Role {
+    Unused,
+    BoardTrait(usize),
+    Population(usize),
+    Body(usize),
+    Trade(usize, usize),
+}
+
+/// A depth first search of the choices with a given food card.
+struct Search<'a> {
+    observation: &'a ActionObservation,
+    rules: &'a RulesConfig,
+    food_card: usize,
+    payments: Vec<usize>,
+    cards: Vec<usize>,
+    roles: Vec<Role>,
+}
+
+impl<'a> Search<'a> {
+    /// Choose which of the rest of the cards pay for new boards, then give
+    /// the others roles.
+    fn pay(&mut self, rest: &[usize], visitor: &mut FnMut(ActionChoice) -> bool) -> bool {
+        match rest.split_first() {
+            Some((&card, rest)) => {
+                self.cards.push(card);
+                let more = self.pay(rest, visitor);
+                self.cards.pop();
+                if !more {
+                    return false
+                }
+
+                self.payments.push(card);
+                let more = self.pay(rest, visitor);
+                self.payments.pop();
+                more
+            },
+            None => self.assign(0, visitor),
+        }
+    }
+
+    /// Give each card from the `i`th on a role, visiting each legal choice.
+    fn assign(&mut self, i: usize, visitor: &mut FnMut(ActionChoice) -> bool) -> bool {
+        if i == self.cards.len() {
+            let choice = self.choice();
+            if self.observation.is_legal(&choice) {
+                return visitor(choice)
+            }
+            return true
+        }
+
+        for role in self.roles() {
+            self.roles.push(role);
+            let more = self.assign(i + 1, visitor);
+            self.roles.pop();
+            if !more {
+                return false
+            }
+        }
+        true
+    }
+
+    /// The roles the next card could have, given the roles of the cards
+    /// before it. Roles which are sure to break the rules are left out.
+    fn roles(&self) -> Vec<Role> {
+        let domain = self.observation.current_player.domain();
+        let boards = self.payments.len();
+        let mut roles = vec![Role::Unused];
+
+        for board in 0..boards {
+            let count = self.count(|r| r == Role::BoardTrait(board));
+            if count < self.rules.max_traits {
+                roles.push(Role::BoardTrait(board));
+            }
+        }
+
+        for species_index in 0..(domain.len() + boards) {
+            let (population, body_size, traits) = match domain.get(species_index) {
+                Some(species) => (species.population(), species.body_size(), species.traits().len()),
+                None => (1, 0, self.rules.max_traits),
+            };
+            let growths = self.count(|r| r == Role::Population(species_index)) as u64;
+            if population + growths < self.rules.max_population {
+                roles.push(Role::Population(species_index));
+            }
+            let growths = self.count(|r| r == Role::Body(species_index)) as u64;
+            if body_size + growths < self.rules.max_body_size {
+                roles.push(Role::Body(species_index));
+            }
+            for trait_index in 0..traits {
+                if self.count(|r| r == Role::Trade(species_index, trait_index)) == 0 {
+                    roles.push(Role::Trade(species_index, trait_index));
+                }
+            }
+        }
+
+        roles
+    }
+
+    /// The number of cards given a role so far which match.
+    fn count<F: Fn(Role) -> bool>(&self, matches: F) -> usize {
+        self.roles.iter().filter(|r| matches(**r)).count()
+    }
+
+    /// The choice of the current roles.
+    fn choice(&self) -> ActionChoice {
+        let mut boards = self.payments.iter().map(|&card_index| {
+            BoardTrade {
+                card_index: card_index,
+                trait_card_indeces: vec![],
+            }
+        }).collect::<Vec<_>>();
+        let mut population_growths = vec![];
+        let mut body_growths = vec![];
+        let mut traits = vec![];
+
+        for (&card_index, role) in self.cards.iter().zip(self.roles.iter()) {
+            match *role {
+                Role::Unused => {},
+                Role::BoardTrait(board) => {
+                    boards[board].trait_card_indeces.push(card_index);
+                },
+                Role::Population(species_index) => {
+                    population_growths.push(Growth {
+                        species_index: species_index,
+                        card_index: card_index,
+                    });
+                },
+                Role::Body(species_index) => {
+                    body_growths.push(Growth {
+                        species_index: species_index,
+                        card_index: card_index,
+                    });
+                },
+                Role::Trade(species_index, trait_index) => {
+                    traits.push(TraitTrade {
+                        species_index: species_index,
+                        trait_index: trait_index,
+                        replacement_index: card_index,
+                    });
+                },
+            }
+        }
+
+        ActionChoice {
+            food_card: self.food_card,
+            population_growths: population_growths,
+            body_growths: body_growths,
+            boards: boards,
+            traits: traits,
+        }
     }
 }
 
-// TODO: Beef these tests way up. They are now used for validation.
 #[cfg(test)]
 mod tests {
-    // use game::*;
-    // use interact::*;
-    // use object::*;
+    use game::*;
+    use interact::*;
+    use object::*;
+
+    fn observation(cards: Vec<Card>) -> ActionObservation {
+        let game = game_with_players(3, &|player| {
+            if player.id() == 1 {
+                player.domain_mut().add(Placement::Right);
+                player.push_cards(cards.clone());
+            }
+        });
+        game.observe()
+    }
+
+    #[test]
+    fn only_food_card() {
+        let observation = observation(vec![Card::mock(2, Trait::Ambush)]);
+
+        assert_eq!(vec![ActionChoice {
+            food_card: 0,
+            population_growths: vec![],
+            body_growths: vec![],
+            boards: vec![],
+            traits: vec![],
+        }], observation.choices());
+    }
+
+    #[test]
+    fn two_cards() {
+        let observation = observation(vec![
+            Card::mock(2, Trait::Ambush),
+            Card::mock(1, Trait::Burrowing),
+        ]);
+        let choices = observation.choices();
+
+        // Either card is food, and the other is unused, a board, or grows
+        // the species' population or body.
+        assert_eq!(8, choices.len());
+        assert!(choices.contains(&ActionChoice {
+            food_card: 1,
+            population_growths: vec![],
+            body_growths: vec![],
+            boards: vec![BoardTrade {
+                card_index: 0,
+                trait_card_indeces: vec![],
+            }],
+            traits: vec![],
+        }));
+    }
+
+    #[test]
+    fn every_choice_is_legal() {
+        let observation = observation(vec![
+            Card::mock(2, Trait::Ambush),
+            Card::mock(1, Trait::Ambush),
+            Card::mock(0, Trait::Carnivore),
+            Card::mock(3, Trait::Fertile),
+        ]);
+        let choices = observation.choices();
+
+        assert!(!choices.is_empty());
+        for choice in choices.iter() {
+            assert!(observation.is_legal(choice));
+        }
+        // Two ambush cards can't both be traits of a new board.
+        assert!(!choices.iter().any(|c| {
+            c.boards.iter().any(|b| b.trait_card_indeces == vec![0, 1])
+        }));
+    }
+
+    #[test]
+    fn stops_when_asked() {
+        let observation = observation(vec![
+            Card::mock(2, Trait::Ambush),
+            Card::mock(1, Trait::Burrowing),
+            Card::mock(0, Trait::Carnivore),
+        ]);
+        let mut visited = 0;
+
+        assert!(!observation.visit_action_choices(&mut |_| {
+            visited += 1;
+            visited < 3
+        }));
+        assert_eq!(3, visited);
+    }
+
+    #[test]
+    fn full_population_cant_grow() {
+        let game = game_with_players(3, &|player| {
+            if player.id() == 1 {
+                player.domain_mut().add(Placement::Right);
+                for _ in 1..MAX_POPULATION {
+                    player.domain_mut()[0].breed().unwrap();
+                }
+                player.push_cards(vec![
+                    Card::mock(2, Trait::Ambush),
+                    Card::mock(1, Trait::Burrowing),
+                ]);
+            }
+        });
+        let observation: ActionObservation = game.observe();
+        let choice = ActionChoice {
+            food_card: 0,
+            population_growths: vec![Growth {
+                species_index: 0,
+                card_index: 1,
+            }],
+            body_growths: vec![],
+            boards: vec![],
+            traits: vec![],
+        };
+
+        assert!(!observation.is_legal(&choice));
+        assert!(!observation.choices().contains(&choice));
+    }
+
+    #[test]
+    fn choices_follow_the_observed_rules() {
+        let mut game = GameState::with_rules(3, 1, RulesConfig::official()).unwrap();
+        game.players_mut()[0].domain_mut().add(Placement::Right);
+        for _ in 1..rules::OFFICIAL_MAX_POPULATION {
+            game.players_mut()[0].domain_mut()[0].breed().unwrap();
+        }
+        game.players_mut()[0].push_cards(vec![
+            Card::mock(2, Trait::Ambush),
+            Card::mock(1, Trait::Burrowing),
+        ]);
+        let observation: ActionObservation = game.observe();
+        let choice = ActionChoice {
+            food_card: 0,
+            population_growths: vec![Growth {
+                species_index: 0,
+                card_index: 1,
+            }],
+            body_growths: vec![],
+            boards: vec![],
+            traits: vec![],
+        };
 
+        assert!(rules::OFFICIAL_MAX_POPULATION < MAX_POPULATION);
+        assert!(!observation.is_legal(&choice));
+        assert!(!observation.choices().contains(&choice));
+    }
 }
@@ -120,7 +120,7 @@ mod tests {
         });
         game.board_mut().push_foods(vec![FoodToken, FoodToken]);
 
-        assert_eq!(2, game.board().food().len());
+        assert_eq!(2, game.board().food_count());
         assert!(game.players()[0].domain()[0].can_eat());
         assert!(game.players()[0].domain()[1].can_eat());
         assert_eq!(Some(3), game.players()[0].domain()[0].can_store());
@@ -139,7 +139,7 @@ mod tests {
             }
         });
 
-        assert_eq!(0, game.board().food().len());
+        assert_eq!(0, game.board().food_count());
 
         let observation: FeedObservation = game.observe();
         let choices = observation.visit_feed_choices(&mut |c| Some(c));
@@ -152,7 +152,7 @@ mod tests {
         let mut game = game_with_players(3, &|_| {});
         game.board_mut().push_foods(vec![FoodToken, FoodToken]);
 
-        assert_eq!(2, game.board().food().len());
+        assert_eq!(2, game.board().food_count());
 
         let observation: FeedObservation = game.observe();
         let choices = observation.visit_feed_choices(&mut |c| Some(c));
@@ -172,7 +172,7 @@ mod tests {
         });
         game.board_mut().push_foods(vec![FoodToken, FoodToken]);
 
-        assert_eq!(2, game.board().food().len());
+        assert_eq!(2, game.board().food_count());
         assert_eq!(Some(2), game.players()[0].domain()[0].can_store());
 
         let observation: FeedObservation = game.observe();
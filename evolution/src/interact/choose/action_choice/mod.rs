@@ -1,5 +1,6 @@
 use interact::*;
 use std::collections::HashSet;
+use error::ChoiceError;
 
 /// A choice how to use cards for a round.
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -14,36 +15,35 @@ pub struct ActionChoice {
 impl Choice for ActionChoice {}
 
 impl ActionChoice {
-    pub fn validate(&self, observation: &ActionObservation) -> Result<(), ()> {
-        if self.is_valid(observation) {
-            Ok(())
-        } else {
-            Err(())
-        }
-    }
-
-    fn is_valid(&self, observation: &ActionObservation) -> bool {
+    /// Check that every index in this choice is valid for the observation,
+    /// returning the first bad index found as an `Err`.
+    pub fn validate(&self, observation: &ActionObservation) -> Result<(), ChoiceError> {
         let mut domain = (**observation.current_player.domain())
             .into_iter()
             .map(|species| species.traits().len())
             .collect::<Vec<_>>();
         let mut hand: HashSet<usize> = (0..observation.current_player.hand().len()).collect();
 
-        try_bool!(hand.remove(&self.food_card));
+        try!(use_card(&mut hand, self.food_card));
 
         for bt in self.boards.iter() {
-            try_bool!(bt.validate(&mut domain, &mut hand));
+            try!(bt.validate(&mut domain, &mut hand));
         }
 
         for g in self.population_growths.iter().chain(self.body_growths.iter()) {
-            try_bool!(g.validate(&domain, &mut hand));
+            try!(g.validate(&domain, &mut hand));
         }
 
         for t in self.traits.iter() {
-            try_bool!(t.validate(&domain, &mut hand));
+            try!(t.validate(&domain, &mut hand));
         }
 
-        true
+        Ok(())
+    }
+
+    #[cfg(test)]
+    fn is_valid(&self, observation: &ActionObservation) -> bool {
+        self.validate(observation).is_ok()
     }
 }
 
@@ -55,9 +55,9 @@ pub struct Growth {
 }
 
 impl Growth {
-    fn validate(&self, domain: &[usize], hand: &mut HashSet<usize>) -> bool {
-        try_bool!(hand.remove(&self.card_index), self.species_index < domain.len());
-        true
+    fn validate(&self, domain: &[usize], hand: &mut HashSet<usize>) -> Result<(), ChoiceError> {
+        try!(use_card(hand, self.card_index));
+        use_species(domain, self.species_index)
     }
 }
 
@@ -69,13 +69,13 @@ pub struct BoardTrade {
 }
 
 impl BoardTrade {
-    fn validate(&self, domain: &mut Vec<usize>, hand: &mut HashSet<usize>) -> bool {
-        try_bool!(hand.remove(&self.card_index));
+    fn validate(&self, domain: &mut Vec<usize>, hand: &mut HashSet<usize>) -> Result<(), ChoiceError> {
+        try!(use_card(hand, self.card_index));
         for i in self.trait_card_indeces.iter() {
-            try_bool!(hand.remove(&i));
+            try!(use_card(hand, *i));
         }
         domain.push(self.trait_card_indeces.len());
-        true
+        Ok(())
     }
 }
 
@@ -88,13 +88,33 @@ pub struct TraitTrade {
 }
 
 impl TraitTrade {
-    fn validate(&self, domain: &[usize], hand: &mut HashSet<usize>) -> bool {
-        try_bool!(
-            hand.remove(&self.replacement_index),
-            self.species_index < domain.len(),
-            self.trait_index < domain[self.species_index]
-        );
-        true
+    fn validate(&self, domain: &[usize], hand: &mut HashSet<usize>) -> Result<(), ChoiceError> {
+        try!(use_card(hand, self.replacement_index));
+        try!(use_species(domain, self.species_index));
+        if self.trait_index < domain[self.species_index] {
+            Ok(())
+        } else {
+            Err(ChoiceError::Trait(self.species_index, self.trait_index))
+        }
+    }
+}
+
+/// Remove the card index from the unused cards in hand, or error if it's
+/// not there.
+fn use_card(hand: &mut HashSet<usize>, card_index: usize) -> Result<(), ChoiceError> {
+    if hand.remove(&card_index) {
+        Ok(())
+    } else {
+        Err(ChoiceError::Card(card_index))
+    }
+}
+
+/// Error if the species index isn't in the domain.
+fn use_species(domain: &[usize], species_index: usize) -> Result<(), ChoiceError> {
+    if species_index < domain.len() {
+        Ok(())
+    } else {
+        Err(ChoiceError::Species(species_index))
     }
 }
 
@@ -102,7 +122,9 @@ impl TraitTrade {
 mod tests {
     use std::collections::HashSet;
     use std::iter::FromIterator;
+    use error::ChoiceError;
     use interact::*;
+    use game::*;
     use object::*;
 
     #[test]
@@ -113,7 +135,7 @@ mod tests {
         };
         let domain = (0..4).collect::<Vec<_>>();
         let mut hand = HashSet::from_iter(0..4);
-        assert!(growth.validate(&domain, &mut hand));
+        assert_eq!(Ok(()), growth.validate(&domain, &mut hand));
     }
 
     #[test]
@@ -124,7 +146,7 @@ mod tests {
         };
         let domain = (0..2).collect::<Vec<_>>();
         let mut hand = HashSet::from_iter(0..4);
-        assert!(!growth.validate(&domain, &mut hand));
+        assert_eq!(Err(ChoiceError::Species(5)), growth.validate(&domain, &mut hand));
     }
 
     #[test]
@@ -138,7 +160,7 @@ mod tests {
 
         assert_eq!(3, domain.len());
 
-        assert!(trade.validate(&mut domain, &mut hand));
+        assert_eq!(Ok(()), trade.validate(&mut domain, &mut hand));
 
         assert_eq!(4, domain.len());
     }
@@ -152,7 +174,7 @@ mod tests {
         let mut domain = (0..3).collect::<Vec<_>>();
         let mut hand = HashSet::from_iter(0..6);
 
-        assert!(!trade.validate(&mut domain, &mut hand));
+        assert_eq!(Err(ChoiceError::Card(14)), trade.validate(&mut domain, &mut hand));
     }
 
     #[test]
@@ -164,7 +186,7 @@ mod tests {
         };
         let domain = vec![0, 3, 0, 0];
         let mut hand = HashSet::from_iter(0..5);
-        assert!(trade.validate(&domain, &mut hand));
+        assert_eq!(Ok(()), trade.validate(&domain, &mut hand));
     }
 
     #[test]
@@ -176,7 +198,7 @@ mod tests {
         };
         let domain = (0..3).collect::<Vec<_>>();
         let mut hand = HashSet::from_iter(0..5);
-        assert!(!trade.validate(&domain, &mut hand));
+        assert_eq!(Err(ChoiceError::Card(14)), trade.validate(&domain, &mut hand));
     }
 
     #[test]
@@ -195,12 +217,13 @@ mod tests {
             current_player: Player::new(2),
             before: vec![],
             after: vec![],
+            rules: RulesConfig::default(),
         };
         let cards = Card::deck().into_iter().take(5).collect();
         action_observation.current_player.push_cards(cards);
         assert_eq!(5, action_observation.current_player.hand().len());
 
-        assert!(action_choice.validate(&action_observation).is_err());
+        assert_eq!(Err(ChoiceError::Card(0)), action_choice.validate(&action_observation));
     }
 
     #[test]
@@ -219,6 +242,7 @@ mod tests {
             current_player: Player::new(2),
             before: vec![],
             after: vec![],
+            rules: RulesConfig::default(),
         };
         let cards = Card::deck().into_iter().take(5).collect();
         action_observation.current_player.push_cards(cards);
@@ -243,6 +267,7 @@ mod tests {
             current_player: Player::new(2),
             before: vec![],
             after: vec![],
+            rules: RulesConfig::default(),
         };
         let cards = Card::deck().into_iter().take(5).collect();
         action_observation.current_player.push_cards(cards);
@@ -273,6 +298,7 @@ mod tests {
             current_player: Player::new(2),
             before: vec![],
             after: vec![],
+            rules: RulesConfig::default(),
         };
         let cards = Card::deck().into_iter().take(5).collect();
         action_observation.current_player.push_cards(cards);
@@ -6,7 +6,7 @@ use interact::*;
 // pass itself to assist validation.
 
 impl wire::FromWire<wire::remote::Action4> for ActionChoice {
-    fn from_wire(wire: wire::remote::Action4) -> Result<Self, ()> {
+    fn from_wire(wire: wire::remote::Action4) -> Result<Self, wire::Error> {
         let action_choice = ActionChoice {
             food_card: *wire.0 as usize,
             population_growths: try!(Vec::from_wire(wire.1)),
@@ -16,7 +16,7 @@ impl wire::FromWire<wire::remote::Action4> for ActionChoice {
         };
 
         if has_duplicate_cards(&action_choice) {
-            Err(())
+            Err(wire::Error::invalid("action choice uses a card more than once"))
         } else {
             Ok(action_choice)
         }
@@ -36,7 +36,7 @@ impl wire::ToWire<wire::remote::Action4> for ActionChoice {
 }
 
 impl wire::FromWire<wire::remote::GP> for Growth {
-    fn from_wire(wire: wire::remote::GP) -> Result<Self, ()> {
+    fn from_wire(wire: wire::remote::GP) -> Result<Self, wire::Error> {
         let species_index = *wire.board_index as usize;
         let card_index = *wire.card_index as usize;
 
@@ -57,7 +57,7 @@ impl wire::ToWire<wire::remote::GP> for Growth {
 }
 
 impl wire::FromWire<wire::remote::GB> for Growth {
-    fn from_wire(wire: wire::remote::GB) -> Result<Self, ()> {
+    fn from_wire(wire: wire::remote::GB) -> Result<Self, wire::Error> {
         let species_index = *wire.board_index as usize;
         let card_index = *wire.card_index as usize;
 
@@ -80,7 +80,7 @@ impl wire::ToWire<wire::remote::GB> for Growth {
 // NOTE: These impls are based on old data, and are being left in for old tests.
 
 impl wire::FromWire<wire::Action4> for ActionChoice {
-    fn from_wire(wire: wire::Action4) -> Result<Self, ()> {
+    fn from_wire(wire: wire::Action4) -> Result<Self, wire::Error> {
         let food_card = *wire.0 as usize;
         let population_growths = try!(Vec::from_wire(wire.1));
         let body_growths = try!(Vec::from_wire(wire.2));
@@ -95,7 +95,7 @@ impl wire::FromWire<wire::Action4> for ActionChoice {
         };
 
         if has_duplicate_cards(&action_choice) {
-            Err(())
+            Err(wire::Error::invalid("action choice uses a card more than once"))
         } else {
             Ok(action_choice)
         }
@@ -115,7 +115,7 @@ impl wire::ToWire<wire::Action4> for ActionChoice {
 }
 
 impl wire::FromWire<wire::GP> for Growth {
-    fn from_wire(wire: wire::GP) -> Result<Self, ()> {
+    fn from_wire(wire: wire::GP) -> Result<Self, wire::Error> {
         let species_index = *wire.board_index as usize;
         let card_index = *wire.card_index as usize;
 
@@ -136,7 +136,7 @@ impl wire::ToWire<wire::GP> for Growth {
 }
 
 impl wire::FromWire<wire::GB> for Growth {
-    fn from_wire(wire: wire::GB) -> Result<Self, ()> {
+    fn from_wire(wire: wire::GB) -> Result<Self, wire::Error> {
         let species_index = *wire.board_index as usize;
         let card_index = *wire.card_index as usize;
 
@@ -157,7 +157,7 @@ impl wire::ToWire<wire::GB> for Growth {
 }
 
 impl wire::FromWire<wire::BT> for BoardTrade {
-    fn from_wire(wire: wire::BT) -> Result<Self, ()> {
+    fn from_wire(wire: wire::BT) -> Result<Self, wire::Error> {
         // TODO: Could be done with one vector.
         let indeces = wire.iter().map(|n| **n as usize).collect::<Vec<_>>();
 
@@ -177,7 +177,7 @@ impl wire::ToWire<wire::BT> for BoardTrade {
 }
 
 impl wire::FromWire<wire::RT> for TraitTrade {
-    fn from_wire(wire: wire::RT) -> Result<Self, ()> {
+    fn from_wire(wire: wire::RT) -> Result<Self, wire::Error> {
         let species_index = *wire.0 as usize;
         let trait_index = *wire.1 as usize;
         let replacement_index = *wire.2 as usize;
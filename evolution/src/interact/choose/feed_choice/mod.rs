@@ -39,7 +39,7 @@ pub enum FeedChoice {
 
 impl FeedChoice {
     // TODO: Docs... for real, this method is complex.
-    pub fn internalize<C: Chooser>(&mut self, game: &Game<C>) {
+    pub fn internalize(&mut self, game: &GameState) {
         match *self {
             FeedChoice::Attack(_, ref mut target_idx, _) => {
                 let idx = game.current_player_idx();
@@ -21,7 +21,7 @@ impl wire::ToWire<wire::FeedChoice> for FeedChoice {
 }
 
 impl wire::FromWire<wire::FeedChoice> for FeedChoice {
-    fn from_wire(wire: wire::FeedChoice) -> Result<Self, ()> {
+    fn from_wire(wire: wire::FeedChoice) -> Result<Self, wire::Error> {
         let feed_choice = match wire {
             wire::FeedChoice::Abstain => FeedChoice::Abstain,
             wire::FeedChoice::Feed(species) => FeedChoice::Feed(*species as usize),
@@ -1,4 +1,5 @@
 use std::fmt::Debug;
+use error::Error;
 use super::Observation;
 
 /// A single choice for an interaction.
@@ -10,13 +11,16 @@ pub trait Choice: Clone + PartialEq + Debug {
 }
 
 /// Asking for a `Choice`.
+///
+/// An `Err` means the chooser failed to give a choice, and says why, while
+/// `Ok(None)` means there was no choice to make.
 pub trait Choose<O: Observation, C: Choice>: Debug {
-    fn choose(&mut self, &O) -> Result<Option<C>, ()>;
+    fn choose(&mut self, &O) -> Result<Option<C>, Error>;
 }
 
 // Choice's themselves implement `Choose`.
 impl<O: Observation, C: Choice> Choose<O, C> for C {
-    fn choose(&mut self, _: &O) -> Result<Option<C>, ()> {
+    fn choose(&mut self, _: &O) -> Result<Option<C>, Error> {
         Ok(Some(self.clone()))
     }
 }
@@ -1,6 +1,41 @@
+use std::time::Duration;
+use error::Error;
+
 pub trait Chooser: Choose<ActionObservation, ActionChoice> + Choose<FeedObservation, FeedChoice> {
     fn start(&mut self, observation: &DealObservation);
     fn info(&self) -> Option<&str>;
+
+    /// Tells this chooser how long it has to make its next choice, or
+    /// `None` for no limit. Choosers which wait on IO should give up after
+    /// that long, others can ignore it.
+    fn time_limit(&mut self, _limit: Option<Duration>) {}
+}
+
+/// Choosers of any kind, so one game can seat choosers of different kinds.
+impl Chooser for Box<Chooser + Send> {
+    fn start(&mut self, observation: &DealObservation) {
+        (**self).start(observation)
+    }
+
+    fn info(&self) -> Option<&str> {
+        (**self).info()
+    }
+
+    fn time_limit(&mut self, limit: Option<Duration>) {
+        (**self).time_limit(limit)
+    }
+}
+
+impl Choose<ActionObservation, ActionChoice> for Box<Chooser + Send> {
+    fn choose(&mut self, observation: &ActionObservation) -> Result<Option<ActionChoice>, Error> {
+        (**self).choose(observation)
+    }
+}
+
+impl Choose<FeedObservation, FeedChoice> for Box<Chooser + Send> {
+    fn choose(&mut self, observation: &FeedObservation) -> Result<Option<FeedChoice>, Error> {
+        (**self).choose(observation)
+    }
 }
 
 // Re-export the public parts of this module.
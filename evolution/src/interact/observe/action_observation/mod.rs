@@ -8,12 +8,14 @@ pub struct ActionObservation {
     pub current_player: Player,
     pub before: Vec<Domain>,
     pub after: Vec<Domain>,
+    /// The rules the game is played by.
+    pub rules: RulesConfig,
 }
 
 impl Observation for ActionObservation {}
 
 // TODO: impl Observe
-impl<C: Chooser> Observe<ActionObservation> for Game<C> {
+impl Observe<ActionObservation> for GameState {
     fn observe(&self) -> ActionObservation {
         let idx = self.current_player_idx();
         let before = self.players()[..idx].iter().map(|p| p.domain().clone()).collect();
@@ -22,6 +24,7 @@ impl<C: Chooser> Observe<ActionObservation> for Game<C> {
             current_player: self.current_player().clone(),
             before: before,
             after: after,
+            rules: self.rules().clone(),
         }
     }
 }
@@ -1,4 +1,5 @@
 use evolution_wire as wire;
+use game::*;
 use interact::*;
 use object::*;
 
@@ -11,12 +12,15 @@ impl wire::ToWire<(wire::remote::LOB, wire::remote::LOB)> for ActionObservation
 }
 
 impl wire::FromWire<(wire::remote::Start, (wire::remote::LOB, wire::remote::LOB))> for ActionObservation {
-    fn from_wire(wire: (wire::remote::Start, (wire::remote::LOB, wire::remote::LOB))) -> Result<Self, ()> {
+    fn from_wire(wire: (wire::remote::Start, (wire::remote::LOB, wire::remote::LOB))) -> Result<Self, wire::Error> {
         let (start, (before, after)) = wire;
         Ok(ActionObservation {
             current_player: try!(Player::from_wire(start)),
             before: try!(Vec::from_wire(before)),
             after: try!(Vec::from_wire(after)),
+            // The remote protocol doesn't send the rules, remote games are
+            // played by the course rules.
+            rules: RulesConfig::default(),
         })
     }
 }
@@ -34,7 +38,7 @@ impl wire::ToWire<wire::Choice> for ActionObservation {
 }
 
 impl wire::FromWire<wire::Choice> for ActionObservation {
-    fn from_wire(wire: wire::Choice) -> Result<Self, ()> {
+    fn from_wire(wire: wire::Choice) -> Result<Self, wire::Error> {
         let current_player = try!(Player::from_wire(wire.current_player));
 
         // TODO: Should be able to get a vec of domains from wire directly.
@@ -56,6 +60,7 @@ impl wire::FromWire<wire::Choice> for ActionObservation {
                 current_player: current_player,
                 before: before,
                 after: after,
+                rules: RulesConfig::default(),
             }
         )
     }
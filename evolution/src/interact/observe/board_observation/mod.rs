@@ -12,7 +12,7 @@ impl Observation for BoardObservation {}
 impl Observe<BoardObservation> for Board {
     fn observe(&self) -> BoardObservation {
         BoardObservation {
-            food: self.food().len() as u64,
+            food: self.food_count(),
         }
     }
 }
@@ -8,7 +8,7 @@ impl wire::ToWire<wire::Natural> for BoardObservation {
 }
 
 impl wire::FromWire<wire::Natural> for BoardObservation {
-    fn from_wire(wire: wire::Natural) -> Result<Self, ()> {
+    fn from_wire(wire: wire::Natural) -> Result<Self, wire::Error> {
         Ok(BoardObservation {
             food: *wire,
         })
@@ -22,7 +22,7 @@ impl wire::ToWire<wire::NaturalPlus> for BoardObservation {
 }
 
 impl wire::FromWire<wire::NaturalPlus> for BoardObservation {
-    fn from_wire(wire: wire::NaturalPlus) -> Result<Self, ()> {
+    fn from_wire(wire: wire::NaturalPlus) -> Result<Self, wire::Error> {
         Ok(BoardObservation {
             food: *wire,
         })
@@ -40,7 +40,7 @@ mod tests {
         let mut board = Board::default();
         board.push_foods(vec![FoodToken, FoodToken]);
 
-        assert_eq!(2, board.food().len());
+        assert_eq!(2, board.food_count());
 
         let wire: wire::Natural = board.observe().to_wire();
 
@@ -59,7 +59,7 @@ mod tests {
         let mut board = Board::default();
         board.push_foods(vec![FoodToken, FoodToken]);
 
-        assert_eq!(2, board.food().len());
+        assert_eq!(2, board.food_count());
 
         let wire: wire::NaturalPlus = board.observe().to_wire();
 
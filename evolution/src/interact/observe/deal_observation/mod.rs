@@ -41,7 +41,7 @@ mod tests {
             Card::mock(1, Trait::Carnivore),
         ]);
 
-        assert_eq!(3, board.food().len());
+        assert_eq!(3, board.food_count());
         assert_eq!(123, player.id());
         assert_eq!(1, player.domain().len());
         assert_eq!(4, player.hand().len());
@@ -14,7 +14,7 @@ impl wire::ToWire<wire::remote::Start> for DealObservation {
 }
 
 impl wire::FromWire<wire::remote::Start> for DealObservation {
-    fn from_wire(wire: wire::remote::Start) -> Result<Self, ()> {
+    fn from_wire(wire: wire::remote::Start) -> Result<Self, wire::Error> {
         let board = try!(BoardObservation::from_wire(wire.watering_hole));
         let player = try!(Player::from_wire(wire));
         Ok(DealObservation {
@@ -55,8 +55,8 @@ mod tests {
         ]);
         player.domain_mut().add(Placement::Right);
 
-        assert_eq!(3, board.food().len());
-        assert_eq!(0, player.bag().len());
+        assert_eq!(3, board.food_count());
+        assert_eq!(0, player.bag_count());
         assert_eq!(1, player.domain().len());
         assert_eq!(4, player.hand().len());
 
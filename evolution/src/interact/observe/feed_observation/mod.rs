@@ -8,11 +8,13 @@ pub struct FeedObservation {
     pub current_player: Player,
     pub opponents: Vec<PlayerObservation>,
     pub board: BoardObservation,
+    /// The rules the game is played by.
+    pub rules: RulesConfig,
 }
 
 impl Observation for FeedObservation {}
 
-impl<C: Chooser> Observe<FeedObservation> for Game<C> {
+impl Observe<FeedObservation> for GameState {
     fn observe(&self) -> FeedObservation {
         let idx = self.current_player_idx();
         let current_player = self.current_player().clone();
@@ -26,6 +28,7 @@ impl<C: Chooser> Observe<FeedObservation> for Game<C> {
             current_player: current_player,
             opponents: opponents,
             board: board,
+            rules: self.rules().clone(),
         }
     }
 }
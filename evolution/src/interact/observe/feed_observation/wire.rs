@@ -6,7 +6,7 @@ use object::*;
  impl wire::ToWire<wire::remote::State> for FeedObservation {
      fn to_wire(&self) -> wire::remote::State {
          wire::remote::State {
-             bag: self.current_player.bag().len().to_wire(),
+             bag: self.current_player.bag_count().to_wire(),
              domain: self.current_player.domain().to_wire(),
              hand: self.current_player.hand().to_wire(),
              watering_hole: self.board.to_wire(),
@@ -16,7 +16,7 @@ use object::*;
  }
 
  impl wire::FromWire<wire::remote::State> for FeedObservation {
-    fn from_wire(wire: wire::remote::State) -> Result<Self, ()> {
+    fn from_wire(wire: wire::remote::State) -> Result<Self, wire::Error> {
         let current_player = try!(Player::from_wire(wire::Player {
             id: wire::NaturalPlus::new(1).expect("failed to make player id"),
             species: wire.domain,
@@ -40,6 +40,9 @@ use object::*;
             current_player: current_player,
             opponents: opponents,
             board: board,
+            // The remote protocol doesn't send the rules, remote games are
+            // played by the course rules.
+            rules: RulesConfig::default(),
         })
     }
  }
@@ -61,7 +64,7 @@ impl wire::ToWire<wire::Feeding> for FeedObservation {
 }
 
 impl wire::FromWire<wire::Feeding> for FeedObservation {
-    fn from_wire(wire: wire::Feeding) -> Result<Self, ()> {
+    fn from_wire(wire: wire::Feeding) -> Result<Self, wire::Error> {
         let board = try!(BoardObservation::from_wire(wire.watering_hole));
         let opponents = try!(Vec::from_wire(wire.opponents));
         let current_player = try!(Player::from_wire(wire.current_player));
@@ -70,6 +73,7 @@ impl wire::FromWire<wire::Feeding> for FeedObservation {
             board: board,
             opponents: opponents,
             current_player: current_player,
+            rules: RulesConfig::default(),
         };
 
         if observation.len() >= MIN_PLAYERS &&
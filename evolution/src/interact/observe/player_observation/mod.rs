@@ -17,7 +17,7 @@ impl Observe<PlayerObservation> for Player {
         PlayerObservation {
             id: self.id(),
             domain: self.domain().clone(),
-            bag: self.bag().len() as u64,
+            bag: self.bag_count(),
         }
     }
 }
@@ -3,7 +3,7 @@ use interact::*;
 use object::*;
 
 impl wire::FromWire<wire::Player> for PlayerObservation {
-    fn from_wire(wire: wire::Player) -> Result<Self, ()> {
+    fn from_wire(wire: wire::Player) -> Result<Self, wire::Error> {
         let mut species_vec = Vec::new();
         for s in wire.species.into_iter() {
             species_vec.push(try!(Species::from_wire(s)))
@@ -24,6 +24,10 @@ macro_rules! debug_expr {
 #[macro_use]
 pub mod ext;
 
+/// Errors saying what went wrong while playing a game.
+pub mod error;
+pub use self::error::Error;
+
 /// Objects of the game, like a card or a species.
 ///
 /// These objects contain **complete** knowledge, this means that they
@@ -62,11 +66,27 @@ pub mod interact;
 /// A *very* silly chooser.
 pub mod silly;
 
+/// More choosers to play against, each selectable by name.
+pub mod strategies;
+
 /// Types and functions required for playing a game of Evolution.
 // TODO: Module shouldn't be public.
 pub mod game;
 pub use self::game::Game;
 
+/// Playing many games between a set of choosers, and ranking them.
+pub mod tournament;
+
+/// Playing many headless games between the built in strategies, and
+/// collecting statistics about them.
+pub mod simulation;
+
+/// Playing games back to back between the players waiting in a lobby.
+pub mod lobby;
+
+/// The client's side of the remote protocol.
+pub mod remote;
+
 // /// Control flow for the game.
 // pub mod machine;
 
@@ -89,14 +109,12 @@ macro_rules! test_harness {
             Logger::init().expect("logger failed to start");
 
             let mut reader = io::stdin();
-            if let Ok(wire) = json::from_reader::<_, $wire_type>(&mut reader) {
-                if let Ok($internal_pat) = $internal_data::from_wire(wire) {
-                    $body
-                } else {
-                    panic!("invalid game object");
-                }
-            } else {
-                panic!("invalid wire data");
+            match json::from_reader::<_, $wire_type>(&mut reader) {
+                Ok(wire) => match $internal_data::from_wire(wire) {
+                    Ok($internal_pat) => $body,
+                    Err(e) => panic!("invalid game object: {}", e),
+                },
+                Err(e) => panic!("invalid wire data: {}", e),
             }
         }
     };
@@ -0,0 +1,276 @@
+use std::cmp;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::net::TcpListener;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+use rand;
+use serde_json as json;
+use evolution_wire::Channel;
+use error::Error;
+use game::*;
+use interact::*;
+
+/// A queue of waiting players, which plays games back to back.
+///
+/// Whenever there are at least `MIN_PLAYERS` choosers waiting, up to
+/// `MAX_PLAYERS` of them are taken from the front of the queue and seated
+/// at a new game, played on its own thread. The results of each game are
+/// written to a numbered JSON file in the lobby's results directory,
+/// numbered after any results already there, and every player who wasn't
+/// ejected goes back to the end of the queue.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::env;
+/// use evolution::lobby::Lobby;
+/// use evolution::silly::Silly;
+///
+/// let mut lobby = Lobby::new(env::temp_dir().join("lobby-doc")).unwrap();
+/// for _ in 0..3 {
+///     lobby.join(Silly);
+/// }
+/// let handle = lobby.start_game().unwrap();
+/// handle.join().unwrap().unwrap();
+/// assert_eq!(3, lobby.waiting());
+/// ```
+pub struct Lobby<C: Chooser> {
+    results: PathBuf,
+    budget: Budget,
+    queue: Arc<Mutex<Vec<C>>>,
+    games: usize,
+    // The number of the last results already in the directory.
+    numbered: usize,
+}
+
+impl<C: Chooser + Send + 'static> Lobby<C> {
+    /// Creates a new empty lobby, writing results into the given directory,
+    /// which is created if it doesn't exist.
+    pub fn new<P: Into<PathBuf>>(results: P) -> Result<Self, Error> {
+        let results = results.into();
+        try!(fs::create_dir_all(&results));
+        let numbered = try!(last_result(&results));
+        Ok(Lobby {
+            results: results,
+            budget: Budget::default(),
+            queue: Arc::new(Mutex::new(Vec::new())),
+            games: 0,
+            numbered: numbered,
+        })
+    }
+
+    /// Returns the directory the results of each game are written to.
+    pub fn results(&self) -> &Path {
+        &self.results
+    }
+
+    /// Sets the time budget for the players of every game started after
+    /// this.
+    pub fn set_budget(&mut self, budget: Budget) {
+        self.budget = budget;
+    }
+
+    /// Returns the number of games started so far.
+    pub fn games(&self) -> usize {
+        self.games
+    }
+
+    /// Adds the chooser to the back of the queue.
+    pub fn join(&self, chooser: C) {
+        let mut queue = self.queue.lock().expect("failed to get lock");
+        queue.push(chooser);
+    }
+
+    /// Returns the number of choosers waiting for a game.
+    pub fn waiting(&self) -> usize {
+        self.queue.lock().expect("failed to get lock").len()
+    }
+
+    /// Starts a game on a new thread if enough choosers are waiting,
+    /// returning a handle to the thread, which joins with the result of
+    /// playing the game.
+    pub fn start_game(&mut self) -> Option<thread::JoinHandle<Result<(), Error>>> {
+        let choosers = {
+            let mut queue = self.queue.lock().expect("failed to get lock");
+            if queue.len() < MIN_PLAYERS {
+                return None;
+            }
+            let num = cmp::min(queue.len(), MAX_PLAYERS);
+            queue.drain(0..num).collect::<Vec<_>>()
+        };
+        self.games += 1;
+
+        let number = self.numbered + self.games;
+        let path = self.results.join(format!("game-{}.json", number));
+        let budget = self.budget;
+        let queue = self.queue.clone();
+        let handle = thread::Builder::new().name(format!("game{}", number)).spawn(move || {
+            let mut game = Game::with_choosers(choosers, rand::random())
+                .expect("lobby seated an illegal number of players");
+            game.set_budget(budget);
+            info!("Starting game {} with {} players.", number, game.players().len());
+            if let Err(e) = game.play() {
+                warn!("game {} failed: {}", number, e);
+                return Err(e)
+            }
+            if let Err(e) = write_results(&path, &game) {
+                warn!("failed to write results of game {}: {}", number, e);
+            }
+
+            // Survivors go back to the end of the queue, ejected players are
+            // dropped.
+            let survivors = game.players().iter().map(|p| p.id()).collect::<Vec<_>>();
+            let mut choosers = game.into_choosers();
+            let mut queue = queue.lock().expect("failed to get lock");
+            for id in survivors {
+                if let Some(chooser) = choosers.remove(&id) {
+                    queue.push(chooser);
+                }
+            }
+            info!("Finished game {}, {} players waiting.", number, queue.len());
+            Ok(())
+        }).expect("failed to spawn game thread");
+        Some(handle)
+    }
+}
+
+impl Lobby<Channel> {
+    /// Accepts clients from the listener forever, starting games as enough
+    /// of them join.
+    ///
+    /// Each client's handshake happens on its own thread, so a slow client
+    /// doesn't hold up the others.
+    pub fn run(mut self, listener: TcpListener) -> Result<(), Error> {
+        // Don't block waiting for clients, so games can start in between.
+        try!(listener.set_nonblocking(true));
+
+        loop {
+            match listener.accept() {
+                Ok((stream, _)) => {
+                    let queue = self.queue.clone();
+                    thread::spawn(move || {
+                        if let Err(e) = stream.set_nonblocking(false) {
+                            warn!("couldn't establish channel: {:?}", e);
+                            return;
+                        }
+                        match Channel::accept_from_tcp_stream(stream) {
+                            Ok(channel) => {
+                                info!("Player connected with info `{}`.", channel.info());
+                                queue.lock().expect("failed to get lock").push(channel);
+                            }
+                            Err(e) => {
+                                warn!("couldn't establish channel: {:?}", e);
+                            }
+                        }
+                    });
+                }
+                Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => {}
+                Err(e) => {
+                    warn!("connecting failed: {:?}", e);
+                }
+            }
+
+            self.start_game();
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+}
+
+/// Returns the highest number of the `game-{n}.json` results in the
+/// directory, or 0 if there are none.
+fn last_result(dir: &Path) -> Result<usize, Error> {
+    let mut last = 0;
+    for entry in try!(fs::read_dir(dir)) {
+        let name = try!(entry).file_name();
+        let number = name.to_str().and_then(|name| {
+            if name.starts_with("game-") && name.ends_with(".json") {
+                name["game-".len()..name.len() - ".json".len()].parse().ok()
+            } else {
+                None
+            }
+        });
+        if let Some(number) = number {
+            last = cmp::max(last, number);
+        }
+    }
+    Ok(last)
+}
+
+/// Writes the results of the game to the file at the given path as JSON.
+fn write_results<C: Chooser>(path: &Path, game: &Game<C>) -> Result<(), Error> {
+    let mut file = try!(File::create(path));
+    let results = json::to_string(&game.results()).expect("failed to serialize results");
+    try!(writeln!(file, "{}", results));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs;
+    use std::path::PathBuf;
+    use rand;
+    use serde_json::{self as json, Value};
+    use silly::*;
+    use super::*;
+
+    /// Returns a new empty directory for a test's results.
+    fn results_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("lobby-{}-{}", name, rand::random::<u64>()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn too_few_waiting() {
+        let dir = results_dir("too-few");
+        let mut lobby = Lobby::new(&dir).unwrap();
+        lobby.join(Silly);
+        lobby.join(Silly);
+
+        assert!(lobby.start_game().is_none());
+        assert_eq!(0, lobby.games());
+        assert_eq!(2, lobby.waiting());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn survivors_requeue() {
+        let dir = results_dir("survivors");
+        let mut lobby = Lobby::new(&dir).unwrap();
+        for _ in 0..10 {
+            lobby.join(Silly);
+        }
+
+        lobby.start_game().unwrap().join().unwrap().unwrap();
+
+        // `MAX_PLAYERS` played, and went back behind the two still waiting.
+        assert_eq!(1, lobby.games());
+        assert_eq!(10, lobby.waiting());
+        let file = fs::File::open(dir.join("game-1.json")).unwrap();
+        let results: Value = json::from_reader(file).unwrap();
+        assert_eq!(MAX_PLAYERS, results.as_object().unwrap().get("players").unwrap().as_array().unwrap().len());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn numbers_after_existing_results() {
+        let dir = results_dir("numbering");
+        fs::File::create(dir.join("game-3.json")).unwrap();
+        fs::File::create(dir.join("notes.txt")).unwrap();
+        let mut lobby = Lobby::new(&dir).unwrap();
+        for _ in 0..3 {
+            lobby.join(Silly);
+        }
+
+        lobby.start_game().unwrap().join().unwrap().unwrap();
+
+        assert_eq!(1, lobby.games());
+        assert_eq!(0, fs::metadata(dir.join("game-3.json")).unwrap().len());
+        assert!(fs::metadata(dir.join("game-4.json")).unwrap().len() > 0);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,10 +1,11 @@
+use std::cmp;
 use object::*;
 
 /// An owned board with food tokens, and cards which were played as food for
 /// the round.
 #[derive(Debug, Default, Clone)]
 pub struct Board {
-    food: Vec<FoodToken>,
+    food: u64,
     cards: Option<Vec<Card>>,
 }
 
@@ -19,10 +20,15 @@ impl Board {
     ///
     /// # Returns
     ///
-    /// An immutable slice of type `&[FoodToken]`, containing the food
-    /// tokens on the board currently.
+    /// The number of food tokens on the board currently.
+    pub fn food_count(&self) -> u64 {
+        self.food
+    }
+
+    /// Returns the food tokens on the board.
+    #[deprecated(note = "food is only counted, use `food_count`")]
     pub fn food(&self) -> &[FoodToken] {
-        &self.food
+        super::food_tokens(self.food)
     }
 
     /// Move a token onto the board.
@@ -31,8 +37,8 @@ impl Board {
     ///
     /// * `&mut self` - The board taking food.
     /// * `food_token` - A food token for the board to take.
-    pub fn push_food(&mut self, food_token: FoodToken) {
-        self.food.push(food_token);
+    pub fn push_food(&mut self, _: FoodToken) {
+        self.food += 1;
     }
 
     /// Move many tokens onto the board.
@@ -42,7 +48,7 @@ impl Board {
     /// * `&mut self` - The board taking food.
     /// * `food_tokens` - A vector of food tokens for the board to take.
     pub fn push_foods(&mut self, food_tokens: Vec<FoodToken>) {
-        self.food.extend(food_tokens);
+        self.food += food_tokens.len() as u64;
     }
 
     /// Get a single food token, if available. An empty watering hole will
@@ -57,7 +63,10 @@ impl Board {
     /// This function returns a food token from the board as `Some` or `None`
     /// if there are no more food tokens left.
     pub fn pop_food(&mut self) -> Option<FoodToken> {
-        self.food.pop()
+        self.food.checked_sub(1).map(|food| {
+            self.food = food;
+            FoodToken
+        })
     }
 
     /// Removes many food tokens from the board. This function returns
@@ -68,9 +77,10 @@ impl Board {
     // think we should do this for all pop_*s functions, and that the
     // `Option<...>` type is not correct.
     pub fn pop_foods(&mut self, requested: u64) -> Option<Vec<FoodToken>> {
-        let split = self.food.len().saturating_sub(requested as usize);
-        if split != self.food.len() {
-            Some(self.food.split_off(split))
+        let amount = cmp::min(self.food, requested);
+        if amount != 0 {
+            self.food -= amount;
+            Some((0..amount).map(|_| FoodToken).collect())
         } else {
             None
         }
@@ -159,10 +169,10 @@ mod tests {
         let mut board = Board::default();
         board.push_foods(vec![FoodToken, FoodToken, FoodToken]);
 
-        assert_eq!(vec![FoodToken, FoodToken, FoodToken], board.food());
+        assert_eq!(3, board.food_count());
 
         assert_eq!(Some(vec![FoodToken]), board.pop_foods(1));
-        assert_eq!(vec![FoodToken, FoodToken], board.food());
+        assert_eq!(2, board.food_count());
     }
 
     #[test]
@@ -170,17 +180,28 @@ mod tests {
         let mut board = Board::default();
         board.push_foods(vec![FoodToken, FoodToken, FoodToken]);
 
-        assert_eq!(vec![FoodToken, FoodToken, FoodToken], board.food());
+        assert_eq!(3, board.food_count());
 
         assert_eq!(Some(vec![FoodToken, FoodToken, FoodToken]), board.pop_foods(3));
-        assert!(board.food().is_empty());
+        assert_eq!(0, board.food_count());
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn food_lends_the_counted_tokens() {
+        let mut board = Board::default();
+        board.push_foods(vec![FoodToken, FoodToken, FoodToken]);
+
+        assert_eq!(&[FoodToken, FoodToken, FoodToken], board.food());
+        board.pop_food();
+        assert_eq!(&[FoodToken, FoodToken], board.food());
     }
 
     #[test]
     fn push_and_pop_foods_none() {
         let mut board = Board::default();
 
-        assert_eq!(Vec::<FoodToken>::new(), board.food());
+        assert_eq!(0, board.food_count());
 
         assert_eq!(None, board.pop_foods(1));
     }
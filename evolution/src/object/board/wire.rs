@@ -3,14 +3,14 @@ use object::*;
 
 impl wire::ToWire<wire::Natural> for Board {
     fn to_wire(&self) -> wire::Natural {
-        wire::Natural(self.food().len() as u64)
+        wire::Natural(self.food_count())
     }
 }
 
 impl wire::FromWire<wire::Natural> for Board {
-    fn from_wire(wire: wire::Natural) -> Result<Self, ()> {
+    fn from_wire(wire: wire::Natural) -> Result<Self, wire::Error> {
         Ok(Board {
-            food: (0..*wire).into_iter().map(|_| FoodToken).collect(),
+            food: *wire,
             cards: None,
         })
     }
@@ -26,7 +26,7 @@ mod tests {
         let mut board = Board::default();
         board.push_foods(vec![FoodToken, FoodToken]);
 
-        assert_eq!(2, board.food().len());
+        assert_eq!(2, board.food_count());
 
         let wire = board.to_wire();
 
@@ -38,6 +38,6 @@ mod tests {
         let wire = wire::Natural(10);
         let board = Board::from_wire(wire);
 
-        assert_eq!(10, board.unwrap().food().len());
+        assert_eq!(10, board.unwrap().food_count());
     }
 }
@@ -2,6 +2,8 @@ use std::cmp::PartialOrd;
 use std::cmp::Ordering;
 use std::cmp::Ordering::*;
 use std::ops::Range;
+use rand::{Rng, SeedableRng};
+use rand::isaac::Isaac64Rng;
 use object::*;
 
 const NOT_CARNIVORES: [Trait; 15] = [Trait::Ambush, Trait::Burrowing,
@@ -10,12 +12,25 @@ const NOT_CARNIVORES: [Trait; 15] = [Trait::Ambush, Trait::Burrowing,
     Trait::LongNeck, Trait::PackHunting, Trait::Scavenger,
     Trait::Symbiosis, Trait::WarningCall];
 
-const CARNIVORE_CARD_BOUNDS: Range<i64> = Range {
+const EXPANSION_TRAITS: [Trait; 5] = [Trait::Intelligent, Trait::Nocturnal,
+    Trait::DefensiveHerding, Trait::Migratory, Trait::MassExtinction];
+
+/// The food values of the carnivore cards in a deck, one card of each.
+pub const CARNIVORE_CARD_BOUNDS: Range<i64> = Range {
     start: -8,
     end: 9,
 };
 
-const NON_CARNIVORE_CARD_BOUNDS: Range<i64> = Range {
+/// The food values of the cards of every other trait in a deck, one card
+/// of each.
+pub const NON_CARNIVORE_CARD_BOUNDS: Range<i64> = Range {
+    start: -3,
+    end: 4,
+};
+
+/// The food values of the cards of every expansion trait in an expansion
+/// deck, one card of each.
+pub const EXPANSION_CARD_BOUNDS: Range<i64> = Range {
     start: -3,
     end: 4,
 };
@@ -45,13 +60,29 @@ impl Card {
     /// assert_eq!(122, deck.len());
     /// ```
     pub fn deck() -> Vec<Card> {
+        Card::deck_within(CARNIVORE_CARD_BOUNDS, NON_CARNIVORE_CARD_BOUNDS)
+    }
+
+    /// Returns a new deck with a carnivore card of each food value in
+    /// `carnivores`, and a card of every other trait for each food value in
+    /// `others`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use evolution::object::*;
+    ///
+    /// let deck = Card::deck_within(-1..2, 0..1);
+    /// assert_eq!(3 + 15, deck.len());
+    /// ```
+    pub fn deck_within(carnivores: Range<i64>, others: Range<i64>) -> Vec<Card> {
         let mut cards = Vec::new();
 
-        for food_value in CARNIVORE_CARD_BOUNDS {
+        for food_value in carnivores {
             cards.push(Card(food_value, Trait::Carnivore));
         }
 
-        for food_value in NON_CARNIVORE_CARD_BOUNDS {
+        for food_value in others {
             for trait_type in NOT_CARNIVORES.iter() {
                 cards.push(Card(food_value, *trait_type));
             }
@@ -61,6 +92,62 @@ impl Card {
         cards
     }
 
+    /// Returns a new deck of all the cards for a game with the expansion
+    /// traits, 7 cards of each on top of the usual deck.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use evolution::object::*;
+    ///
+    /// let deck = Card::expansion_deck();
+    /// assert_eq!(122 + 35, deck.len());
+    /// ```
+    pub fn expansion_deck() -> Vec<Card> {
+        let mut cards = Card::deck();
+        cards.extend(Card::expansion_within(EXPANSION_CARD_BOUNDS));
+        cards.sort();
+        cards
+    }
+
+    /// Returns a card of every expansion trait for each food value in
+    /// `food_values`.
+    pub fn expansion_within(food_values: Range<i64>) -> Vec<Card> {
+        let mut cards = Vec::new();
+        for food_value in food_values {
+            for trait_type in EXPANSION_TRAITS.iter() {
+                cards.push(Card(food_value, *trait_type));
+            }
+        }
+        cards.sort();
+        cards
+    }
+
+    /// Returns a new deck of all the cards for a game, shuffled by a random
+    /// number generator seeded with the given seed. The same seed will always
+    /// result in the same ordering of cards.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use evolution::object::*;
+    ///
+    /// let deck = Card::shuffled_deck(42);
+    /// assert_eq!(122, deck.len());
+    /// assert_eq!(deck, Card::shuffled_deck(42));
+    /// ```
+    pub fn shuffled_deck(seed: u64) -> Vec<Card> {
+        Card::shuffled(Card::deck(), seed)
+    }
+
+    /// Returns the given cards shuffled by a random number generator seeded
+    /// with the given seed, like `shuffled_deck`.
+    pub fn shuffled(mut cards: Vec<Card>, seed: u64) -> Vec<Card> {
+        let mut rng = Isaac64Rng::from_seed(&[seed]);
+        rng.shuffle(&mut cards);
+        cards
+    }
+
     /// The trait of this card. If this card is used to evolve a species,
     /// that species will get this trait.
     pub fn trait_type(&self) -> Trait {
@@ -171,6 +258,20 @@ mod tests {
         }
     }
 
+    #[test]
+    fn expansion_deck_counts() {
+        use std::collections::HashMap;
+
+        let deck = Card::expansion_deck();
+        let map = deck.into_iter().fold(HashMap::new(), |mut map, card| {
+            *map.entry(card.trait_type()).or_insert(0) += 1;
+            map
+        });
+        assert_eq!(21, map.len());
+        assert_eq!(Some(&7), map.get(&Trait::MassExtinction));
+        assert_eq!(Some(&17), map.get(&Trait::Carnivore));
+    }
+
     #[test]
     fn deck_no_non_carnivores_outside_bounds() {
         let deck = Card::deck();
@@ -183,6 +284,19 @@ mod tests {
         }
     }
 
+    #[test]
+    fn shuffled_deck_has_every_card() {
+        let mut deck = Card::shuffled_deck(1);
+        deck.sort();
+        assert_eq!(Card::deck(), deck);
+    }
+
+    #[test]
+    fn shuffled_deck_depends_on_seed() {
+        assert_eq!(Card::shuffled_deck(1), Card::shuffled_deck(1));
+        assert!(Card::shuffled_deck(1) != Card::shuffled_deck(2));
+    }
+
     #[test]
     fn ordering_of_trait_takes_precedence() {
         assert!(Card(3, Trait::Burrowing) < Card(-2, Trait::WarningCall));
@@ -8,7 +8,7 @@ impl wire::ToWire<wire::SpeciesCard> for Card {
 }
 
 impl wire::FromWire<wire::SpeciesCard> for Card {
-    fn from_wire(wire: wire::SpeciesCard) -> Result<Card, ()> {
+    fn from_wire(wire: wire::SpeciesCard) -> Result<Card, wire::Error> {
         let food_value = *wire.0;
         let trait_type = try!(Trait::from_wire(wire.1));
         let bounds = match trait_type {
@@ -18,7 +18,7 @@ impl wire::FromWire<wire::SpeciesCard> for Card {
         if food_value >= bounds.0 && food_value <= bounds.1 {
             Ok(Card(food_value, trait_type))
         } else {
-            Err(())
+            Err(wire::Error::invalid(format!("{:?} card can't have food value {}", trait_type, food_value)))
         }
     }
 }
@@ -1,4 +1,5 @@
 use std::ops::{Deref, Index, IndexMut};
+use error::Error;
 use object::*;
 
 /// A collection of species for a player.
@@ -73,7 +74,7 @@ impl Domain {
 
     /// Kill one population of the given species in this domain, returning
     /// true if the species went extinct.
-    pub fn kill(&mut self, species_idx: usize) -> Result<bool, ()> {
+    pub fn kill(&mut self, species_idx: usize) -> Result<bool, Error> {
         if try!(self[species_idx].kill()) {
             self.0.remove(species_idx);
             Ok(true)
@@ -85,7 +86,7 @@ impl Domain {
     /// Cull all the species in this domain, removing extinct species in the
     /// process. This function returns the number of species that went
     /// extinct.
-    pub fn cull(&mut self) -> Result<usize, ()> {
+    pub fn cull(&mut self) -> Result<usize, Error> {
         let mut extinctions = 0;
         for species in self.0.iter_mut() {
             if try!(species.cull()) {
@@ -8,7 +8,7 @@ impl wire::ToWire<wire::LOS> for Domain {
 }
 
 impl wire::FromWire<wire::LOS> for Domain {
-    fn from_wire(wire: wire::LOS) -> Result<Self, ()> {
+    fn from_wire(wire: wire::LOS) -> Result<Self, wire::Error> {
         Ok(try!(Vec::from_wire(wire)).into())
     }
 }
@@ -44,10 +44,10 @@ mod tests {
         let domain = Domain::from_wire(wire).unwrap();
 
         assert_eq!(1, domain.len());
-        assert_eq!(2, domain[0].food().len());
+        assert_eq!(2, domain[0].food_count());
         assert_eq!(3, domain[0].population());
         assert_eq!(1, domain[0].traits().len());
         assert!(domain[0].has_trait(Trait::FatTissue));
-        assert_eq!(1, domain[0].fat().len());
+        assert_eq!(1, domain[0].fat_count());
     }
 }
@@ -1,7 +1,21 @@
 /// A structure enforcing single ownership over all food tokens in the game world.
+///
+/// A food token takes no space, so boards, players and species only keep a
+/// count of the tokens they hold.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct FoodToken;
 
+const FOOD_TOKEN: FoodToken = FoodToken;
+
+/// Every food token there could be, for lending out slices of tokens which
+/// are only kept as counts. This takes no space, as a food token takes none.
+static FOOD_TOKENS: [FoodToken; ::std::usize::MAX] = [FOOD_TOKEN; ::std::usize::MAX];
+
+/// Returns a slice of `count` food tokens.
+fn food_tokens(count: u64) -> &'static [FoodToken] {
+    &FOOD_TOKENS[..count as usize]
+}
+
 /// A placement describes where players can place a new species in
 /// relation to their other species.
 #[derive(Debug, Copy, Clone)]
@@ -42,7 +56,7 @@ mod board;
 pub use self::board::Board;
 
 mod card;
-pub use self::card::Card;
+pub use self::card::{Card, CARNIVORE_CARD_BOUNDS, NON_CARNIVORE_CARD_BOUNDS, EXPANSION_CARD_BOUNDS};
 
 mod domain;
 pub use self::domain::Domain;
@@ -51,7 +65,7 @@ mod player;
 pub use self::player::Player;
 
 mod species;
-pub use self::species::Species;
+pub use self::species::{Species, AttackExplanation, Factor, Party, Verdict};
 
 mod traits;
-pub use self::traits::Trait;
+pub use self::traits::{Trait, Effect, Attack};
@@ -6,7 +6,7 @@ use object::*;
 pub struct Player {
     id: Id,
     domain: Domain,
-    bag: Vec<FoodToken>,
+    bag: u64,
     hand: Vec<Card>,
 }
 
@@ -32,7 +32,7 @@ impl Player {
         Player {
             id: id,
             domain: Domain::default(),
-            bag: Vec::new(),
+            bag: 0,
             hand: Vec::new(),
         }
     }
@@ -51,9 +51,11 @@ impl Player {
         self.id
     }
 
-    /// Returns the player's score.
+    /// Returns the player's score, the food in their bag, plus the population
+    /// and traits of their species. This is the scoring of the board game,
+    /// which the course rules keep, so it's the same for every `RulesMode`.
     pub fn score(&self) -> u64 {
-        (self.bag.len() as u64) +
+        self.bag +
         self.domain().iter().fold(0, |a, s| a + s.population()) +
         self.domain().iter().fold(0, |a, s| a + (s.traits().len() as u64))
     }
@@ -94,9 +96,15 @@ impl Player {
 
 /// Bag functions.
 impl Player {
-    /// Returns a view of this player's food tokens.
+    /// Returns the number of food tokens in this player's bag.
+    pub fn bag_count(&self) -> u64 {
+        self.bag
+    }
+
+    /// Returns the food tokens in this player's bag.
+    #[deprecated(note = "the bag is only counted, use `bag_count`")]
     pub fn bag(&self) -> &[FoodToken] {
-        &self.bag
+        super::food_tokens(self.bag)
     }
 
 
@@ -104,7 +112,12 @@ impl Player {
     /// into the bag.
     pub fn bag_food(&mut self) {
         let food = self.domain_mut().take_food();
-        self.bag.extend(food);
+        self.bag += food.len() as u64;
+    }
+
+    /// Adds the given food tokens straight to this player's bag.
+    pub fn push_bag(&mut self, food_tokens: Vec<FoodToken>) {
+        self.bag += food_tokens.len() as u64;
     }
 }
 
@@ -253,10 +266,10 @@ mod tests {
         player.domain_mut()[1].eat(FoodToken).unwrap();
         player.domain_mut()[1].eat(FoodToken).unwrap();
 
-        assert_eq!(0, player.bag().len());
+        assert_eq!(0, player.bag_count());
 
         player.bag_food();
 
-        assert_eq!(3, player.bag().len());
+        assert_eq!(3, player.bag_count());
     }
 }
@@ -2,9 +2,9 @@ use evolution_wire as wire;
 use object::*;
 
 impl wire::FromWire<wire::remote::Start> for Player {
-    fn from_wire(wire: wire::remote::Start) -> Result<Player, ()> {
+    fn from_wire(wire: wire::remote::Start) -> Result<Player, wire::Error> {
         let domain = try!(Domain::from_wire(wire.domain));
-        let bag = (0..*wire.bag).map(|_| FoodToken).collect();
+        let bag = *wire.bag;
         let mut hand = Vec::new();
         for card in wire.hand {
             hand.push(try!(Card::from_wire(card)));
@@ -19,7 +19,7 @@ impl wire::FromWire<wire::remote::Start> for Player {
 }
 
 impl wire::FromWire<wire::Player> for Player {
-    fn from_wire(wire: wire::Player) -> Result<Player, ()> {
+    fn from_wire(wire: wire::Player) -> Result<Player, wire::Error> {
         let wire::Player { id, species, bag, cards } = wire;
         let mut species_vec = Vec::new();
         for s in species.into_iter() {
@@ -37,7 +37,7 @@ impl wire::FromWire<wire::Player> for Player {
         Ok(Player {
             id: *id,
             domain: species_vec.into(),
-            bag: (0..*bag).into_iter().map(|_| FoodToken).collect(),
+            bag: *bag,
             hand: card_vec,
         })
     }
@@ -49,14 +49,14 @@ impl wire::ToWire<wire::Player> for Player {
             wire::Player {
                 id: self.id().to_wire(),
                 species: self.domain().to_wire(),
-                bag: (self.bag().len() as u64).to_wire(),
+                bag: self.bag_count().to_wire(),
                 cards: None,
             }
         } else {
             wire::Player {
                 id: self.id.to_wire(),
                 species: self.domain().to_wire(),
-                bag: (self.bag().len() as u64).to_wire(),
+                bag: self.bag_count().to_wire(),
                 cards: Some(self.hand().to_wire()),
             }
         }
@@ -149,7 +149,7 @@ mod tests {
         assert_eq!(1, player.id());
         assert_eq!(1, player.domain().len());
         assert_eq!(1, player.domain()[0].population());
-        assert_eq!(0, player.bag().len());
+        assert_eq!(0, player.bag_count());
         assert_eq!(Trait::Carnivore, player.hand()[0].trait_type());
     }
 
@@ -176,7 +176,7 @@ mod tests {
 
         let player = Player::from_wire(wire).unwrap();
 
-        assert_eq!(3, player.bag().len());
+        assert_eq!(3, player.bag_count());
         assert_eq!(1, player.domain().len());
         assert_eq!(0, player.hand().len());
     }
@@ -209,7 +209,7 @@ mod tests {
 
         let player = Player::from_wire(wire).unwrap();
 
-        assert_eq!(3, player.bag().len());
+        assert_eq!(3, player.bag_count());
         assert_eq!(1, player.domain().len());
         assert_eq!(4, player.hand().len());
     }
@@ -0,0 +1,449 @@
+use std::fmt;
+use object::*;
+use object::traits::TraitSet;
+
+/// One of the species taking part in an attack.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Party {
+    Attacker,
+    Target,
+    /// The target's left neighbor.
+    Left,
+    /// The target's right neighbor.
+    Right,
+}
+
+/// What a trait does to an attack.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    /// The trait stops the attack.
+    Blocks,
+    /// The trait lets the attack happen, or overcomes a defence.
+    Enables,
+    /// The trait lets the attacker through a single trait which would
+    /// otherwise stop the attack.
+    Outwits,
+}
+
+/// A trait of one of the species in an attack which has a say in it, with
+/// the reason why.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Factor {
+    pub party: Party,
+    pub trait_type: Trait,
+    pub verdict: Verdict,
+    pub reason: String,
+}
+
+impl Factor {
+    fn new(party: Party, trait_type: Trait, verdict: Verdict, reason: String) -> Self {
+        Factor {
+            party: party,
+            trait_type: trait_type,
+            verdict: verdict,
+            reason: reason,
+        }
+    }
+}
+
+impl fmt::Display for Factor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let party = match self.party {
+            Party::Attacker => "attacker",
+            Party::Target => "target",
+            Party::Left => "left neighbor",
+            Party::Right => "right neighbor",
+        };
+        let verdict = match self.verdict {
+            Verdict::Blocks => "blocks",
+            Verdict::Enables => "enables",
+            Verdict::Outwits => "outwits",
+        };
+        write!(f, "{} ({}'s {:?}): {}", verdict, party, self.trait_type, self.reason)
+    }
+}
+
+/// Why an attacker can or can't attack a target, as the traits which block
+/// and enable the attack, see `Species::explain_attack`.
+///
+/// An attacker must be a carnivore, and can attack when no trait blocks it,
+/// or when a single trait blocks it and one of the attacker's traits
+/// outwits it. Warning calls from both neighbors count as one block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AttackExplanation {
+    factors: Vec<Factor>,
+}
+
+impl AttackExplanation {
+    /// Returns every trait with a say in the attack, the attacker's first,
+    /// then the target's, then the neighbors'.
+    pub fn factors(&self) -> &[Factor] {
+        &self.factors
+    }
+
+    /// Returns the traits which block the attack.
+    pub fn blocks(&self) -> Vec<&Factor> {
+        self.factors.iter().filter(|f| f.verdict == Verdict::Blocks).collect()
+    }
+
+    /// Returns the traits which enable the attack, or outwit a block.
+    pub fn enables(&self) -> Vec<&Factor> {
+        self.factors.iter().filter(|f| f.verdict != Verdict::Blocks).collect()
+    }
+
+    /// Returns true if the attack can happen.
+    pub fn can_attack(&self) -> bool {
+        let mut tally = Tally::default();
+        for factor in self.factors.iter() {
+            tally.count(factor.party, factor.trait_type, factor.verdict);
+        }
+        tally.can_attack()
+    }
+}
+
+impl fmt::Display for AttackExplanation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.can_attack() {
+            try!(writeln!(f, "the attacker can attack"));
+        } else {
+            try!(writeln!(f, "the attacker can't attack"));
+        }
+        for factor in self.factors.iter() {
+            try!(writeln!(f, "{}", factor));
+        }
+        Ok(())
+    }
+}
+
+/// The traits with a say in an attack, counted to decide whether it can
+/// happen.
+#[derive(Default)]
+struct Tally {
+    carnivore: bool,
+    blocks: TraitSet,
+    outwits: bool,
+}
+
+impl Tally {
+    fn count(&mut self, party: Party, trait_type: Trait, verdict: Verdict) {
+        match verdict {
+            Verdict::Blocks => {
+                self.blocks.insert(trait_type);
+            },
+            Verdict::Enables => {
+                if party == Party::Attacker && trait_type == Trait::Carnivore {
+                    self.carnivore = true;
+                }
+            },
+            Verdict::Outwits => self.outwits = true,
+        }
+    }
+
+    fn can_attack(&self) -> bool {
+        self.carnivore && (self.blocks.is_empty() || (self.blocks.len() == 1 && self.outwits))
+    }
+}
+
+/// Attacking functions.
+impl Species {
+    /// Returns true if this species can attack the given target species,
+    /// given it's neighbors. The traits of each card dictate the result, see
+    /// `explain_attack` for which traits, and why.
+    ///
+    /// # Undefined Behavior
+    ///
+    /// The behavior of this function is not defined for attacking extinct
+    /// target species.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use evolution::object::*;
+    ///
+    /// let mut attacker = Species::default();
+    /// attacker.evolve(Trait::Carnivore).expect("unable to evolve");
+    /// let defender = Species::default();
+    /// // attacker can attack defender with no neighbors to help.
+    /// assert!(attacker.can_attack(&defender, None, None));
+    /// ```
+    pub fn can_attack(&self,
+                      target: &Species,
+                      left: Option<&Species>,
+                      right: Option<&Species>) -> bool
+    {
+        let mut tally = Tally::default();
+        self.judge_attack(target, left, right, &mut |party, trait_type, verdict, _| {
+            tally.count(party, trait_type, verdict);
+        });
+        tally.can_attack()
+    }
+
+    /// Explains whether this species can attack the given target species,
+    /// given it's neighbors, listing the traits which block and enable the
+    /// attack with the reason for each. This is the reasoning behind
+    /// `can_attack`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use evolution::object::*;
+    ///
+    /// let mut attacker = Species::default();
+    /// attacker.evolve(Trait::Carnivore).unwrap();
+    /// let mut target = Species::default();
+    /// target.evolve(Trait::Climbing).unwrap();
+    ///
+    /// let explanation = attacker.explain_attack(&target, None, None);
+    /// assert!(!explanation.can_attack());
+    /// assert_eq!(Trait::Climbing, explanation.blocks()[0].trait_type);
+    /// assert_eq!(Party::Target, explanation.blocks()[0].party);
+    /// ```
+    pub fn explain_attack(&self,
+                          target: &Species,
+                          left: Option<&Species>,
+                          right: Option<&Species>) -> AttackExplanation
+    {
+        let mut factors = vec![];
+        self.judge_attack(target, left, right, &mut |party, trait_type, verdict, reason| {
+            factors.push(Factor::new(party, trait_type, verdict, reason()));
+        });
+        AttackExplanation {
+            factors: factors,
+        }
+    }
+
+    /// Gives every trait with a say in an attack on the target to the
+    /// visitor, the attacker's first, then the target's, then the
+    /// neighbors', along with a function making the reason for it.
+    fn judge_attack(&self,
+                    target: &Species,
+                    left: Option<&Species>,
+                    right: Option<&Species>,
+                    visit: &mut FnMut(Party, Trait, Verdict, &Fn() -> String))
+    {
+        if self.has_trait(Trait::Carnivore) {
+            visit(Party::Attacker, Trait::Carnivore, Verdict::Enables,
+                  &|| "the attacker is a carnivore".to_string());
+        } else {
+            visit(Party::Attacker, Trait::Carnivore, Verdict::Blocks,
+                  &|| "the attacker isn't a carnivore".to_string());
+        }
+
+        // This is the body size we use for checks for attacks.
+        let attack_size = if self.has_trait(Trait::PackHunting) {
+            self.population() + self.body_size()
+        } else {
+            self.body_size()
+        };
+        let shell = target.body_size() + HARD_SHELL_PROTECTION;
+
+        if target.has_trait(Trait::Climbing) && self.has_trait(Trait::Climbing) {
+            visit(Party::Attacker, Trait::Climbing, Verdict::Enables,
+                  &|| "the attacker climbs after the climbing target".to_string());
+        }
+        if target.has_trait(Trait::HardShell) && self.has_trait(Trait::PackHunting) &&
+           self.body_size() < shell && attack_size >= shell
+        {
+            visit(Party::Attacker, Trait::PackHunting, Verdict::Enables, &|| {
+                format!("pack hunting makes the attacker's size {}, enough to crack the hard shell", attack_size)
+            });
+        }
+        let warning = left.map_or(false, |s| s.has_trait(Trait::WarningCall)) ||
+                      right.map_or(false, |s| s.has_trait(Trait::WarningCall));
+        if warning && self.has_trait(Trait::Ambush) {
+            visit(Party::Attacker, Trait::Ambush, Verdict::Enables,
+                  &|| "ambush overcomes the warning call".to_string());
+        }
+        let attack = Attack::new(self, target, left, right);
+        for &t in self.traits() {
+            if t.effect().outwits(&attack) {
+                visit(Party::Attacker, t, Verdict::Outwits,
+                      &|| "the attacker can get past one defence".to_string());
+            }
+        }
+
+        if target.has_trait(Trait::Burrowing) && target.food_count() == target.population() {
+            visit(Party::Target, Trait::Burrowing, Verdict::Blocks, &|| {
+                format!("the target is burrowed, with food equal to it's population of {}", target.population())
+            });
+        }
+        if target.has_trait(Trait::Climbing) && !self.has_trait(Trait::Climbing) {
+            visit(Party::Target, Trait::Climbing, Verdict::Blocks,
+                  &|| "the target climbs, and the attacker doesn't".to_string());
+        }
+        if target.has_trait(Trait::HardShell) && attack_size < shell {
+            visit(Party::Target, Trait::HardShell, Verdict::Blocks, &|| {
+                format!("the attacker's size {} is less than the target's body size plus {}, {}",
+                        attack_size, HARD_SHELL_PROTECTION, shell)
+            });
+        }
+        if target.has_trait(Trait::Herding) && self.population() <= target.population() {
+            visit(Party::Target, Trait::Herding, Verdict::Blocks, &|| {
+                format!("the attacker's population {} is no larger than the target's {}",
+                        self.population(), target.population())
+            });
+        }
+        if target.has_trait(Trait::Symbiosis) {
+            if let Some(r) = right {
+                if r.body_size() > target.body_size() {
+                    visit(Party::Target, Trait::Symbiosis, Verdict::Blocks, &|| {
+                        format!("the right neighbor's body size {} is larger than the target's {}",
+                                r.body_size(), target.body_size())
+                    });
+                }
+            }
+        }
+        // Traits with an effect, like expansion traits, defend through
+        // their hooks.
+        for &t in target.traits() {
+            if t.effect().defends(&attack) {
+                visit(Party::Target, t, Verdict::Blocks, &|| t.effect().defence(&attack));
+            }
+        }
+
+        if !self.has_trait(Trait::Ambush) {
+            for &(party, neighbor) in [(Party::Left, left), (Party::Right, right)].iter() {
+                if neighbor.map_or(false, |s| s.has_trait(Trait::WarningCall)) {
+                    let side = if party == Party::Left { "left" } else { "right" };
+                    visit(party, Trait::WarningCall, Verdict::Blocks, &|| {
+                        format!("the target's {} neighbor warns it, and the attacker can't ambush", side)
+                    });
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use object::*;
+
+    fn carnivore() -> Species {
+        let mut species = Species::default();
+        species.evolve(Trait::Carnivore).unwrap();
+        species
+    }
+
+    #[test]
+    fn not_a_carnivore() {
+        let explanation = Species::default().explain_attack(&Species::default(), None, None);
+
+        assert!(!explanation.can_attack());
+        assert_eq!(1, explanation.blocks().len());
+        assert_eq!(Party::Attacker, explanation.blocks()[0].party);
+        assert_eq!(Trait::Carnivore, explanation.blocks()[0].trait_type);
+    }
+
+    #[test]
+    fn warning_calls_block_once() {
+        let mut attacker = carnivore();
+        attacker.evolve(Trait::Intelligent).unwrap();
+        let mut warning = Species::default();
+        warning.evolve(Trait::WarningCall).unwrap();
+
+        let explanation = attacker.explain_attack(&Species::default(), Some(&warning), Some(&warning));
+        assert_eq!(vec![Party::Left, Party::Right], explanation.blocks().iter().map(|f| f.party).collect::<Vec<_>>());
+        assert!(explanation.can_attack());
+
+        attacker.exchange_trait(1, Trait::Ambush).unwrap();
+        let explanation = attacker.explain_attack(&Species::default(), Some(&warning), Some(&warning));
+        assert!(explanation.blocks().is_empty());
+        assert!(explanation.enables().iter().any(|f| f.trait_type == Trait::Ambush));
+        assert!(explanation.can_attack());
+    }
+
+    #[test]
+    fn pack_hunting_cracks_hard_shells() {
+        let mut attacker = carnivore();
+        attacker.evolve(Trait::PackHunting).unwrap();
+        for _ in 0..3 {
+            attacker.breed().unwrap();
+            attacker.grow().unwrap();
+        }
+        let mut target = Species::default();
+        target.evolve(Trait::HardShell).unwrap();
+        target.evolve(Trait::Herding).unwrap();
+        target.grow().unwrap();
+
+        let explanation = attacker.explain_attack(&target, None, None);
+        assert!(explanation.can_attack());
+        assert_eq!(Some(Trait::PackHunting), explanation.enables().last().map(|f| f.trait_type));
+
+        target.breed().unwrap();
+        target.breed().unwrap();
+        target.breed().unwrap();
+        let explanation = attacker.explain_attack(&target, None, None);
+        assert!(!explanation.can_attack());
+        assert_eq!(Trait::Herding, explanation.blocks()[0].trait_type);
+    }
+
+    #[test]
+    fn defences_give_their_effects_reason() {
+        let mut target = Species::default();
+        target.evolve(Trait::DefensiveHerding).unwrap();
+        target.breed().unwrap();
+
+        let explanation = carnivore().explain_attack(&target, None, None);
+        assert_eq!(Trait::DefensiveHerding, explanation.blocks()[0].trait_type);
+        assert_eq!("the attacker's body size 0 is no larger than the target's population 2",
+                   explanation.blocks()[0].reason);
+    }
+
+    /// A species with the given traits, population, body size and food.
+    fn species(traits: &[Trait], population: u64, body_size: u64, food: u64) -> Species {
+        let mut species = Species::default();
+        for t in traits {
+            species.evolve(*t).unwrap();
+        }
+        for _ in 1..population {
+            species.breed().unwrap();
+        }
+        for _ in 0..body_size {
+            species.grow().unwrap();
+        }
+        for _ in 0..food {
+            species.eat(FoodToken).unwrap();
+        }
+        species
+    }
+
+    #[test]
+    fn can_attack_truth_table() {
+        use object::Trait::*;
+        let none = species(&[], 1, 0, 0);
+        // The attacker, target, left and right neighbors, and whether the
+        // attack is allowed.
+        let table = vec![
+            (species(&[], 1, 0, 0), none, None, None, false),
+            (species(&[Carnivore], 1, 0, 0), none, None, None, true),
+            (species(&[Carnivore], 1, 0, 0), species(&[Burrowing], 1, 0, 1), None, None, false),
+            (species(&[Carnivore], 1, 0, 0), species(&[Burrowing], 2, 0, 1), None, None, true),
+            (species(&[Carnivore], 1, 0, 0), species(&[Climbing], 1, 0, 0), None, None, false),
+            (species(&[Carnivore, Climbing], 1, 0, 0), species(&[Climbing], 1, 0, 0), None, None, true),
+            (species(&[Carnivore], 1, 3, 0), species(&[HardShell], 1, 0, 0), None, None, false),
+            (species(&[Carnivore], 1, 4, 0), species(&[HardShell], 1, 0, 0), None, None, true),
+            (species(&[Carnivore, PackHunting], 2, 2, 0), species(&[HardShell], 1, 0, 0), None, None, true),
+            (species(&[Carnivore], 1, 0, 0), species(&[Herding], 1, 0, 0), None, None, false),
+            (species(&[Carnivore], 2, 0, 0), species(&[Herding], 1, 0, 0), None, None, true),
+            (species(&[Carnivore], 1, 0, 0), species(&[Symbiosis], 1, 0, 0), None, Some(species(&[], 1, 1, 0)), false),
+            (species(&[Carnivore], 1, 0, 0), species(&[Symbiosis], 1, 0, 0), Some(species(&[], 1, 1, 0)), None, true),
+            (species(&[Carnivore], 1, 0, 0), none, Some(species(&[WarningCall], 1, 0, 0)), None, false),
+            (species(&[Carnivore], 1, 0, 0), none, None, Some(species(&[WarningCall], 1, 0, 0)), false),
+            (species(&[Carnivore, Ambush], 1, 0, 0), none, None, Some(species(&[WarningCall], 1, 0, 0)), true),
+            (species(&[Carnivore, Intelligent], 1, 0, 0), none,
+             Some(species(&[WarningCall], 1, 0, 0)), Some(species(&[WarningCall], 1, 0, 0)), true),
+            (species(&[Carnivore, Intelligent], 1, 0, 0), species(&[Climbing], 1, 0, 0), None, None, true),
+            (species(&[Carnivore, Intelligent], 1, 0, 0), species(&[Climbing, Herding], 1, 0, 0), None, None, false),
+            (species(&[Carnivore], 1, 0, 0), species(&[Nocturnal], 1, 0, 0), None, None, false),
+            (species(&[Carnivore, Nocturnal], 1, 0, 0), species(&[Nocturnal], 1, 0, 0), None, None, true),
+            (species(&[Carnivore], 1, 1, 0), species(&[DefensiveHerding], 1, 0, 0), None, None, false),
+            (species(&[Carnivore], 1, 2, 0), species(&[DefensiveHerding], 1, 0, 0), None, None, true),
+        ];
+        for (attacker, target, left, right, allowed) in table {
+            let explanation = attacker.explain_attack(&target, left.as_ref(), right.as_ref());
+            assert_eq!(allowed, explanation.can_attack(),
+                       "{:?} attacking {:?}, between {:?} and {:?}: {}", attacker, target, left, right, explanation);
+            assert_eq!(allowed, attacker.can_attack(&target, left.as_ref(), right.as_ref()));
+        }
+    }
+}
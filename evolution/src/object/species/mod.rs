@@ -1,6 +1,7 @@
 use std::cmp;
-use std::mem::swap;
+use error::{Error, RuleError};
 use object::*;
+use object::traits::TraitSet;
 
 /// A species which evolves, feeds, grows, and fluctuates in population
 /// throughout the game.
@@ -30,16 +31,16 @@ use object::*;
 /// assert_eq!(1, species.population());
 /// assert_eq!(0, species.body_size());
 /// assert!(species.traits().is_empty());
-/// assert!(species.food().is_empty());
-/// // TODO: `assert!(species.fat().is_empty());` really shouldn't panic.
+/// assert_eq!(0, species.food_count());
+/// assert_eq!(0, species.fat_count());
 /// ```
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Species {
     population: u64,
     body_size: u64,
-    traits: Vec<Trait>,
-    food: Vec<FoodToken>,
-    fat: Vec<FoodToken>,
+    traits: TraitSet,
+    food: u64,
+    fat: u64,
 }
 
 /// Population functions.
@@ -86,12 +87,28 @@ impl Species {
     /// spec.breed().expect("unable to breed");
     /// assert_eq!(2, spec.population());
     /// ```
-    pub fn breed(&mut self) -> Result<u64, ()> {
-        if self.population < MAX_POPULATION {
+    pub fn breed(&mut self) -> Result<u64, Error> {
+        self.breed_up_to(MAX_POPULATION)
+    }
+
+    /// Increase the population of this species by one, like `breed`, but
+    /// with the given maximum population in place of `MAX_POPULATION`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use evolution::object::*;
+    ///
+    /// let mut spec = Species::default();
+    /// assert!(spec.breed_up_to(1).is_err());
+    /// assert_eq!(2, spec.breed_up_to(2).unwrap());
+    /// ```
+    pub fn breed_up_to(&mut self, max: u64) -> Result<u64, Error> {
+        if self.population < max {
             self.population += 1;
             Ok(self.population)
         } else {
-            Err(())
+            Err(Error::Rule(RuleError::MaxPopulation))
         }
     }
 
@@ -111,17 +128,14 @@ impl Species {
     /// spec.kill().unwrap();
     /// assert_eq!(0, spec.population());
     /// ```
-    pub fn kill(&mut self) -> Result<bool, ()> {
+    pub fn kill(&mut self) -> Result<bool, Error> {
         match self.population.checked_sub(1) {
             Some(n) => {
                 self.population = n;
-                self.food.truncate(n as usize);
+                self.food = cmp::min(self.food, n);
                 Ok(self.is_extinct())
             }
-            None => {
-                println!("hit");
-                Err(())
-            }
+            None => Err(Error::Rule(RuleError::Extinct)),
         }
     }
 
@@ -141,8 +155,8 @@ impl Species {
     /// spec.cull().unwrap();
     /// assert_eq!(1, spec.population());
     /// ```
-    pub fn cull(&mut self) -> Result<bool, ()> {
-        while self.population() > self.food().len() as u64 {
+    pub fn cull(&mut self) -> Result<bool, Error> {
+        while self.population() > self.food_count() {
             try!(self.kill());
         }
         Ok(self.is_extinct())
@@ -180,12 +194,18 @@ impl Species {
     /// spec.grow().expect("unable to grow");
     /// assert_eq!(1, spec.body_size());
     /// ```
-    pub fn grow(&mut self) -> Result<u64, ()> {
-        if self.body_size < MAX_BODY_SIZE {
+    pub fn grow(&mut self) -> Result<u64, Error> {
+        self.grow_up_to(MAX_BODY_SIZE)
+    }
+
+    /// Grow a species, like `grow`, but with the given maximum body size in
+    /// place of `MAX_BODY_SIZE`.
+    pub fn grow_up_to(&mut self, max: u64) -> Result<u64, Error> {
+        if self.body_size < max {
             self.body_size += 1;
             Ok(self.body_size)
         } else {
-            Err(())
+            Err(Error::Rule(RuleError::MaxBodySize))
         }
     }
 }
@@ -204,7 +224,7 @@ impl Species {
     /// assert_eq!(1, spec.traits().len());
     /// ```
     pub fn traits(&self) -> &[Trait] {
-        &self.traits
+        self.traits.as_slice()
     }
 
     /// Returns true if this species has the given trait.
@@ -220,7 +240,7 @@ impl Species {
     /// assert!(!spec.has_trait(Trait::Climbing));
     /// ```
     pub fn has_trait(&self, t: Trait) -> bool {
-        self.traits.contains(&t)
+        self.traits.contains(t)
     }
 
     /// Give this species the given trait. This function returns `Err` when
@@ -239,14 +259,19 @@ impl Species {
     /// assert!(spec.has_trait(Trait::Climbing));
     /// assert!(spec.has_trait(Trait::FatTissue));
     /// ```
-    pub fn evolve(&mut self, t: Trait) -> Result<(), ()> {
-        if self.traits.len() >= MAX_TRAITS {
-            return Err(())
+    pub fn evolve(&mut self, t: Trait) -> Result<(), Error> {
+        self.evolve_up_to(t, MAX_TRAITS)
+    }
+
+    /// Give this species the given trait, like `evolve`, but with the given
+    /// maximum number of traits in place of `MAX_TRAITS`.
+    pub fn evolve_up_to(&mut self, t: Trait, max: usize) -> Result<(), Error> {
+        if self.traits.len() >= max {
+            return Err(Error::Rule(RuleError::MaxTraits))
         }
-        if self.traits().contains(&t) {
-            return Err(())
+        if !self.traits.insert(t) {
+            return Err(Error::Rule(RuleError::DuplicateTrait(t)))
         }
-        self.traits.push(t);
         Ok(())
     }
 
@@ -264,29 +289,25 @@ impl Species {
     /// assert!(spec.exchange_trait(0, Trait::Carnivore).is_ok());
     /// assert!(spec.has_trait(Trait::Carnivore));
     /// ```
-    pub fn exchange_trait(&mut self, i: usize, t: Trait) -> Result<(), ()> {
+    pub fn exchange_trait(&mut self, i: usize, t: Trait) -> Result<(), Error> {
         match self.traits().iter().enumerate().find(|&(_, trait_type)| { *trait_type == t }) {
             Some((idx, _)) => {
                 if idx != i {
-                    return Err(());
+                    return Err(Error::Rule(RuleError::DuplicateTrait(t)));
                 }
             },
             None => {},
         }
 
-        let traits_len = self.traits.len();
-        if i < traits_len {
-            let old_trait = &self.traits.remove(i);
-            self.traits.insert(i, t);
-
-            // NOTE: Trait::FatTissue should really hold the fat.
-            if old_trait == &Trait::FatTissue {
-                self.fat = vec![];
+        match self.traits.replace(i, t) {
+            Some(old_trait) => {
+                // NOTE: Trait::FatTissue should really hold the fat.
+                if old_trait == Trait::FatTissue {
+                    self.fat = 0;
+                }
+                Ok(())
             }
-
-            Ok(())
-        } else {
-            Err(())
+            None => Err(Error::Rule(RuleError::NoTrait(i))),
         }
     }
 }
@@ -301,12 +322,18 @@ impl Species {
     /// use evolution::object::*;
     ///
     /// let mut spec = Species::default();
-    /// assert_eq!(0, spec.food().len());
+    /// assert_eq!(0, spec.food_count());
     /// spec.eat(FoodToken).expect("unable to eat");
-    /// assert_eq!(1, spec.food().len());
+    /// assert_eq!(1, spec.food_count());
     /// ```
+    pub fn food_count(&self) -> u64 {
+        self.food
+    }
+
+    /// Returns the food tokens this species has eaten.
+    #[deprecated(note = "food is only counted, use `food_count`")]
     pub fn food(&self) -> &[FoodToken] {
-        &self.food
+        super::food_tokens(self.food)
     }
 
     /// Returns true if this species can eat one or more food tokens.
@@ -322,7 +349,7 @@ impl Species {
     /// assert!(!spec.can_eat());
     /// ```
     pub fn can_eat(&self) -> bool {
-        self.population() > (self.food().len() as u64)
+        self.population() > self.food_count()
     }
 
     /// Returns the number of eats a player's species should perform for a
@@ -345,13 +372,13 @@ impl Species {
     /// use evolution::object::*;
     ///
     /// let mut spec = Species::default();
-    /// assert_eq!(0, spec.food().len());
+    /// assert_eq!(0, spec.food_count());
     /// spec.eat(FoodToken).unwrap();
-    /// assert_eq!(1, spec.food().len());
+    /// assert_eq!(1, spec.food_count());
     /// ```
     pub fn eat(&mut self, food_token: FoodToken) -> Result<(), FoodToken> {
-        if (self.food().len() as u64) < self.population() {
-            self.food.push(food_token);
+        if self.food < self.population() {
+            self.food += 1;
             Ok(())
         } else {
             Err(food_token)
@@ -360,10 +387,9 @@ impl Species {
 
     /// Return all the food from this species.
     pub fn take_food(&mut self) -> Vec<FoodToken> {
-        let mut food = vec![];
-        self.population = self.food().len() as u64;
-        swap(&mut self.food, &mut food);
-        food
+        self.population = self.food;
+        self.food = 0;
+        (0..self.population).map(|_| FoodToken).collect()
     }
 }
 
@@ -373,20 +399,26 @@ impl Species {
     ///
     /// # Examples
     ///
-    /// Calling `fat()` on a species **with** fat tissue.
+    /// Calling `fat_count()` on a species **with** fat tissue.
     ///
     /// ```
     /// use evolution::object::*;
     ///
     /// let mut species = Species::default();
-    /// assert_eq!(0, species.fat().len());
+    /// assert_eq!(0, species.fat_count());
     /// species.evolve(Trait::FatTissue).unwrap();
     /// species.grow().unwrap();
     /// species.store(vec![FoodToken]).unwrap();
-    /// assert_eq!(1, species.fat().len());
+    /// assert_eq!(1, species.fat_count());
     /// ```
+    pub fn fat_count(&self) -> u64 {
+        self.fat
+    }
+
+    /// Returns the food tokens this species has stored as fat.
+    #[deprecated(note = "fat is only counted, use `fat_count`")]
     pub fn fat(&self) -> &[FoodToken] {
-        &self.fat
+        super::food_tokens(self.fat)
     }
 
     /// Returns true if this species can store the given number of food tokens.
@@ -413,7 +445,7 @@ impl Species {
     /// ```
     pub fn can_store(&self) -> Option<u64> {
         if self.has_trait(Trait::FatTissue) {
-            let amount = self.body_size() - (self.fat().len() as u64);
+            let amount = self.body_size() - self.fat_count();
             if amount > 0 {
                 Some(amount)
             } else {
@@ -427,12 +459,12 @@ impl Species {
     /// Store the given food tokens to this species fat. This function returns
     /// an error if the species cannot store the food, either because it
     /// doesn't have the trait, or because too much food was given.
-    pub fn store(&mut self, food_tokens: Vec<FoodToken>) -> Result<(), ()> {
-        if (self.fat().len() as u64) + (food_tokens.len() as u64) <= self.body_size() {
-            self.fat.extend(food_tokens);
+    pub fn store(&mut self, food_tokens: Vec<FoodToken>) -> Result<(), Error> {
+        if self.fat + (food_tokens.len() as u64) <= self.body_size() {
+            self.fat += food_tokens.len() as u64;
             Ok(())
         } else {
-            Err(())
+            Err(Error::Rule(RuleError::FatTissue))
         }
     }
 
@@ -449,124 +481,43 @@ impl Species {
     /// spec.grow().unwrap();
     /// spec.store(vec![FoodToken]).unwrap();
     ///
-    /// assert_eq!(0, spec.food().len());
-    /// assert_eq!(1, spec.fat().len());
+    /// assert_eq!(0, spec.food_count());
+    /// assert_eq!(1, spec.fat_count());
     /// spec.digest_fat();
-    /// assert_eq!(1, spec.food().len());
-    /// assert_eq!(0, spec.fat().len());
+    /// assert_eq!(1, spec.food_count());
+    /// assert_eq!(0, spec.fat_count());
     /// ```
     pub fn digest_fat(&mut self) {
         if self.has_trait(Trait::FatTissue) {
-            let current_fat = self.fat().len() as u64;
-            let amount = cmp::min(self.population() - (self.food().len() as u64), current_fat);
-            for _ in 0..amount {
-                let token = self.fat.pop().unwrap();
-                self.food.push(token);
-            }
+            let amount = cmp::min(self.population() - self.food, self.fat);
+            self.fat -= amount;
+            self.food += amount;
         }
     }
 }
 
-/// Attacking functions.
-impl Species {
-    /// Returns true if this species can attack the given target species,
-    /// given it's neighbors. The traits of each card dictate the result.
-    ///
-    /// # Undefined Behavior
-    ///
-    /// The behavior of this function is not defined for attacking extinct
-    /// target species.
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// use evolution::object::*;
-    ///
-    /// let mut attacker = Species::default();
-    /// attacker.evolve(Trait::Carnivore).expect("unable to evolve");
-    /// let defender = Species::default();
-    /// // attacker can attack defender with no neighbors to help.
-    /// assert!(attacker.can_attack(&defender, None, None));
-    /// ```
-    pub fn can_attack(&self,
-                      target: &Species,
-                      left: Option<&Species>,
-                      right: Option<&Species>) -> bool
-    {
-        // This is the body size we use for checks for attacks.
-        let self_body_size = if self.has_trait(Trait::PackHunting) {
-            self.population() + self.body_size()
-        } else {
-            self.body_size()
-        };
-
-        let blocked_by_burrowing = {
-            target.has_trait(Trait::Burrowing) &&
-            (target.food().len() as u64) == target.population()
-        };
-
-        let blocked_by_climbing = {
-            target.has_trait(Trait::Climbing) &&
-            !self.has_trait(Trait::Climbing)
-        };
-
-        let blocked_by_hard_shell = {
-            target.has_trait(Trait::HardShell) &&
-            self_body_size < target.body_size() + HARD_SHELL_PROTECTION
-        };
-
-        let blocked_by_herding = {
-            target.has_trait(Trait::Herding) &&
-            self.population() <= target.population()
-        };
-
-        let blocked_by_symbiosis = {
-            if target.has_trait(Trait::Symbiosis) {
-                if let Some(r) = right {
-                    r.body_size() > target.body_size()
-                } else {
-                    false
-                }
-            } else {
-                false
-            }
-        };
-
-        let blocked_by_warning_call = {
-            match (self.has_trait(Trait::Ambush), left, right) {
-                (false, Some(s), _) if s.has_trait(Trait::WarningCall) => true,
-                (false, _, Some(s)) if s.has_trait(Trait::WarningCall) => true,
-                _ => false,
-            }
-        };
-
-        self.has_trait(Trait::Carnivore) &&
-        !(blocked_by_burrowing ||
-          blocked_by_climbing ||
-          blocked_by_hard_shell ||
-          blocked_by_herding ||
-          blocked_by_symbiosis ||
-          blocked_by_warning_call)
-    }
-}
-
 impl Default for Species {
     fn default() -> Self {
         Species {
             population: 1,
             body_size: 0,
-            traits: Vec::new(),
-            food: Vec::new(),
-            fat: Vec::new(),
+            traits: TraitSet::new(),
+            food: 0,
+            fat: 0,
         }
     }
 }
 
+/// Explanations of attacks.
+mod explain;
+pub use self::explain::{AttackExplanation, Factor, Party, Verdict};
+
 #[cfg(feature = "wire")]
 mod wire;
 
 #[cfg(test)]
 mod tests {
+    use error::{Error, RuleError};
     use object::*;
 
     #[test]
@@ -608,6 +559,25 @@ mod tests {
         assert!(species.evolve(Trait::FatTissue).is_err());
     }
 
+    #[test]
+    fn evolve_errors_say_why() {
+        let mut species = Species::default();
+        species.evolve(Trait::Carnivore).expect("couldn't evolve");
+
+        match species.evolve(Trait::Carnivore) {
+            Err(Error::Rule(RuleError::DuplicateTrait(Trait::Carnivore))) => {},
+            e => panic!("expected a duplicate trait error, got {:?}", e),
+        }
+
+        species.evolve(Trait::Burrowing).expect("couldn't evolve");
+        species.evolve(Trait::Ambush).expect("couldn't evolve");
+
+        match species.evolve(Trait::FatTissue) {
+            Err(Error::Rule(RuleError::MaxTraits)) => {},
+            e => panic!("expected a max traits error, got {:?}", e),
+        }
+    }
+
     #[test]
     fn breed() {
         let mut spec = Species::default();
@@ -638,16 +608,16 @@ mod tests {
         }
 
         assert_eq!(4, spec.population());
-        assert_eq!(4, spec.food().len());
+        assert_eq!(4, spec.food_count());
 
         spec.kill().unwrap();
-        assert_eq!(3, spec.food().len());
+        assert_eq!(3, spec.food_count());
 
         spec.kill().unwrap();
-        assert_eq!(2, spec.food().len());
+        assert_eq!(2, spec.food_count());
 
         spec.kill().unwrap();
-        assert_eq!(1, spec.food().len());
+        assert_eq!(1, spec.food_count());
     }
 
     #[test]
@@ -678,12 +648,12 @@ mod tests {
         spec.breed().unwrap();
         spec.breed().unwrap();
 
-        assert_eq!(0, spec.food().len());
+        assert_eq!(0, spec.food_count());
 
         assert!(spec.eat(FoodToken).is_ok());
         assert!(spec.eat(FoodToken).is_ok());
 
-        assert_eq!(2, spec.food().len());
+        assert_eq!(2, spec.food_count());
     }
 
     #[test]
@@ -693,13 +663,13 @@ mod tests {
         spec.grow().unwrap();
         spec.store(vec![FoodToken]).unwrap();
 
-        assert_eq!(0, spec.food().len());
-        assert_eq!(1, spec.fat().len());
+        assert_eq!(0, spec.food_count());
+        assert_eq!(1, spec.fat_count());
 
         spec.digest_fat();
 
-        assert_eq!(1, spec.food().len());
-        assert_eq!(0, spec.fat().len());
+        assert_eq!(1, spec.food_count());
+        assert_eq!(0, spec.fat_count());
     }
 
     #[test]
@@ -707,13 +677,13 @@ mod tests {
         let mut spec = Species::default();
         spec.evolve(Trait::FatTissue).unwrap();
 
-        assert_eq!(0, spec.food().len());
-        assert_eq!(0, spec.fat().len());
+        assert_eq!(0, spec.food_count());
+        assert_eq!(0, spec.fat_count());
 
         spec.digest_fat();
 
-        assert_eq!(0, spec.food().len());
-        assert_eq!(0, spec.fat().len());
+        assert_eq!(0, spec.food_count());
+        assert_eq!(0, spec.fat_count());
     }
 
     #[test]
@@ -721,13 +691,13 @@ mod tests {
     fn digest_fat_on_non_fat_tissue_species() {
         let mut spec = Species::default();
 
-        assert_eq!(0, spec.food().len());
-        assert_eq!(0, spec.fat().len());
+        assert_eq!(0, spec.food_count());
+        assert_eq!(0, spec.fat_count());
 
         spec.digest_fat();
 
-        assert_eq!(0, spec.food().len());
-        assert_eq!(0, spec.fat().len());
+        assert_eq!(0, spec.food_count());
+        assert_eq!(0, spec.fat_count());
     }
 
     #[test]
@@ -737,7 +707,7 @@ mod tests {
         spec.eat(FoodToken).unwrap();
 
         assert_eq!(2, spec.population());
-        assert_eq!(1, spec.food().len());
+        assert_eq!(1, spec.food_count());
 
         assert_eq!(false, spec.cull().unwrap());
         assert_eq!(1, spec.population());
@@ -748,7 +718,7 @@ mod tests {
         let mut spec = Species::default();
 
         assert_eq!(1, spec.population());
-        assert_eq!(0, spec.food().len());
+        assert_eq!(0, spec.food_count());
 
         spec.cull().unwrap();
 
@@ -762,7 +732,7 @@ mod tests {
         spec.eat(FoodToken).unwrap();
         spec.eat(FoodToken).unwrap();
 
-        assert_eq!(2, spec.food().len());
+        assert_eq!(2, spec.food_count());
         assert_eq!(vec![FoodToken, FoodToken], spec.take_food());
     }
 
@@ -844,7 +814,7 @@ mod tests {
         let mut burrower = Species::default();
         burrower.evolve(Trait::Burrowing).expect("not able to evolve");
 
-        while (burrower.food().len() as u64) < burrower.population() {
+        while burrower.food_count() < burrower.population() {
             burrower.eat(FoodToken).expect("not able to feed");
         }
 
@@ -931,6 +901,47 @@ mod tests {
         assert!(!attacker.can_attack(&victim, None, Some(&right)));
     }
 
+    #[test]
+    fn can_attack_nocturnal() {
+        let mut attacker = Species::default();
+        attacker.evolve(Trait::Carnivore).expect("not able to evolve");
+        let mut victim = Species::default();
+        victim.evolve(Trait::Nocturnal).expect("not able to evolve");
+
+        assert!(!attacker.can_attack(&victim, None, None));
+
+        attacker.evolve(Trait::Nocturnal).expect("not able to evolve");
+        assert!(attacker.can_attack(&victim, None, None));
+    }
+
+    #[test]
+    fn can_attack_defensive_herding() {
+        let mut attacker = Species::default();
+        attacker.evolve(Trait::Carnivore).expect("not able to evolve");
+        let mut herder = Species::default();
+        herder.evolve(Trait::DefensiveHerding).expect("not able to evolve");
+
+        attacker.grow().expect("failed to grow");
+        assert!(!attacker.can_attack(&herder, None, None));
+
+        attacker.grow().expect("failed to grow");
+        assert!(attacker.can_attack(&herder, None, None));
+    }
+
+    #[test]
+    fn can_attack_intelligent() {
+        let mut attacker = Species::default();
+        attacker.evolve(Trait::Carnivore).expect("not able to evolve");
+        attacker.evolve(Trait::Intelligent).expect("not able to evolve");
+        let mut victim = Species::default();
+        victim.evolve(Trait::Climbing).expect("not able to evolve");
+
+        assert!(attacker.can_attack(&victim, None, None));
+
+        victim.evolve(Trait::Nocturnal).expect("not able to evolve");
+        assert!(!attacker.can_attack(&victim, None, None));
+    }
+
     #[test]
     fn can_store_no_fat_tissue() {
         let species = Species::default();
@@ -973,12 +984,12 @@ mod tests {
         let mut species = Species::default();
         species.evolve(Trait::Ambush).unwrap();
 
-        assert!(species.fat().is_empty());
+        assert_eq!(0, species.fat_count());
 
         species.exchange_trait(0, Trait::FatTissue).unwrap();
 
         assert!(species.has_trait(Trait::FatTissue));
-        assert!(species.fat().is_empty());
+        assert_eq!(0, species.fat_count());
     }
 
     #[test]
@@ -988,12 +999,12 @@ mod tests {
         species.grow().unwrap();
         species.store(vec![FoodToken]).unwrap();
 
-        assert!(!species.fat().is_empty());
+        assert!(species.fat_count() != 0);
 
         species.exchange_trait(0, Trait::Ambush).unwrap();
 
         assert!(!species.has_trait(Trait::FatTissue));
-        assert!(species.fat().is_empty());
+        assert_eq!(0, species.fat_count());
     }
 
     #[test]
@@ -1003,12 +1014,12 @@ mod tests {
         species.grow().unwrap();
         species.store(vec![FoodToken]).unwrap();
 
-        assert!(!species.fat().is_empty());
+        assert!(species.fat_count() != 0);
 
         species.exchange_trait(0, Trait::FatTissue).unwrap();
 
         assert!(species.has_trait(Trait::FatTissue));
-        assert!(species.fat().is_empty());
+        assert_eq!(0, species.fat_count());
     }
 
     #[test]
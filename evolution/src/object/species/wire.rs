@@ -1,55 +1,51 @@
-use std::collections::HashSet;
 use evolution_wire as wire;
 use object::*;
+use object::traits::TraitSet;
 
 impl wire::FromWire<wire::Species> for Species {
-    fn from_wire(wire: wire::Species) -> Result<Self, ()> {
-        let mut traits = Vec::new();
+    fn from_wire(wire: wire::Species) -> Result<Self, wire::Error> {
+        let mut traits = TraitSet::new();
+        let mut distinct = true;
         for t in wire.traits.iter() {
-            traits.push(try!(Trait::from_wire(*t)))
+            distinct &= traits.insert(try!(Trait::from_wire(*t)));
         }
 
-        let fat = match wire.fat_food {
-            Some(n) => (0..*n).into_iter().map(|_| FoodToken).collect(),
-            None => vec![],
-        };
-
         let species = Species {
             population: *wire.population,
             body_size: *wire.body,
             traits: traits,
-            food: (0..*wire.food).into_iter().map(|_| FoodToken).collect(),
-            fat: fat,
+            food: *wire.food,
+            fat: wire.fat_food.map_or(0, |n| *n),
         };
 
         // Check internal invariants.
         if species.population() <= MAX_POPULATION &&
            species.body_size() <= MAX_BODY_SIZE &&
            species.traits.len() <= MAX_TRAITS &&
-           (species.food().len() as u64) <= species.population() &&
-           species.traits().len() == species.traits().iter().collect::<HashSet<_>>().len() &&
-           (species.fat().len() as u64) <= species.body_size()
+           species.food_count() <= species.population() &&
+           distinct &&
+           species.fat_count() <= species.body_size()
         {
             Ok(species)
         } else {
-            Err(())
+            Err(wire::Error::invalid("species breaks the species invariants"))
         }
     }
 }
 
 impl wire::ToWire<wire::Species> for Species {
     fn to_wire(&self) -> wire::Species {
-        let fat_food = if self.fat().is_empty() {
+        let fat_food = if self.fat_count() == 0 {
             None
         } else {
-            Some((self.fat().len() as u64).to_wire())
+            Some(self.fat_count().to_wire())
         };
 
         wire::Species {
-            food: (self.food().len() as u64).to_wire(),
+            food: self.food_count().to_wire(),
             body: self.body_size().to_wire(),
             population: self.population().to_wire(),
-            traits: self.traits.to_wire(),
+            traits: self.traits().to_vec().to_wire(),
             fat_food: fat_food,
         }
     }
@@ -85,7 +81,7 @@ mod tests {
             fat_food: None,
         };
         let species = Species::from_wire(wire).unwrap();
-        assert!(species.fat().is_empty());
+        assert_eq!(0, species.fat_count());
     }
 
     #[test]
@@ -98,11 +94,11 @@ mod tests {
            fat_food: Some(wire::Nat::new(3).unwrap()),
         };
         let species = Species::from_wire(wire).unwrap();
-        assert_eq!(1, species.food().len());
+        assert_eq!(1, species.food_count());
         assert_eq!(3, species.body_size());
         assert_eq!(2, species.population());
         assert!(species.has_trait(Trait::FatTissue));
-        assert_eq!(3, species.fat().len());
+        assert_eq!(3, species.fat_count());
     }
 
     #[test]
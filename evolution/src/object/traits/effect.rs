@@ -0,0 +1,144 @@
+use game::context::{Context, Outcome, Species as ContextSpecies};
+use object::{Species, Trait};
+
+/// An attack, as seen by the effects of the traits involved.
+pub struct Attack<'a> {
+    /// The attacking carnivore.
+    pub attacker: &'a Species,
+    /// The species being attacked.
+    pub target: &'a Species,
+    /// The target's left neighbor.
+    pub left: Option<&'a Species>,
+    /// The target's right neighbor.
+    pub right: Option<&'a Species>,
+}
+
+impl<'a> Attack<'a> {
+    pub fn new(attacker: &'a Species,
+               target: &'a Species,
+               left: Option<&'a Species>,
+               right: Option<&'a Species>) -> Self
+    {
+        Attack {
+            attacker: attacker,
+            target: target,
+            left: left,
+            right: right,
+        }
+    }
+}
+
+/// The effect a trait has on the game, as hooks called wherever traits act.
+///
+/// Every hook does nothing by default, so an effect only implements the
+/// hooks it needs. The base game traits are still acted on directly by the
+/// game, and have no hooks, while the expansion traits are implemented
+/// entirely by them.
+pub trait Effect {
+    /// Returns true if this trait of the target prevents the attack.
+    fn defends(&self, _attack: &Attack) -> bool {
+        false
+    }
+
+    /// Returns the reason this trait of the target prevents the attack, for
+    /// when it does.
+    fn defence(&self, _attack: &Attack) -> String {
+        "the target's trait defends it".to_string()
+    }
+
+    /// Returns true if this trait of the attacker lets it attack through a
+    /// single trait which would otherwise prevent the attack.
+    fn outwits(&self, _attack: &Attack) -> bool {
+        false
+    }
+
+    /// Called for each species with this trait when the food cards are
+    /// revealed, after the Fertile and Long Neck species, returning what it
+    /// changed.
+    fn reveal(&self, _species: &mut ContextSpecies) -> Outcome {
+        Outcome::new()
+    }
+}
+
+pub struct Base;
+
+impl Effect for Base {}
+
+pub struct Intelligent;
+
+impl Effect for Intelligent {
+    fn outwits(&self, _attack: &Attack) -> bool {
+        true
+    }
+}
+
+pub struct Nocturnal;
+
+impl Effect for Nocturnal {
+    fn defends(&self, attack: &Attack) -> bool {
+        !attack.attacker.has_trait(Trait::Nocturnal)
+    }
+
+    fn defence(&self, _attack: &Attack) -> String {
+        "the target is nocturnal, and the attacker isn't".to_string()
+    }
+}
+
+pub struct DefensiveHerding;
+
+impl Effect for DefensiveHerding {
+    fn defends(&self, attack: &Attack) -> bool {
+        attack.attacker.body_size() <= attack.target.population()
+    }
+
+    fn defence(&self, attack: &Attack) -> String {
+        format!("the attacker's body size {} is no larger than the target's population {}",
+                attack.attacker.body_size(), attack.target.population())
+    }
+}
+
+pub struct Migratory;
+
+impl Effect for Migratory {
+    fn reveal(&self, species: &mut ContextSpecies) -> Outcome {
+        let food = species.context().board().food_count();
+        let max_population = species.context().rules().max_population;
+        if food > species.population() {
+            species.breed_up_to(max_population).ok();
+        }
+        Outcome::new()
+    }
+}
+
+pub struct MassExtinction;
+
+impl Effect for MassExtinction {
+    fn reveal(&self, species: &mut ContextSpecies) -> Outcome {
+        let indices = species.indices();
+        let population = species.population();
+        let mut larger = vec![];
+        for (player_idx, player) in species.context().players().iter().enumerate() {
+            for (species_idx, other) in player.domain().iter().enumerate() {
+                if (player_idx, species_idx) != indices && other.population() > population {
+                    larger.push((player_idx, species_idx));
+                }
+            }
+        }
+        // A larger population is at least two, so no species goes extinct
+        // here.
+        let mut outcome = Outcome::new();
+        for indices in larger {
+            if let Ok(killed) = ContextSpecies::new(species.context_mut(), indices).kill() {
+                outcome.append(killed);
+            }
+        }
+        outcome
+    }
+}
+
+pub static BASE: Base = Base;
+pub static INTELLIGENT: Intelligent = Intelligent;
+pub static NOCTURNAL: Nocturnal = Nocturnal;
+pub static DEFENSIVE_HERDING: DefensiveHerding = DefensiveHerding;
+pub static MIGRATORY: Migratory = Migratory;
+pub static MASS_EXTINCTION: MassExtinction = MassExtinction;
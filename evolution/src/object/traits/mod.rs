@@ -2,6 +2,10 @@ use std::cmp::Ordering;
 
 /// A trait which a trait card may have. These are modifiers for
 /// how a species board acts.
+///
+/// The traits from `Intelligent` on are expansion traits, which are only in
+/// decks with expansion cards. The game acts on them through their `Effect`,
+/// see `Trait::effect`.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub enum Trait {
     /// Carnivore must attack to eat during the feeding stage.
@@ -49,14 +53,85 @@ pub enum Trait {
     /// neighboring species unless the attacker has the Ambush
     /// property.
     WarningCall,
+    /// Intelligent lets a Carnivore attack despite one trait which would
+    /// otherwise prevent the attack.
+    Intelligent,
+    /// Nocturnal prevents an attack unless the Carnivore is also
+    /// Nocturnal.
+    Nocturnal,
+    /// Defensive Herding prevents an attack from a Carnivore whose body
+    /// size is no larger than this species' population.
+    DefensiveHerding,
+    /// Migratory adds one animal to the population when the food cards
+    /// are revealed, if there is more food than its population.
+    Migratory,
+    /// Mass Extinction kills one animal of every other species with a
+    /// larger population when the food cards are revealed.
+    MassExtinction,
+}
+
+impl Trait {
+    /// Returns the hooks for this trait's effect on the game.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use evolution::object::*;
+    ///
+    /// let mut attacker = Species::default();
+    /// attacker.evolve(Trait::Carnivore).unwrap();
+    /// let mut target = Species::default();
+    /// target.evolve(Trait::Nocturnal).unwrap();
+    ///
+    /// let attack = Attack::new(&attacker, &target, None, None);
+    /// assert!(Trait::Nocturnal.effect().defends(&attack));
+    /// assert!(!Trait::Climbing.effect().defends(&attack));
+    /// ```
+    pub fn effect(&self) -> &'static Effect {
+        match *self {
+            Trait::Intelligent => &effect::INTELLIGENT,
+            Trait::Nocturnal => &effect::NOCTURNAL,
+            Trait::DefensiveHerding => &effect::DEFENSIVE_HERDING,
+            Trait::Migratory => &effect::MIGRATORY,
+            Trait::MassExtinction => &effect::MASS_EXTINCTION,
+            _ => &effect::BASE,
+        }
+    }
+}
+
+impl Trait {
+    /// Returns this trait's place among the traits ordered by name, which is
+    /// the same order as their wire strings.
+    fn rank(&self) -> u8 {
+        match *self {
+            Trait::Ambush => 0,
+            Trait::Burrowing => 1,
+            Trait::Carnivore => 2,
+            Trait::Climbing => 3,
+            Trait::Cooperation => 4,
+            Trait::DefensiveHerding => 5,
+            Trait::FatTissue => 6,
+            Trait::Fertile => 7,
+            Trait::Foraging => 8,
+            Trait::HardShell => 9,
+            Trait::Herding => 10,
+            Trait::Horns => 11,
+            Trait::Intelligent => 12,
+            Trait::LongNeck => 13,
+            Trait::MassExtinction => 14,
+            Trait::Migratory => 15,
+            Trait::Nocturnal => 16,
+            Trait::PackHunting => 17,
+            Trait::Scavenger => 18,
+            Trait::Symbiosis => 19,
+            Trait::WarningCall => 20,
+        }
+    }
 }
 
 impl Ord for Trait {
     fn cmp(&self, other: &Trait) -> Ordering {
-        // NOTE: This is "ok" because the strings `Debug` will generate are
-        // ordered lexicographically the same as the wire string
-        // representation.
-        format!("{:?}", self).cmp(&format!("{:?}", other))
+        self.rank().cmp(&other.rank())
     }
 }
 
@@ -66,6 +141,12 @@ impl PartialOrd for Trait {
     }
 }
 
+mod effect;
+pub use self::effect::{Effect, Attack};
+
+mod set;
+pub use self::set::{TraitSet, TRAIT_COUNT};
+
 #[cfg(feature = "wire")]
 mod wire;
 
@@ -80,4 +161,21 @@ mod tests {
         assert!(Trait::FatTissue >= Trait::FatTissue);
         assert!(Trait::FatTissue <= Trait::FatTissue);
     }
+
+    #[test]
+    fn ordered_by_name() {
+        let mut traits = vec![Trait::Carnivore, Trait::Ambush, Trait::Burrowing, Trait::Climbing,
+                              Trait::Cooperation, Trait::FatTissue, Trait::Fertile,
+                              Trait::Foraging, Trait::HardShell, Trait::Herding, Trait::Horns,
+                              Trait::LongNeck, Trait::PackHunting, Trait::Scavenger,
+                              Trait::Symbiosis, Trait::WarningCall, Trait::Intelligent,
+                              Trait::Nocturnal, Trait::DefensiveHerding, Trait::Migratory,
+                              Trait::MassExtinction];
+        let mut names = traits.iter().map(|t| format!("{:?}", t)).collect::<Vec<_>>();
+
+        traits.sort();
+        names.sort();
+
+        assert_eq!(names, traits.iter().map(|t| format!("{:?}", t)).collect::<Vec<_>>());
+    }
 }
@@ -0,0 +1,136 @@
+use std::fmt;
+use object::Trait;
+
+/// The number of different traits.
+pub const TRAIT_COUNT: usize = Trait::MassExtinction as usize + 1;
+
+/// A set of different traits, which remembers the order they were added in.
+///
+/// Checking for a trait tests a single bit, and the set is `Copy`, so a
+/// species holding one is copied without allocating.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TraitSet {
+    bits: u32,
+    len: usize,
+    // Slots past `len` always hold `Trait::Carnivore`, so equal sets are
+    // equal all the way through.
+    order: [Trait; TRAIT_COUNT],
+}
+
+impl TraitSet {
+    /// Creates an empty set.
+    pub fn new() -> Self {
+        TraitSet {
+            bits: 0,
+            len: 0,
+            order: [Trait::Carnivore; TRAIT_COUNT],
+        }
+    }
+
+    /// Returns the number of traits in this set.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if this set has no traits.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns `true` if this set has the given trait.
+    pub fn contains(&self, t: Trait) -> bool {
+        self.bits & bit(t) != 0
+    }
+
+    /// Adds the trait after all the others, returning `false` if it was
+    /// already in this set.
+    pub fn insert(&mut self, t: Trait) -> bool {
+        if self.contains(t) {
+            return false
+        }
+        self.bits |= bit(t);
+        self.order[self.len] = t;
+        self.len += 1;
+        true
+    }
+
+    /// Puts the trait in the place of the trait at the given index, returning
+    /// the trait it replaced, or `None` if there is no trait at the index.
+    /// The trait must not be in this set at any other index.
+    pub fn replace(&mut self, i: usize, t: Trait) -> Option<Trait> {
+        if i >= self.len {
+            return None
+        }
+        let old = self.order[i];
+        self.bits &= !bit(old);
+        self.bits |= bit(t);
+        self.order[i] = t;
+        Some(old)
+    }
+
+    /// Returns the traits in the order they were added.
+    pub fn as_slice(&self) -> &[Trait] {
+        &self.order[..self.len]
+    }
+}
+
+impl Default for TraitSet {
+    fn default() -> Self {
+        TraitSet::new()
+    }
+}
+
+impl fmt::Debug for TraitSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.as_slice()).finish()
+    }
+}
+
+/// Returns the bit standing for the trait in a set.
+fn bit(t: Trait) -> u32 {
+    1 << (t as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use object::*;
+    use super::TraitSet;
+
+    #[test]
+    fn insert_keeps_order() {
+        let mut set = TraitSet::new();
+
+        assert!(set.insert(Trait::Scavenger));
+        assert!(set.insert(Trait::Ambush));
+        assert!(!set.insert(Trait::Scavenger));
+
+        assert_eq!(&[Trait::Scavenger, Trait::Ambush], set.as_slice());
+        assert!(set.contains(Trait::Ambush));
+        assert!(!set.contains(Trait::Carnivore));
+    }
+
+    #[test]
+    fn replace() {
+        let mut set = TraitSet::new();
+        set.insert(Trait::Carnivore);
+        set.insert(Trait::Horns);
+
+        assert_eq!(Some(Trait::Carnivore), set.replace(0, Trait::MassExtinction));
+        assert_eq!(None, set.replace(2, Trait::Climbing));
+
+        assert_eq!(&[Trait::MassExtinction, Trait::Horns], set.as_slice());
+        assert!(!set.contains(Trait::Carnivore));
+        assert!(set.contains(Trait::MassExtinction));
+    }
+
+    #[test]
+    fn equal_sets_compare_equal() {
+        let mut a = TraitSet::new();
+        a.insert(Trait::Burrowing);
+        let mut b = TraitSet::new();
+        b.insert(Trait::Carnivore);
+        b.replace(0, Trait::Burrowing);
+
+        assert_eq!(a, b);
+    }
+}
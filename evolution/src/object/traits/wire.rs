@@ -2,24 +2,29 @@ use evolution_wire as wire;
 use object::*;
 
 impl wire::FromWire<wire::Trait> for Trait {
-    fn from_wire(wire: wire::Trait) -> Result<Self, ()> {
+    fn from_wire(wire: wire::Trait) -> Result<Self, wire::Error> {
         Ok(match wire {
-            wire::Trait::Carnivore   => Trait::Carnivore,
-            wire::Trait::Ambush      => Trait::Ambush,
-            wire::Trait::Burrowing   => Trait::Burrowing,
-            wire::Trait::Climbing    => Trait::Climbing,
-            wire::Trait::Cooperation => Trait::Cooperation,
-            wire::Trait::FatTissue   => Trait::FatTissue,
-            wire::Trait::Fertile     => Trait::Fertile,
-            wire::Trait::Foraging    => Trait::Foraging,
-            wire::Trait::HardShell   => Trait::HardShell,
-            wire::Trait::Herding     => Trait::Herding,
-            wire::Trait::Horns       => Trait::Horns,
-            wire::Trait::LongNeck    => Trait::LongNeck,
-            wire::Trait::PackHunting => Trait::PackHunting,
-            wire::Trait::Scavenger   => Trait::Scavenger,
-            wire::Trait::Symbiosis   => Trait::Symbiosis,
-            wire::Trait::WarningCall => Trait::WarningCall,
+            wire::Trait::Carnivore        => Trait::Carnivore,
+            wire::Trait::Ambush           => Trait::Ambush,
+            wire::Trait::Burrowing        => Trait::Burrowing,
+            wire::Trait::Climbing         => Trait::Climbing,
+            wire::Trait::Cooperation      => Trait::Cooperation,
+            wire::Trait::FatTissue        => Trait::FatTissue,
+            wire::Trait::Fertile          => Trait::Fertile,
+            wire::Trait::Foraging         => Trait::Foraging,
+            wire::Trait::HardShell        => Trait::HardShell,
+            wire::Trait::Herding          => Trait::Herding,
+            wire::Trait::Horns            => Trait::Horns,
+            wire::Trait::LongNeck         => Trait::LongNeck,
+            wire::Trait::PackHunting      => Trait::PackHunting,
+            wire::Trait::Scavenger        => Trait::Scavenger,
+            wire::Trait::Symbiosis        => Trait::Symbiosis,
+            wire::Trait::WarningCall      => Trait::WarningCall,
+            wire::Trait::Intelligent      => Trait::Intelligent,
+            wire::Trait::Nocturnal        => Trait::Nocturnal,
+            wire::Trait::DefensiveHerding => Trait::DefensiveHerding,
+            wire::Trait::Migratory        => Trait::Migratory,
+            wire::Trait::MassExtinction   => Trait::MassExtinction,
         })
     }
 }
@@ -27,22 +32,27 @@ impl wire::FromWire<wire::Trait> for Trait {
 impl wire::ToWire<wire::Trait> for Trait {
     fn to_wire(&self) -> wire::Trait {
         match *self {
-            Trait::Carnivore   => wire::Trait::Carnivore,
-            Trait::Ambush      => wire::Trait::Ambush,
-            Trait::Burrowing   => wire::Trait::Burrowing,
-            Trait::Climbing    => wire::Trait::Climbing,
-            Trait::Cooperation => wire::Trait::Cooperation,
-            Trait::FatTissue   => wire::Trait::FatTissue,
-            Trait::Fertile     => wire::Trait::Fertile,
-            Trait::Foraging    => wire::Trait::Foraging,
-            Trait::HardShell   => wire::Trait::HardShell,
-            Trait::Herding     => wire::Trait::Herding,
-            Trait::Horns       => wire::Trait::Horns,
-            Trait::LongNeck    => wire::Trait::LongNeck,
-            Trait::PackHunting => wire::Trait::PackHunting,
-            Trait::Scavenger   => wire::Trait::Scavenger,
-            Trait::Symbiosis   => wire::Trait::Symbiosis,
-            Trait::WarningCall => wire::Trait::WarningCall,
+            Trait::Carnivore        => wire::Trait::Carnivore,
+            Trait::Ambush           => wire::Trait::Ambush,
+            Trait::Burrowing        => wire::Trait::Burrowing,
+            Trait::Climbing         => wire::Trait::Climbing,
+            Trait::Cooperation      => wire::Trait::Cooperation,
+            Trait::FatTissue        => wire::Trait::FatTissue,
+            Trait::Fertile          => wire::Trait::Fertile,
+            Trait::Foraging         => wire::Trait::Foraging,
+            Trait::HardShell        => wire::Trait::HardShell,
+            Trait::Herding          => wire::Trait::Herding,
+            Trait::Horns            => wire::Trait::Horns,
+            Trait::LongNeck         => wire::Trait::LongNeck,
+            Trait::PackHunting      => wire::Trait::PackHunting,
+            Trait::Scavenger        => wire::Trait::Scavenger,
+            Trait::Symbiosis        => wire::Trait::Symbiosis,
+            Trait::WarningCall      => wire::Trait::WarningCall,
+            Trait::Intelligent      => wire::Trait::Intelligent,
+            Trait::Nocturnal        => wire::Trait::Nocturnal,
+            Trait::DefensiveHerding => wire::Trait::DefensiveHerding,
+            Trait::Migratory        => wire::Trait::Migratory,
+            Trait::MassExtinction   => wire::Trait::MassExtinction,
         }
     }
 }
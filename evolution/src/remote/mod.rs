@@ -0,0 +1,38 @@
+use std::fmt;
+
+/// The phase of the remote protocol a client is in, which decides the
+/// messages it may get next.
+///
+/// A game starts `AwaitingStart`. Each round starts with a start message,
+/// and is followed by one request for the player's action, then any number
+/// of requests for the player's feeding. The next round, or the next game
+/// in a lobby, starts with another start message. The server closes the
+/// connection between rounds to end the game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    /// Expects a start message.
+    AwaitingStart,
+    /// Expects a request for the player's action.
+    AwaitingAction,
+    /// Expects a request for the player's feeding, a start message, or the
+    /// end of the game.
+    Feeding,
+    /// The game ended, no more messages are expected.
+    Over,
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Phase::AwaitingStart => write!(f, "awaiting the start of a round"),
+            Phase::AwaitingAction => write!(f, "awaiting an action request"),
+            Phase::Feeding => write!(f, "feeding"),
+            Phase::Over => write!(f, "the game is over"),
+        }
+    }
+}
+
+#[cfg(feature = "wire")]
+mod session;
+#[cfg(feature = "wire")]
+pub use self::session::{ClientSession, Message, Reply};
@@ -0,0 +1,309 @@
+use serde_json::{self as json, Value};
+use evolution_wire::{self as wire, ToWire, FromWire, Channel};
+use error::{Error, ChoiceError, ProtocolError};
+use interact::*;
+use remote::Phase;
+
+/// A message from the server to a client.
+#[derive(Debug)]
+pub enum Message {
+    /// The start of a round, with the player's state and new cards.
+    Start(wire::remote::Start),
+    /// A request for the player's action, with the boards of the players
+    /// before, and after them in turn order.
+    Choose(wire::remote::LOB, wire::remote::LOB),
+    /// A request for the player's feeding.
+    Feed(wire::remote::State),
+}
+
+impl Message {
+    /// Reads a message from a JSON value, which must be exactly one of the
+    /// kinds of message.
+    pub fn from_value(value: Value) -> Result<Self, Error> {
+        let mut messages = vec![];
+        if let Ok(start) = json::from_value(value.clone()) {
+            messages.push(Message::Start(start));
+        }
+        if let Ok((before, after)) = json::from_value(value.clone()) {
+            messages.push(Message::Choose(before, after));
+        }
+        if let Ok(state) = json::from_value(value.clone()) {
+            messages.push(Message::Feed(state));
+        }
+        if messages.len() == 1 {
+            Ok(messages.remove(0))
+        } else {
+            Err(Error::Decode(format!("not a remote message: {:?}", value)))
+        }
+    }
+
+    /// Returns the name of this kind of message.
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Message::Start(_) => "start",
+            Message::Choose(..) => "choose",
+            Message::Feed(_) => "feed",
+        }
+    }
+}
+
+/// A client's reply to a message.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Reply {
+    Action(ActionChoice),
+    Feed(FeedChoice),
+}
+
+impl Phase {
+    /// Returns the phase after getting the message in this phase.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Protocol` error if the message can't come
+    /// in this phase.
+    pub fn next(&self, message: &Message) -> Result<Phase, Error> {
+        match (*self, message) {
+            (Phase::AwaitingStart, &Message::Start(_)) |
+            (Phase::Feeding, &Message::Start(_)) => Ok(Phase::AwaitingAction),
+            (Phase::AwaitingAction, &Message::Choose(..)) |
+            (Phase::Feeding, &Message::Feed(_)) => Ok(Phase::Feeding),
+            (phase, message) => {
+                Err(Error::Protocol(ProtocolError::OutOfOrder(phase, message.name())))
+            },
+        }
+    }
+}
+
+/// The client's side of a game played over the remote protocol, following
+/// the `Phase` of the protocol, and asking a chooser for the replies.
+///
+/// # Examples
+///
+/// ```rust
+/// use evolution::remote::{ClientSession, Phase};
+/// use evolution::silly::Silly;
+///
+/// let mut session = ClientSession::new(Silly);
+/// assert_eq!(Phase::AwaitingStart, session.phase());
+/// // The server closing the connection before the game is an error.
+/// assert!(session.close().is_err());
+/// ```
+#[derive(Debug)]
+pub struct ClientSession<C: Chooser> {
+    chooser: C,
+    phase: Phase,
+    start: Option<wire::remote::Start>,
+}
+
+impl<C: Chooser> ClientSession<C> {
+    /// Creates a new session awaiting the start of a game, which the
+    /// chooser plays.
+    pub fn new(chooser: C) -> Self {
+        ClientSession {
+            chooser: chooser,
+            phase: Phase::AwaitingStart,
+            start: None,
+        }
+    }
+
+    /// Returns the phase of the protocol this session is in.
+    pub fn phase(&self) -> Phase {
+        self.phase
+    }
+
+    /// Returns the chooser playing this session.
+    pub fn chooser(&self) -> &C {
+        &self.chooser
+    }
+
+    /// Consumes this session, returning it's chooser.
+    pub fn into_chooser(self) -> C {
+        self.chooser
+    }
+
+    /// Handles a message from the server, returning the reply to send back,
+    /// if the message needs one. A start message is given to the chooser's
+    /// `start`, and requests are given to it's `choose`. A chooser without
+    /// a feeding abstains.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Protocol` error, leaving the session as it
+    /// was, if the message can't come in the current phase. An invalid
+    /// message, a failed choice or a missing action is also an error.
+    pub fn handle(&mut self, message: Message) -> Result<Option<Reply>, Error> {
+        let next = try!(self.phase.next(&message));
+        let reply = match message {
+            Message::Start(start) => {
+                let observation = try!(DealObservation::from_wire(start.clone()));
+                self.chooser.start(&observation);
+                self.start = Some(start);
+                None
+            },
+            Message::Choose(before, after) => {
+                // The start is kept until the action is chosen, so the
+                // session is left as it was if choosing fails.
+                let start = self.start.clone().expect("awaiting an action without a start");
+                let observation = try!(ActionObservation::from_wire((start, (before, after))));
+                let choice = match try!(self.chooser.choose(&observation)) {
+                    Some(choice) => choice,
+                    None => return Err(Error::Choice(ChoiceError::Missing)),
+                };
+                self.start = None;
+                Some(Reply::Action(choice))
+            },
+            Message::Feed(state) => {
+                let observation = try!(FeedObservation::from_wire(state));
+                let choice = try!(self.chooser.choose(&observation));
+                Some(Reply::Feed(choice.unwrap_or(FeedChoice::Abstain)))
+            },
+        };
+        self.phase = next;
+        Ok(reply)
+    }
+
+    /// Handles the server closing the connection, which ends the game
+    /// between rounds.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Protocol` error if the connection closed
+    /// before the game, or part way through a round.
+    pub fn close(&mut self) -> Result<(), Error> {
+        match self.phase {
+            Phase::Feeding | Phase::Over => {
+                self.phase = Phase::Over;
+                Ok(())
+            },
+            phase => Err(Error::Protocol(ProtocolError::Closed(phase))),
+        }
+    }
+
+    /// Plays on the channel, replying to each message from the server until
+    /// it closes the connection at the end of the game. A lobby which keeps
+    /// the connection open plays more games on it.
+    pub fn run(&mut self, channel: &mut Channel) -> Result<(), Error> {
+        while self.phase != Phase::Over {
+            let value = match channel.recv::<Value>() {
+                Ok(value) => value,
+                Err(wire::Error::Closed) => return self.close(),
+                Err(e) => return Err(Error::from(e)),
+            };
+            let message = try!(Message::from_value(value));
+            debug!("{:?} got {} message", self.phase, message.name());
+            match try!(self.handle(message)) {
+                Some(Reply::Action(choice)) => {
+                    try!(channel.send(&ToWire::<wire::remote::Action4>::to_wire(&choice)));
+                },
+                Some(Reply::Feed(choice)) => {
+                    try!(channel.send(&ToWire::<wire::FeedChoice>::to_wire(&choice)));
+                },
+                None => {},
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json as json;
+    use error::{Error, ProtocolError};
+    use interact::*;
+    use remote::Phase;
+    use silly::Silly;
+    use super::*;
+
+    const SPECIES: &'static str = r#"[["food", 0], ["body", 1], ["population", 1], ["traits", []]]"#;
+
+    fn message(s: &str) -> Message {
+        Message::from_value(json::from_str(s).unwrap()).unwrap()
+    }
+
+    fn start() -> Message {
+        message(&format!(r#"[3, 0, [{}], [[1, "carnivore"], [2, "burrowing"], [0, "horns"], [3, "foraging"]]]"#, SPECIES))
+    }
+
+    fn choose() -> Message {
+        message(&format!("[[[{}]], []]", SPECIES))
+    }
+
+    fn feed() -> Message {
+        message(&format!("[0, [{}], [], 3, [[{}]]]", SPECIES, SPECIES))
+    }
+
+    fn is_out_of_order(result: Result<Option<Reply>, Error>, phase: Phase, name: &str) -> bool {
+        match result {
+            Err(Error::Protocol(ProtocolError::OutOfOrder(p, n))) => p == phase && n == name,
+            _ => false,
+        }
+    }
+
+    #[test]
+    fn from_value() {
+        assert_eq!("start", start().name());
+        assert_eq!("choose", choose().name());
+        assert_eq!("feed", feed().name());
+        assert!(Message::from_value(json::from_str("[1, 2]").unwrap()).is_err());
+    }
+
+    #[test]
+    fn plays_rounds() {
+        let mut session = ClientSession::new(Silly);
+
+        assert_eq!(None, session.handle(start()).unwrap());
+        assert_eq!(Phase::AwaitingAction, session.phase());
+        match session.handle(choose()).unwrap() {
+            Some(Reply::Action(_)) => {},
+            r => panic!("expected an action, got {:?}", r),
+        }
+        assert_eq!(Phase::Feeding, session.phase());
+        assert_eq!(Some(Reply::Feed(FeedChoice::Feed(0))), session.handle(feed()).unwrap());
+        assert_eq!(Phase::Feeding, session.phase());
+        assert_eq!(None, session.handle(start()).unwrap());
+        assert_eq!(Phase::AwaitingAction, session.phase());
+    }
+
+    #[test]
+    fn rejects_out_of_order() {
+        let mut session = ClientSession::new(Silly);
+
+        assert!(is_out_of_order(session.handle(choose()), Phase::AwaitingStart, "choose"));
+        assert!(is_out_of_order(session.handle(feed()), Phase::AwaitingStart, "feed"));
+        session.handle(start()).unwrap();
+        assert!(is_out_of_order(session.handle(start()), Phase::AwaitingAction, "start"));
+        assert!(is_out_of_order(session.handle(feed()), Phase::AwaitingAction, "feed"));
+        session.handle(choose()).unwrap();
+        assert!(is_out_of_order(session.handle(choose()), Phase::Feeding, "choose"));
+        assert_eq!(Phase::Feeding, session.phase());
+    }
+
+    #[test]
+    fn failed_action_leaves_session_as_it_was() {
+        let mut session = ClientSession::new(Silly);
+        session.handle(start()).unwrap();
+        let invalid = message(r#"[[[[["food", 0], ["body", 1], ["population", 1], ["traits", ["horns", "horns"]]]]], []]"#);
+
+        assert!(session.handle(invalid).is_err());
+        assert_eq!(Phase::AwaitingAction, session.phase());
+        match session.handle(choose()).unwrap() {
+            Some(Reply::Action(_)) => {},
+            r => panic!("expected an action, got {:?}", r),
+        }
+    }
+
+    #[test]
+    fn closing_ends_the_game_between_rounds() {
+        let mut session = ClientSession::new(Silly);
+        session.handle(start()).unwrap();
+
+        assert!(match session.close() {
+            Err(Error::Protocol(ProtocolError::Closed(Phase::AwaitingAction))) => true,
+            _ => false,
+        });
+        session.handle(choose()).unwrap();
+        session.close().unwrap();
+        assert_eq!(Phase::Over, session.phase());
+        assert!(is_out_of_order(session.handle(start()), Phase::Over, "start"));
+    }
+}
@@ -1,8 +1,9 @@
+use error::Error;
 use interact::*;
 use silly::*;
 
 impl Choose<ActionObservation, ActionChoice> for Silly {
-    fn choose(&mut self, observation: &ActionObservation) -> Result<Option<ActionChoice>, ()> {
+    fn choose(&mut self, observation: &ActionObservation) -> Result<Option<ActionChoice>, Error> {
         let current_player = &observation.current_player;
         let mut cards = current_player.hand().clone().into_iter().enumerate().collect::<Vec<_>>();
         cards.sort_by(|lhs, rhs| {
@@ -97,6 +98,7 @@ mod tests {
             current_player: game.current_player().clone(),
             before: vec![],
             after: vec![],
+            rules: RulesConfig::default(),
         };
         let action_choice = Silly.choose(&action_observation).unwrap().unwrap();
 
@@ -1,8 +1,9 @@
+use error::Error;
 use interact::*;
 use silly::*;
 
 impl Choose<FeedObservation, FeedChoice> for Silly {
-    fn choose(&mut self, observation: &FeedObservation) -> Result<Option<FeedChoice>, ()> {
+    fn choose(&mut self, observation: &FeedObservation) -> Result<Option<FeedChoice>, Error> {
         let current_player_idx = observation.current_player_index();
         let all_choices = observation.choices();
         let considered_choices = all_choices.into_iter().filter(|&c| {
@@ -21,7 +22,7 @@ impl Silly {
     /// Given a vector of choices, return the lowest ordered one (best)
     /// based on the implementation of `RankedFeedChoice`.
     fn best_feed_choice(observation: &FeedObservation,
-                        choices: Vec<FeedChoice>) -> Result<Option<FeedChoice>, ()>
+                        choices: Vec<FeedChoice>) -> Result<Option<FeedChoice>, Error>
     {
         if choices.len() > 0 {
             let mut ranked: Vec<RankedFeedChoice> =
@@ -16,7 +16,7 @@ impl<'a> Ord for RankedSpecies<'a> {
     fn cmp(&self, other: &RankedSpecies<'a>) -> Ordering {
         match other.population().cmp(&self.population()) {
             Ordering::Equal => {
-                match (other.food().len() as u64).cmp(&(self.food().len() as u64)) {
+                match other.food_count().cmp(&self.food_count()) {
                     Ordering::Equal => {
                         other.body_size().cmp(&self.body_size())
                     },
@@ -0,0 +1,401 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use rand::{Rng, SeedableRng};
+use rand::isaac::Isaac64Rng;
+use serde_json::{self as json, Value};
+use error::{Error, RuleError};
+use game::*;
+use interact::*;
+use object::Trait;
+use strategies::Strategy;
+
+/// Many headless games between the built in strategies, collecting
+/// statistics about how they played.
+///
+/// The named strategies take turns filling the seats of each game, starting
+/// one seat further along every game, so no strategy always plays first.
+/// Each game is given it's own seed derived from the seed of the simulation.
+///
+/// # Examples
+///
+/// ```rust
+/// use evolution::game::RulesConfig;
+/// use evolution::simulation::Simulation;
+///
+/// let names = vec!["silly".into(), "greedy".into()];
+/// let simulation = Simulation::new(names, 4, 10, 1, RulesConfig::default()).unwrap();
+/// let statistics = simulation.run().unwrap();
+/// assert_eq!(10, statistics.games);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Simulation {
+    names: Vec<String>,
+    players: usize,
+    games: usize,
+    seed: u64,
+    rules: RulesConfig,
+}
+
+impl Simulation {
+    /// Creates a new simulation of `games` games of `players` players each,
+    /// seating the named strategies, see `strategies::NAMES`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `PlayerCount` error if `players` isn't a
+    /// legal number of players under the rules, and a `Decode` error if a
+    /// strategy's name isn't known.
+    pub fn new(names: Vec<String>,
+               players: usize,
+               games: usize,
+               seed: u64,
+               rules: RulesConfig)
+        -> Result<Self, Error>
+    {
+        if players < rules.min_players || players > rules.max_players {
+            return Err(Error::Rule(RuleError::PlayerCount(players)))
+        }
+        if names.is_empty() {
+            return Err(Error::Decode("no strategies given".into()))
+        }
+        for name in names.iter() {
+            if Strategy::from_name(name).is_none() {
+                return Err(Error::Decode(format!("unknown strategy `{}`", name)))
+            }
+        }
+
+        Ok(Simulation {
+            names: names,
+            players: players,
+            games: games,
+            seed: seed,
+            rules: rules,
+        })
+    }
+
+    /// Returns the names of the strategies seated at the given game.
+    pub fn seating(&self, game: usize) -> Vec<String> {
+        (0..self.players).map(|seat| {
+            self.names[(game + seat) % self.names.len()].clone()
+        }).collect()
+    }
+
+    /// Plays every game of this simulation, one after another.
+    pub fn run(&self) -> Result<Statistics, Error> {
+        let mut rng = Isaac64Rng::from_seed(&[self.seed]);
+        let mut statistics = Statistics::new(&self.names);
+        for i in 0..self.games {
+            let seating = self.seating(i);
+            let choosers = seating.iter().map(|name| {
+                Strategy::from_name(name).expect("strategy checked when created")
+            }).collect();
+            let mut game = try!(Game::with_rules(choosers, rng.next_u64(), self.rules.clone()));
+            try!(game.play());
+            statistics.add(&game, &seating);
+        }
+        Ok(statistics)
+    }
+}
+
+/// The record of a single strategy across a simulation.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Record {
+    pub name: String,
+    /// The number of seats the strategy played.
+    pub seats: usize,
+    pub wins: usize,
+    pub total_score: u64,
+    /// The sum of the squares of the strategy's scores, for the variance.
+    pub total_squared_score: u64,
+}
+
+impl Record {
+    /// The fraction of seats this strategy won, counting ties as wins.
+    pub fn win_rate(&self) -> f64 {
+        if self.seats == 0 {
+            0.0
+        } else {
+            self.wins as f64 / self.seats as f64
+        }
+    }
+
+    /// The mean score of this strategy, counting a seat it was ejected from
+    /// as a score of zero.
+    pub fn mean_score(&self) -> f64 {
+        if self.seats == 0 {
+            0.0
+        } else {
+            self.total_score as f64 / self.seats as f64
+        }
+    }
+
+    /// The variance of this strategy's scores.
+    pub fn score_variance(&self) -> f64 {
+        if self.seats == 0 {
+            0.0
+        } else {
+            let mean = self.mean_score();
+            self.total_squared_score as f64 / self.seats as f64 - mean * mean
+        }
+    }
+}
+
+/// Statistics aggregated over the games of a simulation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Statistics {
+    pub games: usize,
+    /// The number of rounds played across every game.
+    pub rounds: usize,
+    /// The number of species which went extinct, by starving or in an
+    /// attack.
+    pub extinctions: usize,
+    pub attacks: usize,
+    /// The number of attacks the attacker survived to eat from.
+    pub successful_attacks: usize,
+    /// The number of species alive at the end of a game.
+    pub surviving_species: usize,
+    traits: BTreeMap<Trait, usize>,
+    records: Vec<Record>,
+}
+
+impl Statistics {
+    /// Creates empty statistics for the named strategies, each name is
+    /// recorded once.
+    pub fn new(names: &[String]) -> Self {
+        let mut records: Vec<Record> = Vec::new();
+        for name in names {
+            if !records.iter().any(|r| r.name == *name) {
+                records.push(Record { name: name.clone(), ..Record::default() });
+            }
+        }
+        Statistics {
+            games: 0,
+            rounds: 0,
+            extinctions: 0,
+            attacks: 0,
+            successful_attacks: 0,
+            surviving_species: 0,
+            traits: BTreeMap::new(),
+            records: records,
+        }
+    }
+
+    /// Adds a finished game to these statistics, the `seating` names the
+    /// strategy playing each seat, in the game's starting order.
+    pub fn add<C: Chooser>(&mut self, game: &Game<C>, seating: &[String]) {
+        self.games += 1;
+        self.rounds += game.round();
+
+        let best = game.players().iter().map(|p| p.score()).max();
+        for (seat, name) in seating.iter().enumerate() {
+            let id = (seat + 1) as Id;
+            let score = game.players().iter().find(|p| p.id() == id).map(|p| p.score());
+            let record = self.records.iter_mut().find(|r| r.name == *name)
+                .expect("strategy isn't in the statistics");
+            record.seats += 1;
+            if let Some(score) = score {
+                record.total_score += score;
+                record.total_squared_score += score * score;
+                if Some(score) == best {
+                    record.wins += 1;
+                }
+            }
+        }
+
+        for event in game.events() {
+            match *event {
+                Event::Attack { attacker_extinct, defender_extinct, .. } => {
+                    self.attacks += 1;
+                    if !attacker_extinct {
+                        self.successful_attacks += 1;
+                    }
+                    self.extinctions += attacker_extinct as usize + defender_extinct as usize;
+                },
+                Event::Bag { ref extinctions } => {
+                    self.extinctions += extinctions.iter().fold(0, |sum, &(_, n)| sum + n);
+                },
+                _ => {},
+            }
+        }
+
+        for player in game.players() {
+            for species in player.domain().iter() {
+                self.surviving_species += 1;
+                for trait_type in species.traits() {
+                    *self.traits.entry(*trait_type).or_insert(0) += 1;
+                }
+            }
+        }
+    }
+
+    /// Returns the records of every strategy, in the order they were named.
+    pub fn records(&self) -> &[Record] {
+        &self.records
+    }
+
+    /// The mean number of rounds in a game.
+    pub fn mean_rounds(&self) -> f64 {
+        if self.games == 0 {
+            0.0
+        } else {
+            self.rounds as f64 / self.games as f64
+        }
+    }
+
+    /// Returns the number of surviving species with each trait, and the
+    /// fraction of the surviving species that is, sorted by trait.
+    pub fn trait_frequencies(&self) -> Vec<(Trait, usize, f64)> {
+        self.traits.iter().map(|(&trait_type, &count)| {
+            (trait_type, count, count as f64 / self.surviving_species as f64)
+        }).collect()
+    }
+
+    /// Returns these statistics as a JSON object.
+    pub fn to_json(&self) -> String {
+        let records = self.records.iter().map(|record| {
+            let mut object = BTreeMap::new();
+            object.insert("name".to_string(), Value::String(record.name.clone()));
+            object.insert("seats".to_string(), Value::U64(record.seats as u64));
+            object.insert("wins".to_string(), Value::U64(record.wins as u64));
+            object.insert("win_rate".to_string(), Value::F64(record.win_rate()));
+            object.insert("mean_score".to_string(), Value::F64(record.mean_score()));
+            object.insert("score_variance".to_string(), Value::F64(record.score_variance()));
+            Value::Object(object)
+        }).collect();
+        let traits = self.trait_frequencies().into_iter().map(|(trait_type, count, frequency)| {
+            let mut object = BTreeMap::new();
+            object.insert("count".to_string(), Value::U64(count as u64));
+            object.insert("frequency".to_string(), Value::F64(frequency));
+            (format!("{:?}", trait_type), Value::Object(object))
+        }).collect();
+
+        let mut object = BTreeMap::new();
+        object.insert("games".to_string(), Value::U64(self.games as u64));
+        object.insert("mean_rounds".to_string(), Value::F64(self.mean_rounds()));
+        object.insert("extinctions".to_string(), Value::U64(self.extinctions as u64));
+        object.insert("attacks".to_string(), Value::U64(self.attacks as u64));
+        object.insert("successful_attacks".to_string(), Value::U64(self.successful_attacks as u64));
+        object.insert("surviving_species".to_string(), Value::U64(self.surviving_species as u64));
+        object.insert("strategies".to_string(), Value::Array(records));
+        object.insert("traits".to_string(), Value::Object(traits));
+        json::to_string(&Value::Object(object)).expect("failed to serialize statistics")
+    }
+
+    /// Returns these statistics as CSV, one `group,name,statistic,value` row
+    /// for each statistic. The group is `game`, `strategy` or `trait`.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("group,name,statistic,value\n");
+        csv.push_str(&format!("game,,games,{}\n", self.games));
+        csv.push_str(&format!("game,,mean_rounds,{}\n", self.mean_rounds()));
+        csv.push_str(&format!("game,,extinctions,{}\n", self.extinctions));
+        csv.push_str(&format!("game,,attacks,{}\n", self.attacks));
+        csv.push_str(&format!("game,,successful_attacks,{}\n", self.successful_attacks));
+        csv.push_str(&format!("game,,surviving_species,{}\n", self.surviving_species));
+        for record in self.records.iter() {
+            csv.push_str(&format!("strategy,{},seats,{}\n", record.name, record.seats));
+            csv.push_str(&format!("strategy,{},wins,{}\n", record.name, record.wins));
+            csv.push_str(&format!("strategy,{},win_rate,{}\n", record.name, record.win_rate()));
+            csv.push_str(&format!("strategy,{},mean_score,{}\n", record.name, record.mean_score()));
+            csv.push_str(&format!("strategy,{},score_variance,{}\n", record.name, record.score_variance()));
+        }
+        for (trait_type, count, frequency) in self.trait_frequencies() {
+            csv.push_str(&format!("trait,{:?},count,{}\n", trait_type, count));
+            csv.push_str(&format!("trait,{:?},frequency,{}\n", trait_type, frequency));
+        }
+        csv
+    }
+}
+
+impl fmt::Display for Statistics {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        try!(writeln!(f, "games: {} mean rounds: {:.2}", self.games, self.mean_rounds()));
+        try!(writeln!(f, "extinctions: {} attacks: {} successful: {}",
+                      self.extinctions, self.attacks, self.successful_attacks));
+        try!(writeln!(f, "{:<20} {:>6} {:>6} {:>9} {:>10} {:>10}",
+                      "strategy", "seats", "wins", "win rate", "avg score", "variance"));
+        for record in self.records.iter() {
+            try!(writeln!(f, "{:<20} {:>6} {:>6} {:>9.3} {:>10.2} {:>10.2}",
+                          record.name,
+                          record.seats,
+                          record.wins,
+                          record.win_rate(),
+                          record.mean_score(),
+                          record.score_variance()));
+        }
+        try!(writeln!(f, "surviving species: {}", self.surviving_species));
+        for (trait_type, count, frequency) in self.trait_frequencies() {
+            try!(writeln!(f, "{:<20} {:>6} {:>9.3}", format!("{:?}", trait_type), count, frequency));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use game::*;
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    #[test]
+    fn unknown_strategy() {
+        assert!(Simulation::new(names(&["silly", "nope"]), 3, 1, 0, RulesConfig::default()).is_err());
+        assert!(Simulation::new(names(&[]), 3, 1, 0, RulesConfig::default()).is_err());
+    }
+
+    #[test]
+    fn illegal_players() {
+        assert!(Simulation::new(names(&["silly"]), 2, 1, 0, RulesConfig::default()).is_err());
+        assert!(Simulation::new(names(&["silly"]), 9, 1, 0, RulesConfig::default()).is_err());
+    }
+
+    #[test]
+    fn seating_rotates() {
+        let simulation = Simulation::new(names(&["silly", "greedy"]), 3, 2, 0, RulesConfig::default()).unwrap();
+
+        assert_eq!(names(&["silly", "greedy", "silly"]), simulation.seating(0));
+        assert_eq!(names(&["greedy", "silly", "greedy"]), simulation.seating(1));
+    }
+
+    #[test]
+    fn run_counts_seats() {
+        let simulation = Simulation::new(names(&["silly", "carnivore"]), 4, 6, 2, RulesConfig::default()).unwrap();
+        let statistics = simulation.run().unwrap();
+
+        assert_eq!(6, statistics.games);
+        assert!(statistics.rounds >= 6);
+        assert_eq!(12, statistics.records()[0].seats);
+        assert_eq!(12, statistics.records()[1].seats);
+        let wins = statistics.records().iter().fold(0, |sum, r| sum + r.wins);
+        assert!(wins >= 6);
+        assert!(statistics.successful_attacks <= statistics.attacks);
+        assert_eq!(statistics, simulation.run().unwrap());
+    }
+
+    #[test]
+    fn score_variance() {
+        let record = Record {
+            name: "silly".into(),
+            seats: 4,
+            wins: 1,
+            total_score: 2 + 4 + 4 + 6,
+            total_squared_score: 4 + 16 + 16 + 36,
+        };
+
+        assert_eq!(0.25, record.win_rate());
+        assert_eq!(4.0, record.mean_score());
+        assert_eq!(2.0, record.score_variance());
+    }
+
+    #[test]
+    fn csv_rows() {
+        let statistics = Statistics::new(&names(&["silly", "silly"]));
+        let csv = statistics.to_csv();
+
+        assert!(csv.starts_with("group,name,statistic,value\ngame,,games,0\n"));
+        assert!(csv.contains("strategy,silly,win_rate,0\n"));
+        assert_eq!(1 + 6 + 5, csv.lines().count());
+    }
+}
@@ -0,0 +1,198 @@
+use error::Error;
+use interact::*;
+use object::*;
+use silly::*;
+
+/// Plays for one big carnivore, attacking whenever it can.
+///
+/// Cards go to making a carnivore species, then to growing its body and
+/// population. When feeding, it attacks the opponent species with the
+/// biggest body it can, and otherwise feeds like `Silly`.
+#[derive(Debug, Default)]
+pub struct Carnivore;
+
+impl Chooser for Carnivore {
+    fn start(&mut self, _: &DealObservation) {
+        // Do nothing.
+    }
+
+    fn info(&self) -> Option<&str> {
+        Some("carnivore")
+    }
+}
+
+impl Choose<ActionObservation, ActionChoice> for Carnivore {
+    fn choose(&mut self, observation: &ActionObservation) -> Result<Option<ActionChoice>, Error> {
+        let player = &observation.current_player;
+        let rules = &observation.rules;
+        let hand = player.hand();
+        // Plant cards first, the least food first, keeping carnivore cards
+        // for the species boards.
+        let mut cards = (0..hand.len()).collect::<Vec<_>>();
+        cards.sort_by_key(|&i| (hand[i].is_carnivore(), hand[i].food_value()));
+        if cards.is_empty() {
+            return Ok(None)
+        }
+
+        let mut choice = ActionChoice {
+            food_card: cards.remove(0),
+            population_growths: Vec::new(),
+            body_growths: Vec::new(),
+            boards: Vec::new(),
+            traits: Vec::new(),
+        };
+
+        // The species to grow, with its population and body size.
+        let carnivore = player.domain().iter().position(|s| s.has_trait(Trait::Carnivore));
+        let mut target = match carnivore {
+            Some(i) => Some((i, player.domain()[i].population(), player.domain()[i].body_size())),
+            None => None,
+        };
+        if target.is_none() && cards.len() >= 2 {
+            let trait_cards = match cards.iter().position(|&i| hand[i].is_carnivore()) {
+                Some(p) if rules.max_traits > 0 => vec![cards.remove(p)],
+                _ => Vec::new(),
+            };
+            if !trait_cards.is_empty() || player.domain().is_empty() {
+                choice.boards.push(BoardTrade {
+                    card_index: cards.remove(0),
+                    trait_card_indeces: trait_cards,
+                });
+                target = Some((player.domain().len(), 1, 0));
+            }
+        }
+        let (species_index, mut population, mut body_size) = match target {
+            Some(target) => target,
+            None if !player.domain().is_empty() => {
+                (0, player.domain()[0].population(), player.domain()[0].body_size())
+            }
+            None => return Ok(Some(choice)),
+        };
+
+        // Grow the body first, so it can attack more species.
+        for card in cards {
+            if body_size <= population && body_size < rules.max_body_size {
+                body_size += 1;
+                choice.body_growths.push(Growth {
+                    species_index: species_index,
+                    card_index: card,
+                });
+            } else if population < rules.max_population {
+                population += 1;
+                choice.population_growths.push(Growth {
+                    species_index: species_index,
+                    card_index: card,
+                });
+            } else if body_size < rules.max_body_size {
+                body_size += 1;
+                choice.body_growths.push(Growth {
+                    species_index: species_index,
+                    card_index: card,
+                });
+            }
+        }
+
+        Ok(Some(choice))
+    }
+}
+
+impl Choose<FeedObservation, FeedChoice> for Carnivore {
+    fn choose(&mut self, observation: &FeedObservation) -> Result<Option<FeedChoice>, Error> {
+        let current_player_idx = observation.current_player_index();
+        let attack = observation.choices().into_iter().filter_map(|c| {
+            match c {
+                FeedChoice::Attack(_, target_idx, defender_idx) if target_idx != current_player_idx => {
+                    let defender = &observation.opponents[target_idx].domain[defender_idx];
+                    Some((c, defender.body_size()))
+                }
+                _ => None,
+            }
+        }).max_by_key(|&(_, body_size)| body_size);
+
+        match attack {
+            Some((c, _)) => Ok(Some(c)),
+            None => Silly.choose(observation),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use game::*;
+    use interact::*;
+    use object::*;
+    use strategies::*;
+
+    #[test]
+    fn makes_a_carnivore() {
+        let game = game_with_players(3, &|player| {
+            if player.id() == 1 {
+                player.push_cards(vec![
+                    Card::mock(0, Trait::Carnivore),
+                    Card::mock(1, Trait::FatTissue),
+                    Card::mock(-1, Trait::Horns),
+                    Card::mock(2, Trait::Ambush),
+                ]);
+            }
+        });
+        let observation: ActionObservation = game.observe();
+
+        let choice = Carnivore.choose(&observation).unwrap().unwrap();
+
+        assert_eq!(Ok(()), choice.validate(&observation));
+        assert_eq!(2, choice.food_card);
+        assert_eq!(vec![BoardTrade {
+            card_index: 1,
+            trait_card_indeces: vec![0],
+        }], choice.boards);
+        assert_eq!(1, choice.body_growths.len());
+    }
+
+    #[test]
+    fn grows_by_the_observed_rules() {
+        let mut game = GameState::with_rules(3, 1, RulesConfig::official()).unwrap();
+        game.players_mut()[0].domain_mut().add(Placement::Right);
+        {
+            let species = &mut game.players_mut()[0].domain_mut()[0];
+            species.evolve(Trait::Carnivore).unwrap();
+            for _ in 1..rules::OFFICIAL_MAX_POPULATION {
+                species.breed().unwrap();
+            }
+            for _ in 1..rules::OFFICIAL_MAX_BODY_SIZE {
+                species.grow().unwrap();
+            }
+        }
+        game.players_mut()[0].push_cards(Card::deck().into_iter().take(4).collect());
+        let observation: ActionObservation = game.observe();
+
+        let choice = Carnivore.choose(&observation).unwrap().unwrap();
+
+        assert!(observation.is_legal(&choice));
+        assert_eq!(1, choice.body_growths.len());
+        assert!(choice.population_growths.is_empty());
+    }
+
+    #[test]
+    fn attacks_biggest() {
+        let mut game = game_with_players(3, &|player| {
+            player.domain_mut().add(Placement::Right);
+            match player.id() {
+                1 => {
+                    player.domain_mut()[0].evolve(Trait::Carnivore).unwrap();
+                    player.domain_mut()[0].grow().unwrap();
+                    player.domain_mut()[0].grow().unwrap();
+                }
+                3 => {
+                    player.domain_mut()[0].grow().unwrap();
+                }
+                _ => {}
+            }
+        });
+        game.board_mut().push_food(FoodToken);
+        let observation: FeedObservation = game.observe();
+
+        let choice = Carnivore.choose(&observation).unwrap().unwrap();
+
+        assert_eq!(FeedChoice::Attack(0, 1, 0), choice);
+    }
+}
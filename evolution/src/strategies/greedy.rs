@@ -0,0 +1,148 @@
+use std::fmt;
+use rand::{self, SeedableRng};
+use rand::isaac::Isaac64Rng;
+use error::Error;
+use interact::*;
+use silly::*;
+use strategies::simulate;
+
+/// The number of random actions a `Greedy` chooser considers by default.
+pub const DEFAULT_SAMPLES: usize = 32;
+
+/// Looks one step ahead, making the choice which leaves it best off
+/// compared to its opponents.
+///
+/// Each choice is scored by stepping a copy of the observed game with it,
+/// see `strategies::simulate::value`. There are too many actions to try
+/// them all, so `Silly`'s action and a number of random ones are tried.
+pub struct Greedy {
+    rng: Isaac64Rng,
+    samples: usize,
+}
+
+impl Greedy {
+    /// Creates a greedy chooser, which tries the given number of random
+    /// actions each turn, chosen from the seed.
+    pub fn new(samples: usize, seed: u64) -> Self {
+        Greedy {
+            rng: Isaac64Rng::from_seed(&[seed]),
+            samples: samples,
+        }
+    }
+}
+
+impl Default for Greedy {
+    fn default() -> Self {
+        Greedy::new(DEFAULT_SAMPLES, rand::random())
+    }
+}
+
+impl fmt::Debug for Greedy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Greedy {{ samples: {} }}", self.samples)
+    }
+}
+
+impl Chooser for Greedy {
+    fn start(&mut self, _: &DealObservation) {
+        // Do nothing.
+    }
+
+    fn info(&self) -> Option<&str> {
+        Some("greedy")
+    }
+}
+
+impl Choose<ActionObservation, ActionChoice> for Greedy {
+    fn choose(&mut self, observation: &ActionObservation) -> Result<Option<ActionChoice>, Error> {
+        let mut candidates = Vec::new();
+        // `Silly` needs at least four cards.
+        if observation.current_player.hand().len() >= 4 {
+            if let Some(choice) = try!(Silly.choose(observation)) {
+                candidates.push(choice);
+            }
+        }
+        for _ in 0..self.samples {
+            if let Some(choice) = simulate::random_action(observation, &mut self.rng) {
+                candidates.push(choice);
+            }
+        }
+
+        let id = observation.current_player.id();
+        Ok(best(candidates, |c| {
+            simulate::after_action(observation, c).and_then(|game| simulate::value(&game, id))
+        }))
+    }
+}
+
+impl Choose<FeedObservation, FeedChoice> for Greedy {
+    fn choose(&mut self, observation: &FeedObservation) -> Result<Option<FeedChoice>, Error> {
+        let id = observation.current_player.id();
+        Ok(best(observation.choices(), |&c| {
+            simulate::after_feed(observation, c).and_then(|game| simulate::value(&game, id))
+        }))
+    }
+}
+
+/// Returns the first of the choices with the highest value, skipping
+/// choices without one.
+fn best<C, F>(choices: Vec<C>, value: F) -> Option<C>
+    where F: Fn(&C) -> Option<i64>
+{
+    let mut best = None;
+    for choice in choices {
+        if let Some(v) = value(&choice) {
+            let better = match best {
+                Some((_, best_v)) => v > best_v,
+                None => true,
+            };
+            if better {
+                best = Some((choice, v));
+            }
+        }
+    }
+    best.map(|(choice, _)| choice)
+}
+
+#[cfg(test)]
+mod tests {
+    use game::*;
+    use interact::*;
+    use object::*;
+    use strategies::*;
+
+    #[test]
+    fn attacks_instead_of_abstaining() {
+        let mut game = game_with_players(3, &|player| {
+            player.domain_mut().add(Placement::Right);
+            if player.id() == 1 {
+                player.domain_mut()[0].evolve(Trait::Carnivore).unwrap();
+                player.domain_mut()[0].grow().unwrap();
+            }
+        });
+        game.board_mut().push_food(FoodToken);
+        let observation: FeedObservation = game.observe();
+
+        let choice = Greedy::new(0, 0).choose(&observation).unwrap().unwrap();
+
+        match choice {
+            FeedChoice::Attack(0, _, 0) => {},
+            c => panic!("expected an attack, got {:?}", c),
+        }
+    }
+
+    #[test]
+    fn actions_are_valid() {
+        let game = game_with_players(3, &|player| {
+            if player.id() == 1 {
+                player.domain_mut().add(Placement::Right);
+                player.push_cards(Card::deck().into_iter().take(5).collect());
+            }
+        });
+        let observation: ActionObservation = game.observe();
+
+        let choice = Greedy::new(8, 1).choose(&observation).unwrap().unwrap();
+
+        assert_eq!(Ok(()), choice.validate(&observation));
+    }
+}
@@ -0,0 +1,386 @@
+use std::cmp;
+use std::f64;
+use std::fmt;
+use std::time::{Duration, Instant};
+use rand::{self, Rng, SeedableRng};
+use rand::isaac::Isaac64Rng;
+use error::Error;
+use game::*;
+use interact::*;
+use object::*;
+use silly::*;
+use strategies::simulate;
+
+/// The number of rollouts a `Mcts` chooser runs per choice by default.
+pub const DEFAULT_ITERATIONS: usize = 400;
+
+/// The longest a `Mcts` chooser searches per choice by default, in
+/// milliseconds.
+pub const DEFAULT_SEARCH_MILLIS: u64 = 500;
+
+/// How much a `Mcts` chooser explores choices it hasn't tried much, by
+/// default. This is about `sqrt(2)`, the usual UCB1 constant.
+pub const DEFAULT_EXPLORATION: f64 = 1.4;
+
+/// The number of random actions a `Mcts` chooser considers, along with
+/// `Silly`'s action.
+const ACTION_CANDIDATES: usize = 16;
+
+/// Searches for the best choice with Monte Carlo rollouts.
+///
+/// Each rollout picks one of the current player's legal choices with the
+/// UCB1 rule, so promising choices get more rollouts, then plays out the
+/// rest of the round from a determinized game. The hidden cards, everyone
+/// else's hands and the deck, are dealt at random from the cards the
+/// current player can't see, and every player after the choice plays
+/// randomly. The choice with the most rollouts is made.
+///
+/// Rollouts end with the round, and are scored with
+/// `strategies::simulate::value`.
+pub struct Mcts {
+    rng: Isaac64Rng,
+    iterations: usize,
+    time: Option<Duration>,
+    limit: Option<Duration>,
+    exploration: f64,
+}
+
+impl Mcts {
+    /// Creates a chooser which runs at most `iterations` rollouts, for at
+    /// most `time`, for each choice, chosen from the seed.
+    pub fn new(iterations: usize, time: Option<Duration>, seed: u64) -> Self {
+        Mcts {
+            rng: Isaac64Rng::from_seed(&[seed]),
+            iterations: iterations,
+            time: time,
+            limit: None,
+            exploration: DEFAULT_EXPLORATION,
+        }
+    }
+
+    /// Sets how much to explore choices which haven't been tried much.
+    pub fn set_exploration(&mut self, exploration: f64) {
+        self.exploration = exploration;
+    }
+
+    /// Returns how long to search for, which is at most half of the time
+    /// limit given by the game, to leave time for sending the choice.
+    fn search_time(&self) -> Option<Duration> {
+        match (self.time, self.limit.map(|limit| limit / 2)) {
+            (Some(a), Some(b)) => Some(cmp::min(a, b)),
+            (a, None) => a,
+            (None, b) => b,
+        }
+    }
+
+    /// Runs rollouts for the choices, returning the choice with the most
+    /// rollouts. Rollouts return `None` for choices which get the player
+    /// ejected, and those choices aren't tried again.
+    fn search<Ch, F>(&mut self, choices: Vec<Ch>, mut rollout: F) -> Option<Ch>
+        where F: FnMut(&mut Isaac64Rng, &Ch) -> Option<i64>
+    {
+        if choices.len() <= 1 {
+            return choices.into_iter().next()
+        }
+
+        let mut arms = choices.into_iter().map(|choice| {
+            Arm {
+                choice: choice,
+                visits: 0,
+                reward: 0.0,
+                legal: true,
+            }
+        }).collect::<Vec<_>>();
+        let start = Instant::now();
+        let time = self.search_time();
+        for _ in 0..self.iterations {
+            if time.map_or(false, |time| start.elapsed() >= time) {
+                break
+            }
+            let index = match select(&arms, self.exploration) {
+                Some(index) => index,
+                None => break,
+            };
+            match rollout(&mut self.rng, &arms[index].choice) {
+                Some(value) => {
+                    arms[index].visits += 1;
+                    arms[index].reward += reward(value);
+                }
+                None => arms[index].legal = false,
+            }
+        }
+        arms.into_iter()
+            .filter(|arm| arm.legal)
+            .max_by_key(|arm| arm.visits)
+            .map(|arm| arm.choice)
+    }
+}
+
+impl Default for Mcts {
+    fn default() -> Self {
+        Mcts::new(DEFAULT_ITERATIONS,
+                  Some(Duration::from_millis(DEFAULT_SEARCH_MILLIS)),
+                  rand::random())
+    }
+}
+
+impl fmt::Debug for Mcts {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Mcts {{ iterations: {}, time: {:?} }}", self.iterations, self.time)
+    }
+}
+
+impl Chooser for Mcts {
+    fn start(&mut self, _: &DealObservation) {
+        // Do nothing.
+    }
+
+    fn info(&self) -> Option<&str> {
+        Some("mcts")
+    }
+
+    fn time_limit(&mut self, limit: Option<Duration>) {
+        self.limit = limit;
+    }
+}
+
+impl Choose<ActionObservation, ActionChoice> for Mcts {
+    fn choose(&mut self, observation: &ActionObservation) -> Result<Option<ActionChoice>, Error> {
+        let mut candidates = Vec::new();
+        // `Silly` needs at least four cards.
+        if observation.current_player.hand().len() >= 4 {
+            if let Some(choice) = try!(Silly.choose(observation)) {
+                candidates.push(choice);
+            }
+        }
+        for _ in 0..ACTION_CANDIDATES {
+            if let Some(choice) = simulate::random_action(observation, &mut self.rng) {
+                if !candidates.contains(&choice) {
+                    candidates.push(choice);
+                }
+            }
+        }
+
+        Ok(self.search(candidates, |rng, choice| action_rollout(observation, choice, rng)))
+    }
+}
+
+impl Choose<FeedObservation, FeedChoice> for Mcts {
+    fn choose(&mut self, observation: &FeedObservation) -> Result<Option<FeedChoice>, Error> {
+        Ok(self.search(observation.choices(), |rng, &choice| feed_rollout(observation, choice, rng)))
+    }
+}
+
+/// A choice being searched, with the number of rollouts and the total
+/// reward of them.
+struct Arm<Ch> {
+    choice: Ch,
+    visits: usize,
+    reward: f64,
+    legal: bool,
+}
+
+/// Returns the index of the legal arm to roll out next by the UCB1 rule,
+/// trying every arm once first.
+fn select<Ch>(arms: &[Arm<Ch>], exploration: f64) -> Option<usize> {
+    let total = arms.iter().fold(0, |a, arm| a + arm.visits) as f64;
+    let mut best = None;
+    let mut best_score = f64::NEG_INFINITY;
+    for (i, arm) in arms.iter().enumerate() {
+        if !arm.legal {
+            continue
+        }
+        if arm.visits == 0 {
+            return Some(i)
+        }
+        let visits = arm.visits as f64;
+        let score = arm.reward / visits + exploration * (total.ln() / visits).sqrt();
+        if score > best_score {
+            best = Some(i);
+            best_score = score;
+        }
+    }
+    best
+}
+
+/// Squashes a value from `simulate::value` into a reward between 0 and 1.
+fn reward(value: i64) -> f64 {
+    1.0 / (1.0 + (-(value as f64) / 4.0).exp())
+}
+
+/// Returns every card the current player can't see, the whole deck of the
+/// rules without their hand, shuffled.
+fn unseen<R: Rng>(rules: &RulesConfig, hand: &[Card], rng: &mut R) -> Vec<Card> {
+    let mut cards = rules.deck(rng.gen());
+    for card in hand {
+        if let Some(i) = cards.iter().position(|c| c == card) {
+            cards.remove(i);
+        }
+    }
+    cards
+}
+
+/// Makes random legal choices while playing out a round.
+struct Playout<'a>(&'a mut Isaac64Rng);
+
+impl<'a> fmt::Debug for Playout<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Playout")
+    }
+}
+
+impl<'a> Choose<ActionObservation, ActionChoice> for Playout<'a> {
+    fn choose(&mut self, observation: &ActionObservation) -> Result<Option<ActionChoice>, Error> {
+        Ok(simulate::random_action(observation, &mut *self.0))
+    }
+}
+
+impl<'a> Choose<FeedObservation, FeedChoice> for Playout<'a> {
+    fn choose(&mut self, observation: &FeedObservation) -> Result<Option<FeedChoice>, Error> {
+        Ok(self.0.choose(&observation.choices()).cloned())
+    }
+}
+
+/// Plays the rest of the feeding randomly.
+fn finish_feeding(game: &mut GameState, rng: &mut Isaac64Rng) {
+    let mut playout = Playout(rng);
+    while !game.turn_is_over() && game.has_current_player() {
+        if step::Feed(&mut *game, &mut Auto(&mut playout)).step().is_err() {
+            break
+        }
+    }
+}
+
+/// Feeds with the choice in a determinized game, and plays out the round.
+fn feed_rollout(observation: &FeedObservation,
+                choice: FeedChoice,
+                rng: &mut Isaac64Rng)
+    -> Option<i64>
+{
+    let id = observation.current_player.id();
+    let deck = unseen(&observation.rules, observation.current_player.hand(), rng);
+    let mut game = simulate::feed_game(observation, deck);
+    let mut choice = choice;
+    if step::Feed(&mut game, &mut choice).step().is_err() || !game.kicked().is_empty() {
+        return None
+    }
+    finish_feeding(&mut game, rng);
+    simulate::value(&game, id)
+}
+
+/// Takes the action in a determinized game, and plays out the round.
+fn action_rollout(observation: &ActionObservation,
+                  choice: &ActionChoice,
+                  rng: &mut Isaac64Rng)
+    -> Option<i64>
+{
+    let id = observation.current_player.id();
+    let deck = unseen(&observation.rules, observation.current_player.hand(), rng);
+    let mut game = simulate::action_game(observation, deck);
+    // The players before have given their food cards, and the players
+    // after still have all of their cards.
+    for _ in 0..observation.before.len() {
+        if let Some(card) = game.deal() {
+            game.board_mut().add_card(card);
+        }
+    }
+    let current = observation.before.len();
+    for i in (current + 1)..game.players().len() {
+        let amount = game.rules().cards_for(cmp::max(game.players()[i].domain().len(), 1));
+        let cards = game.deals(amount);
+        game.players_mut()[i].push_cards(cards);
+    }
+
+    let mut choice = choice.clone();
+    if step::Action(&mut game, &mut choice).step().is_err() || !game.kicked().is_empty() {
+        return None
+    }
+    {
+        let mut playout = Playout(&mut *rng);
+        for _ in 0..observation.after.len() {
+            if step::Action(&mut game, &mut playout).step().is_err() {
+                return None
+            }
+        }
+    }
+    if step::Reveal(&mut game).step().is_err() {
+        return None
+    }
+    finish_feeding(&mut game, rng);
+    simulate::value(&game, id)
+}
+
+#[cfg(test)]
+mod tests {
+    use game::*;
+    use interact::*;
+    use object::*;
+    use strategies::*;
+
+    #[test]
+    fn attacks_instead_of_abstaining() {
+        let mut game = game_with_players(3, &|player| {
+            player.domain_mut().add(Placement::Right);
+            if player.id() == 1 {
+                player.domain_mut()[0].evolve(Trait::Carnivore).unwrap();
+                player.domain_mut()[0].grow().unwrap();
+            }
+        });
+        game.board_mut().push_food(FoodToken);
+        let observation: FeedObservation = game.observe();
+
+        let choice = Mcts::new(60, None, 1).choose(&observation).unwrap().unwrap();
+
+        match choice {
+            FeedChoice::Attack(0, _, 0) => {},
+            c => panic!("expected an attack, got {:?}", c),
+        }
+    }
+
+    #[test]
+    fn actions_are_valid() {
+        let mut game = game_with_players(3, &|player| {
+            player.domain_mut().add(Placement::Right);
+        });
+        let cards = game.deals(5);
+        game.players_mut()[0].push_cards(cards);
+        let observation: ActionObservation = game.observe();
+
+        let choice = Mcts::new(40, None, 2).choose(&observation).unwrap().unwrap();
+
+        assert_eq!(Ok(()), choice.validate(&observation));
+    }
+
+    #[test]
+    fn unseen_cards_come_from_the_rules_deck() {
+        use rand::SeedableRng;
+        use rand::isaac::Isaac64Rng;
+        let rules = RulesConfig {
+            expansion_cards: EXPANSION_CARD_BOUNDS,
+            ..RulesConfig::default()
+        };
+        let mut hand = rules.deck(1);
+        hand.truncate(4);
+
+        let mut unseen = super::unseen(&rules, &hand, &mut Isaac64Rng::from_seed(&[1]));
+        unseen.extend(hand);
+        unseen.sort();
+
+        assert_eq!(Card::expansion_deck(), unseen);
+    }
+
+    #[test]
+    fn single_choice_without_search() {
+        let mut game = game_with_players(3, &|player| {
+            if player.id() == 1 {
+                player.domain_mut().add(Placement::Right);
+            }
+        });
+        game.board_mut().push_food(FoodToken);
+        let observation: FeedObservation = game.observe();
+
+        let choice = Mcts::new(0, None, 3).choose(&observation).unwrap();
+
+        assert_eq!(Some(FeedChoice::Feed(0)), choice);
+    }
+}
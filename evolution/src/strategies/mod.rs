@@ -0,0 +1,157 @@
+use std::time::Duration;
+use error::Error;
+use interact::*;
+use silly::*;
+
+/// The names of every strategy, as given to `Strategy::from_name`.
+pub const NAMES: [&'static str; 6] = ["silly", "random", "greedy", "carnivore", "mcts", "solver"];
+
+/// One of the built in strategies, chosen by name.
+///
+/// # Examples
+///
+/// ```rust
+/// use evolution::Game;
+/// use evolution::strategies::Strategy;
+///
+/// let choosers = vec!["silly", "random", "greedy", "carnivore"].into_iter().map(|name| {
+///     Strategy::from_name(name).unwrap()
+/// }).collect();
+/// let mut game = Game::with_choosers(choosers, 1).unwrap();
+/// game.play().unwrap();
+/// ```
+#[derive(Debug)]
+pub enum Strategy {
+    Silly(Silly),
+    Random(Random),
+    Greedy(Greedy),
+    Carnivore(Carnivore),
+    Mcts(Mcts),
+    Solver(Solver),
+}
+
+impl Strategy {
+    /// Returns the strategy with the given name, see `NAMES`, or `None` if
+    /// there isn't one.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "silly" => Some(Strategy::Silly(Silly)),
+            "random" => Some(Strategy::Random(Random::default())),
+            "greedy" => Some(Strategy::Greedy(Greedy::default())),
+            "carnivore" => Some(Strategy::Carnivore(Carnivore)),
+            "mcts" => Some(Strategy::Mcts(Mcts::default())),
+            "solver" => Some(Strategy::Solver(Solver::default())),
+            _ => None,
+        }
+    }
+
+    /// Returns the name of this strategy.
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Strategy::Silly(_) => "silly",
+            Strategy::Random(_) => "random",
+            Strategy::Greedy(_) => "greedy",
+            Strategy::Carnivore(_) => "carnivore",
+            Strategy::Mcts(_) => "mcts",
+            Strategy::Solver(_) => "solver",
+        }
+    }
+}
+
+impl Chooser for Strategy {
+    fn start(&mut self, observation: &DealObservation) {
+        match *self {
+            Strategy::Silly(ref mut c) => c.start(observation),
+            Strategy::Random(ref mut c) => c.start(observation),
+            Strategy::Greedy(ref mut c) => c.start(observation),
+            Strategy::Carnivore(ref mut c) => c.start(observation),
+            Strategy::Mcts(ref mut c) => c.start(observation),
+            Strategy::Solver(ref mut c) => c.start(observation),
+        }
+    }
+
+    fn info(&self) -> Option<&str> {
+        Some(self.name())
+    }
+
+    fn time_limit(&mut self, limit: Option<Duration>) {
+        match *self {
+            Strategy::Silly(ref mut c) => c.time_limit(limit),
+            Strategy::Random(ref mut c) => c.time_limit(limit),
+            Strategy::Greedy(ref mut c) => c.time_limit(limit),
+            Strategy::Carnivore(ref mut c) => c.time_limit(limit),
+            Strategy::Mcts(ref mut c) => c.time_limit(limit),
+            Strategy::Solver(ref mut c) => c.time_limit(limit),
+        }
+    }
+}
+
+impl Choose<ActionObservation, ActionChoice> for Strategy {
+    fn choose(&mut self, observation: &ActionObservation) -> Result<Option<ActionChoice>, Error> {
+        match *self {
+            Strategy::Silly(ref mut c) => c.choose(observation),
+            Strategy::Random(ref mut c) => c.choose(observation),
+            Strategy::Greedy(ref mut c) => c.choose(observation),
+            Strategy::Carnivore(ref mut c) => c.choose(observation),
+            Strategy::Mcts(ref mut c) => c.choose(observation),
+            Strategy::Solver(ref mut c) => c.choose(observation),
+        }
+    }
+}
+
+impl Choose<FeedObservation, FeedChoice> for Strategy {
+    fn choose(&mut self, observation: &FeedObservation) -> Result<Option<FeedChoice>, Error> {
+        match *self {
+            Strategy::Silly(ref mut c) => c.choose(observation),
+            Strategy::Random(ref mut c) => c.choose(observation),
+            Strategy::Greedy(ref mut c) => c.choose(observation),
+            Strategy::Carnivore(ref mut c) => c.choose(observation),
+            Strategy::Mcts(ref mut c) => c.choose(observation),
+            Strategy::Solver(ref mut c) => c.choose(observation),
+        }
+    }
+}
+
+/// Stepping copies of an observed game, to see what choices would do.
+pub mod simulate;
+
+mod random;
+pub use self::random::Random;
+
+mod greedy;
+pub use self::greedy::{Greedy, DEFAULT_SAMPLES};
+
+mod carnivore;
+pub use self::carnivore::Carnivore;
+
+mod mcts;
+pub use self::mcts::{Mcts, DEFAULT_ITERATIONS, DEFAULT_SEARCH_MILLIS, DEFAULT_EXPLORATION};
+
+mod solver;
+pub use self::solver::{Solver, Solution, DEFAULT_POSITIONS};
+
+#[cfg(test)]
+mod tests {
+    use game::*;
+    use strategies::*;
+
+    #[test]
+    fn from_name() {
+        for name in NAMES.iter() {
+            assert_eq!(*name, Strategy::from_name(name).unwrap().name());
+        }
+        assert!(Strategy::from_name("clever").is_none());
+    }
+
+    #[test]
+    fn play_a_game() {
+        // Leave out `mcts`, it searches for too long.
+        let names = ["silly", "random", "greedy", "carnivore", "solver"];
+        let choosers = names.iter().map(|name| Strategy::from_name(name).unwrap()).collect();
+        let mut game = Game::with_choosers(choosers, 7).unwrap();
+
+        game.play().unwrap();
+
+        assert!(game.kicked().is_empty());
+    }
+}
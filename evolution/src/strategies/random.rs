@@ -0,0 +1,112 @@
+use std::fmt;
+use rand::{self, Rng, SeedableRng};
+use rand::isaac::Isaac64Rng;
+use error::Error;
+use interact::*;
+use strategies::simulate;
+
+/// Makes a random legal choice every time.
+pub struct Random(Isaac64Rng);
+
+impl Random {
+    /// Creates a random chooser, which makes the same choices for the same
+    /// seed.
+    pub fn new(seed: u64) -> Self {
+        Random(Isaac64Rng::from_seed(&[seed]))
+    }
+}
+
+impl Default for Random {
+    fn default() -> Self {
+        Random::new(rand::random())
+    }
+}
+
+impl fmt::Debug for Random {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Random")
+    }
+}
+
+impl Chooser for Random {
+    fn start(&mut self, _: &DealObservation) {
+        // Do nothing.
+    }
+
+    fn info(&self) -> Option<&str> {
+        Some("random")
+    }
+}
+
+impl Choose<ActionObservation, ActionChoice> for Random {
+    fn choose(&mut self, observation: &ActionObservation) -> Result<Option<ActionChoice>, Error> {
+        Ok(simulate::random_action(observation, &mut self.0))
+    }
+}
+
+impl Choose<FeedObservation, FeedChoice> for Random {
+    fn choose(&mut self, observation: &FeedObservation) -> Result<Option<FeedChoice>, Error> {
+        Ok(self.0.choose(&observation.choices()).cloned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand::isaac::Isaac64Rng;
+    use game::*;
+    use interact::*;
+    use object::*;
+    use strategies::*;
+
+    #[test]
+    fn actions_are_valid() {
+        let game = game_with_players(3, &|player| {
+            if player.id() == 1 {
+                player.domain_mut().add(Placement::Right);
+                player.push_cards(Card::deck().into_iter().take(7).collect());
+            }
+        });
+        let observation: ActionObservation = game.observe();
+
+        for seed in 0..100 {
+            let choice = Random::new(seed).choose(&observation).unwrap().unwrap();
+            assert_eq!(Ok(()), choice.validate(&observation));
+        }
+    }
+
+    #[test]
+    fn actions_follow_the_observed_rules() {
+        let mut game = GameState::with_rules(3, 1, RulesConfig::official()).unwrap();
+        game.players_mut()[0].domain_mut().add(Placement::Right);
+        for _ in 1..rules::OFFICIAL_MAX_POPULATION {
+            game.players_mut()[0].domain_mut()[0].breed().unwrap();
+        }
+        for _ in 0..rules::OFFICIAL_MAX_BODY_SIZE {
+            game.players_mut()[0].domain_mut()[0].grow().unwrap();
+        }
+        game.players_mut()[0].push_cards(Card::deck().into_iter().take(7).collect());
+        let observation: ActionObservation = game.observe();
+
+        for seed in 0..100 {
+            let choice = simulate::random_action(&observation, &mut Isaac64Rng::from_seed(&[seed])).unwrap();
+            assert!(observation.is_legal(&choice));
+        }
+    }
+
+    #[test]
+    fn feeds_are_legal() {
+        let mut game = game_with_players(3, &|player| {
+            player.domain_mut().add(Placement::Right);
+            player.domain_mut()[0].evolve(Trait::Carnivore).unwrap();
+            player.domain_mut()[0].grow().unwrap();
+        });
+        game.board_mut().push_food(FoodToken);
+        let observation: FeedObservation = game.observe();
+
+        for seed in 0..20 {
+            let choice = Random::new(seed).choose(&observation).unwrap().unwrap();
+            assert!(observation.choices().contains(&choice));
+        }
+    }
+}
@@ -0,0 +1,166 @@
+use rand::Rng;
+use game::*;
+use interact::*;
+use object::*;
+
+/// Returns a game made from what the current player can see while feeding,
+/// with them as the first, and current player, and the given deck, played
+/// by the observed rules.
+///
+/// Opponents have no cards, and nothing in their bags, since the current
+/// player can't see them.
+pub fn feed_game(observation: &FeedObservation, deck: Vec<Card>) -> GameState {
+    let mut players = vec![observation.current_player.clone()];
+    for opponent in observation.opponents.iter() {
+        let mut player = Player::new(opponent.id);
+        *player.domain_mut() = opponent.domain.clone();
+        players.push(player);
+    }
+    let mut board = Board::default();
+    for _ in 0..observation.board.food {
+        board.push_food(FoodToken);
+    }
+    GameState::from_parts(players, board, deck, 0, observation.rules.clone())
+}
+
+/// Returns a game made from what the current player can see while taking
+/// their action, with the other players in the same order around them, and
+/// the given deck, played by the observed rules.
+///
+/// Opponents have no cards, and nothing in their bags.
+pub fn action_game(observation: &ActionObservation, deck: Vec<Card>) -> GameState {
+    let current = &observation.current_player;
+    // The other players' ids aren't observed, so make up unique ones.
+    let mut ids = (1..).filter(|&id| id != current.id());
+    let mut players = Vec::new();
+    for domain in observation.before.iter() {
+        let mut player = Player::new(ids.next().expect("ran out of ids"));
+        *player.domain_mut() = domain.clone();
+        players.push(player);
+    }
+    players.push(current.clone());
+    for domain in observation.after.iter() {
+        let mut player = Player::new(ids.next().expect("ran out of ids"));
+        *player.domain_mut() = domain.clone();
+        players.push(player);
+    }
+    GameState::from_parts(players,
+                          Board::default(),
+                          deck,
+                          observation.before.len(),
+                          observation.rules.clone())
+}
+
+/// Returns the game after the current player feeds with the given choice,
+/// or `None` if the choice would get them ejected.
+pub fn after_feed(observation: &FeedObservation, choice: FeedChoice) -> Option<GameState> {
+    let mut game = feed_game(observation, Vec::new());
+    if game.apply_feed(choice).is_err() || !game.kicked().is_empty() {
+        return None
+    }
+    Some(game)
+}
+
+/// Returns the game after the current player takes the given action, or
+/// `None` if the choice would get them ejected.
+pub fn after_action(observation: &ActionObservation, choice: &ActionChoice) -> Option<GameState> {
+    let mut game = action_game(observation, Vec::new());
+    if game.apply_action(choice.clone()).is_err() {
+        return None
+    }
+    Some(game)
+}
+
+/// Returns how well the player with the given id is doing in the game,
+/// compared to the best of the other players, or `None` if they aren't in
+/// the game.
+///
+/// A player is worth their score, plus the food their species have eaten
+/// or stored, which will be in their bag at the end of the round.
+pub fn value(game: &GameState, id: Id) -> Option<i64> {
+    let worth = |player: &Player| {
+        let food = player.domain().iter().fold(0, |a, s| a + s.food_count() + s.fat_count());
+        player.score() as i64 + food as i64
+    };
+    game.players().iter().find(|p| p.id() == id).map(|player| {
+        let best_opponent = game.players()
+                                .iter()
+                                .filter(|p| p.id() != id)
+                                .map(|p| worth(p))
+                                .max()
+                                .unwrap_or(0);
+        worth(player) - best_opponent
+    })
+}
+
+/// Returns a random action which is legal for the current player, or `None`
+/// if they have no cards.
+///
+/// Every card but the food card is either traded for a new species board,
+/// possibly with one trait, grows a species, or is kept.
+pub fn random_action<R: Rng>(observation: &ActionObservation, rng: &mut R) -> Option<ActionChoice> {
+    let player = &observation.current_player;
+    let rules = &observation.rules;
+    let mut cards = (0..player.hand().len()).collect::<Vec<_>>();
+    rng.shuffle(&mut cards);
+    let mut cards = cards.into_iter();
+
+    let mut choice = ActionChoice {
+        food_card: match cards.next() {
+            Some(card) => card,
+            None => return None,
+        },
+        population_growths: Vec::new(),
+        body_growths: Vec::new(),
+        boards: Vec::new(),
+        traits: Vec::new(),
+    };
+    // The population and body size of each species, after this choice.
+    let mut species = player.domain()
+                            .iter()
+                            .map(|s| (s.population(), s.body_size()))
+                            .collect::<Vec<_>>();
+
+    while let Some(card) = cards.next() {
+        // A species is needed for anything but a new board.
+        let option = if species.is_empty() { 0 } else { rng.gen_range(0, 4) };
+        match option {
+            0 => {
+                let trait_card_indeces = if rules.max_traits > 0 && rng.gen() {
+                    cards.next().into_iter().collect()
+                } else {
+                    Vec::new()
+                };
+                choice.boards.push(BoardTrade {
+                    card_index: card,
+                    trait_card_indeces: trait_card_indeces,
+                });
+                species.push((1, 0));
+            }
+            1 => {
+                let open = (0..species.len()).filter(|&i| species[i].0 < rules.max_population).collect::<Vec<_>>();
+                if let Some(&i) = rng.choose(&open) {
+                    species[i].0 += 1;
+                    choice.population_growths.push(Growth {
+                        species_index: i,
+                        card_index: card,
+                    });
+                }
+            }
+            2 => {
+                let open = (0..species.len()).filter(|&i| species[i].1 < rules.max_body_size).collect::<Vec<_>>();
+                if let Some(&i) = rng.choose(&open) {
+                    species[i].1 += 1;
+                    choice.body_growths.push(Growth {
+                        species_index: i,
+                        card_index: card,
+                    });
+                }
+            }
+            _ => {
+                // Keep the card for later.
+            }
+        }
+    }
+    Some(choice)
+}
@@ -0,0 +1,469 @@
+use std::collections::HashMap;
+use error::Error;
+use game::*;
+use interact::*;
+use object::*;
+use silly::*;
+use strategies::simulate;
+
+/// The most positions a `Solver` searches per choice by default.
+pub const DEFAULT_POSITIONS: usize = 20000;
+
+/// Solves the rest of a turn of feeding for the food margin.
+///
+/// Once the cards are revealed nothing about feeding is hidden, the
+/// players' hands and the deck don't change what a feeding choice does. The
+/// solver searches every line of choices to the end of the turn, remembering
+/// the value of each position it has solved, as the food and population of
+/// every species, the food on the board and whose turn it is.
+///
+/// Each player is worth the food their species have eaten or stored by the
+/// end of the turn, and makes the choice which leaves them the furthest
+/// ahead of the best of the other players. With two players this is
+/// minimax. This is a food margin, not the score: the population and traits
+/// a species loses or keeps, and the cards dealt for extinctions, aren't
+/// counted, so a choice the solver calls optimal can still lose points.
+/// Choices are made through `Auto`, as the dealer makes them, so a player
+/// only chooses when they have more than one thing to do.
+///
+/// Actions are taken like `Silly`, and so is feeding when there are too
+/// many positions to search.
+///
+/// # Examples
+///
+/// ```rust
+/// use evolution::game::*;
+/// use evolution::interact::*;
+/// use evolution::object::*;
+/// use evolution::strategies::Solver;
+///
+/// let mut state = GameState::with_seed(3, 1).unwrap();
+/// state.players_mut()[0].domain_mut().add(Placement::Right);
+/// state.players_mut()[0].domain_mut()[0].evolve(Trait::Carnivore).unwrap();
+/// state.players_mut()[1].domain_mut().add(Placement::Right);
+/// state.players_mut()[2].domain_mut().add(Placement::Right);
+/// state.board_mut().push_food(FoodToken);
+///
+/// let solution = Solver::default().solve(&state).unwrap();
+/// assert_eq!(Some(FeedChoice::Attack(0, 0, 0)), solution.best());
+/// assert_eq!(Some(2), solution.regret(FeedChoice::Abstain));
+/// ```
+#[derive(Debug)]
+pub struct Solver {
+    positions: usize,
+}
+
+impl Solver {
+    /// Creates a solver which gives up after searching the given number of
+    /// positions.
+    pub fn new(positions: usize) -> Self {
+        Solver {
+            positions: positions,
+        }
+    }
+
+    /// Solves the rest of the game's turn of feeding for the current
+    /// player.
+    ///
+    /// # Returns
+    ///
+    /// This function returns `None` if the turn is over, or there are more
+    /// positions than this solver searches.
+    pub fn solve(&self, game: &GameState) -> Option<Solution> {
+        if game.turn_is_over() || !game.has_current_player() {
+            return None
+        }
+        // The deck only matters for the cards dealt for an extinction.
+        let mut root = game.detached();
+        let cards = root.deck().len();
+        root.deals(cards);
+
+        let mut search = Search {
+            ids: root.players().iter().map(|p| p.id()).collect(),
+            table: HashMap::new(),
+            positions: self.positions,
+        };
+        let mover = search.mover(&root);
+        let mut values = Vec::new();
+        for (choice, child) in children(&root) {
+            let worths = match search.solve(&child) {
+                Some(worths) => worths,
+                None => return None,
+            };
+            if let Some(choice) = choice {
+                values.push((choice, margin(&worths, mover)));
+            }
+        }
+        let line = match search.line(&root) {
+            Some(line) => line,
+            None => return None,
+        };
+        Some(Solution {
+            player: root.current_player().id(),
+            values: values,
+            line: line,
+            positions: search.table.len(),
+        })
+    }
+}
+
+impl Default for Solver {
+    fn default() -> Self {
+        Solver::new(DEFAULT_POSITIONS)
+    }
+}
+
+impl Chooser for Solver {
+    fn start(&mut self, _: &DealObservation) {
+        // Do nothing.
+    }
+
+    fn info(&self) -> Option<&str> {
+        Some("solver")
+    }
+}
+
+impl Choose<ActionObservation, ActionChoice> for Solver {
+    fn choose(&mut self, observation: &ActionObservation) -> Result<Option<ActionChoice>, Error> {
+        Silly.choose(observation)
+    }
+}
+
+impl Choose<FeedObservation, FeedChoice> for Solver {
+    fn choose(&mut self, observation: &FeedObservation) -> Result<Option<FeedChoice>, Error> {
+        let game = simulate::feed_game(observation, Vec::new());
+        match self.solve(&game).and_then(|solution| solution.best()) {
+            Some(choice) => Ok(Some(choice)),
+            None => Silly.choose(observation),
+        }
+    }
+}
+
+/// The solved turn of feeding, from the current player's view.
+///
+/// Values are how far ahead of the best of the other players the current
+/// player ends the turn, in food, when everyone maximizes their own food
+/// margin after the choice.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Solution {
+    player: Id,
+    values: Vec<(FeedChoice, i64)>,
+    line: Vec<(Id, Option<FeedChoice>)>,
+    positions: usize,
+}
+
+impl Solution {
+    /// Returns the id of the player choosing.
+    pub fn player(&self) -> Id {
+        self.player
+    }
+
+    /// Returns every legal choice with it's value, in the order of
+    /// `FeedObservation::choices`.
+    pub fn values(&self) -> &[(FeedChoice, i64)] {
+        &self.values
+    }
+
+    /// Returns the first choice with the best value, or `None` if the
+    /// player has no choices and will be skipped.
+    pub fn best(&self) -> Option<FeedChoice> {
+        self.value().and_then(|best| {
+            self.values.iter().find(|&&(_, v)| v == best).map(|&(c, _)| c)
+        })
+    }
+
+    /// Returns the best value of any choice.
+    pub fn value(&self) -> Option<i64> {
+        self.values.iter().map(|&(_, v)| v).max()
+    }
+
+    /// Returns the value of the given choice, or `None` if it isn't legal.
+    pub fn value_of(&self, choice: FeedChoice) -> Option<i64> {
+        self.values.iter().find(|&&(c, _)| c == choice).map(|&(_, v)| v)
+    }
+
+    /// Returns how much food worse off the given choice leaves the player
+    /// than the best choice, or `None` if it isn't legal.
+    pub fn regret(&self, choice: FeedChoice) -> Option<i64> {
+        match (self.value(), self.value_of(choice)) {
+            (Some(best), Some(value)) => Some(best - value),
+            _ => None,
+        }
+    }
+
+    /// Returns the choices every player makes, in turn, when everyone plays
+    /// optimally for the rest of the turn. A choice is `None` when the
+    /// player is skipped.
+    pub fn line(&self) -> &[(Id, Option<FeedChoice>)] {
+        &self.line
+    }
+
+    /// Returns the number of positions searched.
+    pub fn positions(&self) -> usize {
+        self.positions
+    }
+}
+
+/// Everything about a game which changes what feeding can do.
+#[derive(Debug, PartialEq, Eq, Hash)]
+struct Position {
+    food: u64,
+    current: Option<usize>,
+    skipped: Vec<usize>,
+    players: Vec<(Id, Vec<(u64, u64, u64, u64, Vec<Trait>)>)>,
+}
+
+impl Position {
+    fn of(game: &GameState) -> Self {
+        Position {
+            food: game.board().food_count(),
+            current: if game.has_current_player() { Some(game.current_player_idx()) } else { None },
+            skipped: game.skipped(),
+            players: game.players().iter().map(|player| {
+                let domain = player.domain().iter().map(|s| {
+                    (s.population(), s.body_size(), s.food_count(), s.fat_count(), s.traits().to_vec())
+                }).collect();
+                (player.id(), domain)
+            }).collect(),
+        }
+    }
+}
+
+/// A search through the positions of a turn, with the worth of each player
+/// at the end of the turn, indexed like `ids`, for every position solved.
+struct Search {
+    ids: Vec<Id>,
+    table: HashMap<Position, Vec<i64>>,
+    positions: usize,
+}
+
+impl Search {
+    /// Returns the index of the current player in `ids`.
+    fn mover(&self, game: &GameState) -> usize {
+        let id = game.current_player().id();
+        self.ids.iter().position(|&i| i == id).expect("unknown player")
+    }
+
+    /// Returns the food and fat of each player's species, ejected players
+    /// are worth nothing.
+    fn worths(&self, game: &GameState) -> Vec<i64> {
+        self.ids.iter().map(|&id| {
+            game.players().iter().find(|p| p.id() == id).map_or(0, |player| {
+                player.domain().iter().fold(0, |a, s| a + (s.food_count() + s.fat_count()) as i64)
+            })
+        }).collect()
+    }
+
+    /// Returns the worths of each player at the end of the turn, when
+    /// everyone plays optimally from this position, or `None` if there are
+    /// too many positions.
+    fn solve(&mut self, game: &GameState) -> Option<Vec<i64>> {
+        if game.turn_is_over() || !game.has_current_player() {
+            return Some(self.worths(game))
+        }
+        let position = Position::of(game);
+        if let Some(worths) = self.table.get(&position) {
+            return Some(worths.clone())
+        }
+        if self.table.len() >= self.positions {
+            return None
+        }
+
+        let mover = self.mover(game);
+        let mut best: Option<Vec<i64>> = None;
+        for (_, child) in children(game) {
+            let worths = match self.solve(&child) {
+                Some(worths) => worths,
+                None => return None,
+            };
+            if best.as_ref().map_or(true, |b| margin(&worths, mover) > margin(b, mover)) {
+                best = Some(worths);
+            }
+        }
+        let worths = best.unwrap_or_else(|| self.worths(game));
+        self.table.insert(position, worths.clone());
+        Some(worths)
+    }
+
+    /// Returns the choices made by each player in turn, playing optimally
+    /// from this position to the end of the turn.
+    fn line(&mut self, game: &GameState) -> Option<Vec<(Id, Option<FeedChoice>)>> {
+        let mut line = Vec::new();
+        let mut game = game.detached();
+        while !game.turn_is_over() && game.has_current_player() {
+            let mover = self.mover(&game);
+            let player = game.current_player().id();
+            let mut best: Option<(i64, GameState)> = None;
+            for (_, child) in children(&game) {
+                let value = match self.solve(&child) {
+                    Some(worths) => margin(&worths, mover),
+                    None => return None,
+                };
+                if best.as_ref().map_or(true, |&(v, _)| value > v) {
+                    best = Some((value, child));
+                }
+            }
+            let child = match best {
+                Some((_, child)) => child,
+                None => break,
+            };
+            // `Auto` may have made the choice, so take it from the log.
+            let choice = child.events().iter().rev().filter_map(|event| match *event {
+                Event::Feed { choice, .. } => Some(choice),
+                Event::Skip { .. } => Some(None),
+                _ => None,
+            }).next().and_then(|choice| choice);
+            line.push((player, choice));
+            game = child.detached();
+        }
+        Some(line)
+    }
+}
+
+/// Returns how far ahead of the best of the other players the player at
+/// the given index is.
+fn margin(worths: &[i64], index: usize) -> i64 {
+    let best_other = worths.iter()
+                           .enumerate()
+                           .filter(|&(i, _)| i != index)
+                           .map(|(_, &w)| w)
+                           .max()
+                           .unwrap_or(0);
+    worths[index] - best_other
+}
+
+/// Returns the game after each of the current player's legal choices is
+/// made through `Auto`, or after they are skipped when they have none.
+fn children(game: &GameState) -> Vec<(Option<FeedChoice>, GameState)> {
+    let observation: FeedObservation = game.observe();
+    let mut choices = observation.choices().into_iter().map(Some).collect::<Vec<_>>();
+    if choices.is_empty() {
+        choices.push(None);
+    }
+    choices.into_iter().filter_map(|choice| {
+        let mut child = game.detached();
+        let mut chooser = choice.unwrap_or(FeedChoice::Abstain);
+        match step::Feed(&mut child, &mut Auto(&mut chooser)).step() {
+            Ok(()) => Some((choice, child)),
+            Err(_) => None,
+        }
+    }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use game::*;
+    use interact::*;
+    use object::*;
+    use strategies::*;
+
+    #[test]
+    fn attacks_instead_of_abstaining() {
+        let mut game = game_with_players(3, &|player| {
+            player.domain_mut().add(Placement::Right);
+            if player.id() == 1 {
+                player.domain_mut()[0].evolve(Trait::Carnivore).unwrap();
+                player.domain_mut()[0].grow().unwrap();
+            }
+        });
+        game.board_mut().push_food(FoodToken);
+
+        let solution = Solver::default().solve(&game).unwrap();
+
+        match solution.best() {
+            Some(FeedChoice::Attack(0, _, 0)) => {},
+            c => panic!("expected an attack, got {:?}", c),
+        }
+        assert_eq!(1, solution.player());
+        assert_eq!(Some(2), solution.regret(FeedChoice::Abstain));
+        assert_eq!(Some(1), solution.value());
+        assert_eq!(None, solution.regret(FeedChoice::Feed(0)));
+    }
+
+    #[test]
+    fn attacks_the_species_which_would_eat_the_most() {
+        // Killing the lone species leaves the bigger one to eat the rest.
+        let mut game = game_with_players(3, &|player| {
+            player.domain_mut().add(Placement::Right);
+            match player.id() {
+                1 => {
+                    player.domain_mut()[0].evolve(Trait::Carnivore).unwrap();
+                    player.domain_mut()[0].grow().unwrap();
+                },
+                3 => {
+                    player.domain_mut()[0].breed().unwrap();
+                },
+                _ => {},
+            }
+        });
+        game.board_mut().push_foods(vec![FoodToken, FoodToken, FoodToken]);
+
+        let solution = Solver::default().solve(&game).unwrap();
+
+        assert_eq!(Some(FeedChoice::Attack(0, 1, 0)), solution.best());
+        assert_eq!(Some(0), solution.value());
+        assert_eq!(Some(1), solution.regret(FeedChoice::Attack(0, 0, 0)));
+        assert_eq!(Some(2), solution.regret(FeedChoice::Abstain));
+        assert_eq!(Some(FeedChoice::Attack(0, 1, 0)), solution.line()[0].1);
+        assert_eq!(3, solution.line().len());
+    }
+
+    #[test]
+    fn forced_choices_have_no_regret() {
+        // `Auto` stores as much as it can for the only fat tissue species.
+        let mut game = game_with_players(3, &|player| {
+            player.domain_mut().add(Placement::Right);
+            if player.id() == 1 {
+                player.domain_mut()[0].evolve(Trait::FatTissue).unwrap();
+                player.domain_mut()[0].grow().unwrap();
+                player.domain_mut()[0].grow().unwrap();
+                player.domain_mut().add(Placement::Right);
+            }
+        });
+        game.board_mut().push_foods(vec![FoodToken, FoodToken]);
+
+        let solution = Solver::default().solve(&game).unwrap();
+
+        assert!(solution.values().iter().all(|&(c, _)| solution.regret(c) == Some(0)));
+        assert_eq!(Some(2), solution.value());
+        assert_eq!(&[(1, Some(FeedChoice::Store(0, 2)))], solution.line());
+    }
+
+    #[test]
+    fn gives_up_on_too_many_positions() {
+        let mut game = game_with_players(3, &|player| {
+            player.domain_mut().add(Placement::Right);
+            player.domain_mut().add(Placement::Right);
+        });
+        game.board_mut().push_foods(vec![FoodToken; 4]);
+
+        assert!(Solver::new(1).solve(&game).is_none());
+        assert!(Solver::default().solve(&game).is_some());
+    }
+
+    #[test]
+    fn nothing_to_solve_once_the_turn_is_over() {
+        let game = game_with_players(3, &|player| {
+            player.domain_mut().add(Placement::Right);
+        });
+
+        assert!(Solver::default().solve(&game).is_none());
+    }
+
+    #[test]
+    fn chooses_like_the_solution() {
+        let mut game = game_with_players(3, &|player| {
+            player.domain_mut().add(Placement::Right);
+            if player.id() == 1 {
+                player.domain_mut()[0].evolve(Trait::Carnivore).unwrap();
+                player.domain_mut()[0].grow().unwrap();
+            }
+        });
+        game.board_mut().push_food(FoodToken);
+        let observation: FeedObservation = game.observe();
+
+        let choice = Solver::default().choose(&observation).unwrap();
+
+        assert_eq!(Solver::default().solve(&game).unwrap().best(), choice);
+    }
+}
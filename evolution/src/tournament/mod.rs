@@ -0,0 +1,477 @@
+use std::cmp::{self, Ordering};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::{Arc, Mutex, mpsc};
+use std::thread;
+use rand::{Rng, SeedableRng};
+use rand::isaac::Isaac64Rng;
+use serde_json::{self as json, Value};
+use error::{Error, RuleError};
+use game::*;
+use interact::*;
+
+/// A schedule of many games between a set of named entrants.
+///
+/// Each game seats a rotating group of entrants, in an order shuffled for
+/// that game, and is given it's own seed derived from the seed of the
+/// tournament, so a whole tournament can be replayed from it's seed.
+///
+/// # Examples
+///
+/// ```rust
+/// use evolution::silly::Silly;
+/// use evolution::tournament::Tournament;
+///
+/// let names = vec!["a".into(), "b".into(), "c".into(), "d".into()];
+/// let tournament = Tournament::new(names, 8, 4, 1).unwrap();
+/// let standings = tournament.run_parallel(2, |_| Silly).unwrap();
+/// println!("{}", standings);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Tournament {
+    names: Vec<String>,
+    games: usize,
+    players_per_game: usize,
+    seed: u64,
+}
+
+/// A single game of a tournament.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fixture {
+    /// The seed for the game's deck.
+    pub seed: u64,
+    /// The indices of the entrants playing, in the game's starting order.
+    pub seating: Vec<usize>,
+}
+
+impl Tournament {
+    /// Creates a new tournament of `games` games, with `players_per_game`
+    /// of the named entrants seated at each game.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `PlayerCount` error if `players_per_game`
+    /// isn't a legal number of players, or if there are too few entrants to
+    /// fill a game.
+    pub fn new(names: Vec<String>,
+               games: usize,
+               players_per_game: usize,
+               seed: u64)
+        -> Result<Self, Error>
+    {
+        if players_per_game < MIN_PLAYERS ||
+           players_per_game > MAX_PLAYERS ||
+           players_per_game > names.len()
+        {
+            return Err(Error::Rule(RuleError::PlayerCount(players_per_game)))
+        }
+
+        Ok(Tournament {
+            names: names,
+            games: games,
+            players_per_game: players_per_game,
+            seed: seed,
+        })
+    }
+
+    /// Returns the names of the entrants of this tournament.
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// Returns every game of this tournament. The group of entrants seated
+    /// rotates through the entrants from one game to the next, and the
+    /// group's seats are shuffled by the game's seed, so no entrant always
+    /// plays before another.
+    pub fn schedule(&self) -> Vec<Fixture> {
+        let mut rng = Isaac64Rng::from_seed(&[self.seed]);
+        let entrants = self.names.len();
+        (0..self.games).map(|i| {
+            let seed = rng.next_u64();
+            let mut seating = (0..self.players_per_game).map(|j| (i + j) % entrants).collect::<Vec<_>>();
+            Isaac64Rng::from_seed(&[seed]).shuffle(&mut seating);
+            Fixture {
+                seed: seed,
+                seating: seating,
+            }
+        }).collect()
+    }
+
+    /// Plays every game of this tournament one after another, with the given
+    /// choosers, one for each entrant. The choosers are moved into each game
+    /// they are seated at, and taken back out afterwards, so this works with
+    /// choosers like `Channel`s which can't be created on demand.
+    ///
+    /// Like a `Lobby`, an entrant ejected from a game is dropped, and sits out
+    /// the rest of the tournament. Their later games are played by the rest
+    /// of the entrants seated, or not played if too few are left.
+    pub fn run<C: Chooser>(&self, entrants: Vec<C>) -> Result<Standings, Error> {
+        if entrants.len() != self.names.len() {
+            return Err(Error::Rule(RuleError::PlayerCount(entrants.len())))
+        }
+
+        let mut pool: Vec<Option<C>> = entrants.into_iter().map(Some).collect();
+        let mut standings = Standings::new(self.names.clone());
+        for fixture in self.schedule() {
+            let fixture = Fixture {
+                seed: fixture.seed,
+                seating: fixture.seating.into_iter().filter(|&entrant| pool[entrant].is_some()).collect(),
+            };
+            if fixture.seating.len() < MIN_PLAYERS {
+                continue
+            }
+            let choosers = fixture.seating.iter().map(|&entrant| {
+                pool[entrant].take().expect("entrant seated twice")
+            }).collect();
+            let mut game = try!(Game::with_choosers(choosers, fixture.seed));
+            try!(game.play());
+            standings.add(&Outcome::new(&fixture, &game));
+            let survivors = game.players().iter().map(|p| p.id()).collect::<Vec<_>>();
+            for (id, chooser) in game.into_choosers() {
+                if survivors.contains(&id) {
+                    pool[fixture.seating[(id - 1) as usize]] = Some(chooser);
+                }
+            }
+        }
+        Ok(standings)
+    }
+
+    /// Plays every game of this tournament across the given number of worker
+    /// threads. Each worker creates fresh choosers for every game it plays,
+    /// by calling `factory` with the index of each seated entrant.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if a worker thread panics.
+    pub fn run_parallel<C, F>(&self, threads: usize, factory: F) -> Result<Standings, Error>
+        where C: Chooser + 'static,
+              F: Fn(usize) -> C + Send + Sync + 'static,
+    {
+        let fixtures = Arc::new(Mutex::new(self.schedule().into_iter()));
+        let factory = Arc::new(factory);
+        let (sender, receiver) = mpsc::channel();
+
+        let mut workers = Vec::new();
+        for i in 0..cmp::max(threads, 1) {
+            let fixtures = fixtures.clone();
+            let factory = factory.clone();
+            let sender = sender.clone();
+            let worker = thread::Builder::new().name(format!("tournament{}", i)).spawn(move || {
+                loop {
+                    let fixture = match fixtures.lock().expect("failed to get lock").next() {
+                        Some(fixture) => fixture,
+                        None => break,
+                    };
+                    let choosers = fixture.seating.iter().map(|&entrant| factory(entrant)).collect();
+                    let outcome = Game::<C>::with_choosers(choosers, fixture.seed).and_then(|mut game| {
+                        try!(game.play());
+                        Ok(Outcome::new(&fixture, &game))
+                    });
+                    sender.send(outcome).expect("failed to send outcome");
+                }
+            });
+            workers.push(try!(worker));
+        }
+        drop(sender);
+
+        let mut standings = Standings::new(self.names.clone());
+        for outcome in receiver {
+            standings.add(&try!(outcome));
+        }
+        for worker in workers {
+            worker.join().expect("tournament worker panicked");
+        }
+        Ok(standings)
+    }
+}
+
+/// The result of a single game of a tournament.
+#[derive(Debug, Clone)]
+pub struct Outcome {
+    /// The final score of each entrant still in the game at the end.
+    pub scores: Vec<(usize, u64)>,
+    /// The entrants who were ejected from the game.
+    pub ejected: Vec<usize>,
+}
+
+impl Outcome {
+    /// Collects the outcome of a game played for the given fixture.
+    pub fn new<C: Chooser>(fixture: &Fixture, game: &Game<C>) -> Self {
+        let mut scores = Vec::new();
+        let mut ejected = Vec::new();
+        for (seat, &entrant) in fixture.seating.iter().enumerate() {
+            let id = (seat + 1) as Id;
+            match game.players().iter().find(|p| p.id() == id) {
+                Some(player) => scores.push((entrant, player.score())),
+                None => ejected.push(entrant),
+            }
+        }
+        Outcome {
+            scores: scores,
+            ejected: ejected,
+        }
+    }
+
+    /// Returns the entrants with the highest score, more than one entrant
+    /// wins a game when they tie.
+    pub fn winners(&self) -> Vec<usize> {
+        let best = self.scores.iter().map(|&(_, score)| score).max();
+        self.scores.iter().filter(|&&(_, score)| Some(score) == best).map(|&(e, _)| e).collect()
+    }
+}
+
+/// The record of a single entrant across a tournament.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Record {
+    pub name: String,
+    pub games: usize,
+    pub wins: usize,
+    pub total_score: u64,
+    pub ejections: usize,
+}
+
+impl Record {
+    /// The average score of this entrant, counting a game they were ejected
+    /// from as a score of zero.
+    pub fn average_score(&self) -> f64 {
+        if self.games == 0 {
+            0.0
+        } else {
+            self.total_score as f64 / self.games as f64
+        }
+    }
+}
+
+/// The aggregated records of every entrant in a tournament.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Standings {
+    records: Vec<Record>,
+}
+
+impl Standings {
+    /// Creates empty standings for the named entrants.
+    pub fn new(names: Vec<String>) -> Self {
+        Standings {
+            records: names.into_iter().map(|name| {
+                Record { name: name, ..Record::default() }
+            }).collect(),
+        }
+    }
+
+    /// Adds the outcome of a game to these standings.
+    pub fn add(&mut self, outcome: &Outcome) {
+        for &(entrant, score) in outcome.scores.iter() {
+            self.records[entrant].games += 1;
+            self.records[entrant].total_score += score;
+        }
+        for &entrant in outcome.ejected.iter() {
+            self.records[entrant].games += 1;
+            self.records[entrant].ejections += 1;
+        }
+        for entrant in outcome.winners() {
+            self.records[entrant].wins += 1;
+        }
+    }
+
+    /// Returns the records of every entrant, in the order they were entered.
+    pub fn records(&self) -> &[Record] {
+        &self.records
+    }
+
+    /// Returns the records of every entrant, ranked by wins and then by
+    /// average score.
+    pub fn ranked(&self) -> Vec<&Record> {
+        let mut ranked: Vec<&Record> = self.records.iter().collect();
+        ranked.sort_by(|a, b| {
+            match b.wins.cmp(&a.wins) {
+                Ordering::Equal => {
+                    b.average_score().partial_cmp(&a.average_score()).unwrap_or(Ordering::Equal)
+                },
+                o => o,
+            }
+        });
+        ranked
+    }
+
+    /// Returns these standings as a JSON array of records, ranked like
+    /// `ranked`.
+    pub fn to_json(&self) -> String {
+        let records = self.ranked().into_iter().map(|record| {
+            let mut object = BTreeMap::new();
+            object.insert("name".to_string(), Value::String(record.name.clone()));
+            object.insert("games".to_string(), Value::U64(record.games as u64));
+            object.insert("wins".to_string(), Value::U64(record.wins as u64));
+            object.insert("average_score".to_string(), Value::F64(record.average_score()));
+            object.insert("ejections".to_string(), Value::U64(record.ejections as u64));
+            Value::Object(object)
+        }).collect();
+        json::to_string(&Value::Array(records)).expect("failed to serialize standings")
+    }
+}
+
+impl fmt::Display for Standings {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        try!(writeln!(f, "{:>4} {:<20} {:>6} {:>6} {:>10} {:>10}",
+                      "rank", "name", "games", "wins", "avg score", "ejections"));
+        for (i, record) in self.ranked().into_iter().enumerate() {
+            try!(writeln!(f, "{:>4} {:<20} {:>6} {:>6} {:>10.2} {:>10}",
+                          i + 1,
+                          record.name,
+                          record.games,
+                          record.wins,
+                          record.average_score(),
+                          record.ejections));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use silly::*;
+
+    fn names(n: usize) -> Vec<String> {
+        (0..n).map(|i| format!("silly{}", i)).collect()
+    }
+
+    #[test]
+    fn too_few_entrants() {
+        assert!(Tournament::new(names(3), 1, 4, 0).is_err());
+    }
+
+    #[test]
+    fn illegal_players_per_game() {
+        assert!(Tournament::new(names(10), 1, 2, 0).is_err());
+        assert!(Tournament::new(names(10), 1, 9, 0).is_err());
+    }
+
+    #[test]
+    fn schedule_rotates_seating() {
+        let tournament = Tournament::new(names(4), 3, 3, 0).unwrap();
+        let schedule = tournament.schedule();
+
+        assert_eq!(3, schedule.len());
+        let groups = schedule.iter().map(|fixture| {
+            let mut group = fixture.seating.clone();
+            group.sort();
+            group
+        }).collect::<Vec<_>>();
+        assert_eq!(vec![vec![0, 1, 2], vec![1, 2, 3], vec![0, 2, 3]], groups);
+    }
+
+    #[test]
+    fn schedule_shuffles_seats() {
+        let tournament = Tournament::new(names(4), 40, 4, 0).unwrap();
+        let schedule = tournament.schedule();
+
+        // Every entrant starts some game.
+        for entrant in 0..4 {
+            assert!(schedule.iter().any(|fixture| fixture.seating[0] == entrant));
+        }
+    }
+
+    #[test]
+    fn schedule_depends_on_seed() {
+        let a = Tournament::new(names(4), 3, 4, 1).unwrap();
+        let b = Tournament::new(names(4), 3, 4, 2).unwrap();
+
+        assert_eq!(a.schedule(), a.schedule());
+        assert!(a.schedule() != b.schedule());
+    }
+
+    #[test]
+    fn run_counts_games() {
+        let tournament = Tournament::new(names(4), 4, 3, 0).unwrap();
+
+        let standings = tournament.run(vec![Silly, Silly, Silly, Silly]).unwrap();
+
+        for record in standings.records() {
+            assert_eq!(3, record.games);
+        }
+        let wins = standings.records().iter().fold(0, |sum, r| sum + r.wins);
+        assert!(wins >= 4);
+    }
+
+    #[test]
+    fn run_needs_a_chooser_per_entrant() {
+        let tournament = Tournament::new(names(4), 4, 3, 0).unwrap();
+
+        assert!(tournament.run(vec![Silly, Silly, Silly]).is_err());
+    }
+
+    /// A chooser which never gives a choice, and is ejected right away.
+    #[derive(Debug)]
+    struct Broken;
+
+    impl Chooser for Broken {
+        fn start(&mut self, _: &DealObservation) {}
+
+        fn info(&self) -> Option<&str> {
+            None
+        }
+    }
+
+    impl Choose<ActionObservation, ActionChoice> for Broken {
+        fn choose(&mut self, _: &ActionObservation) -> Result<Option<ActionChoice>, Error> {
+            Err(Error::Timeout)
+        }
+    }
+
+    impl Choose<FeedObservation, FeedChoice> for Broken {
+        fn choose(&mut self, _: &FeedObservation) -> Result<Option<FeedChoice>, Error> {
+            Err(Error::Timeout)
+        }
+    }
+
+    #[test]
+    fn run_drops_ejected_entrants() {
+        let tournament = Tournament::new(names(4), 8, 3, 0).unwrap();
+        let mut entrants: Vec<Box<Chooser + Send>> = vec![Box::new(Broken)];
+        for _ in 0..3 {
+            entrants.push(Box::new(Silly));
+        }
+
+        let standings = tournament.run(entrants).unwrap();
+
+        assert_eq!(1, standings.records()[0].games);
+        assert_eq!(1, standings.records()[0].ejections);
+        for record in &standings.records()[1..] {
+            assert_eq!(0, record.ejections);
+        }
+    }
+
+    #[test]
+    fn run_parallel_matches_run() {
+        let tournament = Tournament::new(names(5), 6, 4, 3).unwrap();
+
+        let sequential = tournament.run(vec![Silly, Silly, Silly, Silly, Silly]).unwrap();
+        let parallel = tournament.run_parallel(3, |_| Silly).unwrap();
+
+        assert_eq!(sequential, parallel);
+    }
+
+    #[test]
+    fn winners_include_ties() {
+        let outcome = Outcome {
+            scores: vec![(0, 3), (1, 5), (2, 5)],
+            ejected: vec![3],
+        };
+
+        assert_eq!(vec![1, 2], outcome.winners());
+    }
+
+    #[test]
+    fn ejections_count_as_games() {
+        let mut standings = Standings::new(names(4));
+        standings.add(&Outcome {
+            scores: vec![(0, 3), (1, 5), (2, 1)],
+            ejected: vec![3],
+        });
+
+        assert_eq!(1, standings.records()[3].games);
+        assert_eq!(1, standings.records()[3].ejections);
+        assert_eq!(0.0, standings.records()[3].average_score());
+        assert_eq!("silly1", standings.ranked()[0].name);
+    }
+}
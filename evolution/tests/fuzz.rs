@@ -0,0 +1,497 @@
+//! Plays many random games, checking the invariants of the game after every
+//! step. Games are stepped on their `GameState` directly, so the changes
+//! each step made are seen, and food is checked to be kept exactly.
+//!
+//! Set `EVOLUTION_FUZZ_GAMES` to play more, or fewer games than the default
+//! of 200 for each rule book. When an invariant breaks, the game is shrunk to
+//! the fewest players which still break it, then its event log is replayed up
+//! to the broken step, and its action choices simplified while they still
+//! break one. The test panics with the shrunk log.
+
+extern crate rand;
+extern crate evolution;
+
+use std::cmp;
+use std::collections::HashMap;
+use std::env;
+use std::fmt;
+use rand::{Rng, SeedableRng};
+use rand::isaac::Isaac64Rng;
+use evolution::error::{Error, ChoiceError};
+use evolution::game::*;
+use evolution::game::context::Outcome;
+use evolution::interact::*;
+use evolution::object::*;
+use evolution::strategies::simulate;
+
+/// The most cards in a hand to enumerate every action for.
+const ENUMERATED_HAND: usize = 4;
+
+/// Makes random legal choices under the game's rules, from every legal
+/// choice where it's quick enough to find them all.
+struct Fuzzer(Isaac64Rng);
+
+impl fmt::Debug for Fuzzer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Fuzzer")
+    }
+}
+
+impl Choose<ActionObservation, ActionChoice> for Fuzzer {
+    fn choose(&mut self, observation: &ActionObservation) -> Result<Option<ActionChoice>, Error> {
+        if observation.current_player.hand().len() <= ENUMERATED_HAND {
+            let mut choices = vec![];
+            observation.visit_action_choices(&mut |choice| {
+                choices.push(choice);
+                true
+            });
+            return Ok(self.0.choose(&choices).cloned())
+        }
+        let choice = simulate::random_action(observation, &mut self.0);
+        if let Some(ref choice) = choice {
+            assert!(observation.is_legal(choice), "random action {:?} is illegal", choice);
+        }
+        Ok(choice)
+    }
+}
+
+impl Choose<FeedObservation, FeedChoice> for Fuzzer {
+    fn choose(&mut self, observation: &FeedObservation) -> Result<Option<FeedChoice>, Error> {
+        Ok(self.0.choose(&observation.choices()).cloned())
+    }
+}
+
+/// A step taken, with what it changed.
+enum Stepped {
+    Deal,
+    Action,
+    Reveal(Vec<Card>, Outcome),
+    Feed(Outcome),
+    Bag,
+}
+
+/// What's counted in the game before a step, to compare with after it.
+struct Count {
+    food: u64,
+    board_food: u64,
+    players: HashMap<Id, u64>,
+    domains: HashMap<Id, Vec<Species>>,
+    starving_fat: u64,
+    cards: usize,
+    deck: usize,
+    kicked: usize,
+}
+
+impl Count {
+    fn new(state: &GameState) -> Self {
+        let mut players = HashMap::new();
+        let mut domains = HashMap::new();
+        let mut starving_fat = 0;
+        for player in state.players() {
+            let mut food = player.bag_count();
+            for species in player.domain().iter() {
+                food += species.food_count() + species.fat_count();
+                if species.food_count() == 0 {
+                    starving_fat += species.fat_count();
+                }
+            }
+            players.insert(player.id(), food);
+            domains.insert(player.id(), player.domain().to_vec());
+        }
+        Count {
+            food: state.board().food_count() + players.values().sum::<u64>(),
+            board_food: state.board().food_count(),
+            players: players,
+            domains: domains,
+            starving_fat: starving_fat,
+            cards: cards_in_play(state).len(),
+            deck: state.deck().len(),
+            kicked: state.kicked().len(),
+        }
+    }
+}
+
+/// The cards in the deck, players' hands and on the board.
+fn cards_in_play(state: &GameState) -> Vec<Card> {
+    let mut cards = state.deck().to_vec();
+    for player in state.players() {
+        cards.extend(player.hand().iter().cloned());
+    }
+    if let Some(board_cards) = state.board().cards() {
+        cards.extend(board_cards.iter().cloned());
+    }
+    cards.sort();
+    cards
+}
+
+/// Checks the invariants which hold at any time in a game dealt from `deck`.
+fn check_state(state: &GameState, deck: &[Card]) -> Result<(), String> {
+    let rules = state.rules();
+
+    let mut ids = state.players().iter().map(|p| p.id()).collect::<Vec<_>>();
+    ids.extend(state.kicked().iter().map(|k| k.id));
+    let count = ids.len();
+    ids.sort();
+    ids.dedup();
+    if ids.len() != count {
+        return Err("player ids aren't unique".into())
+    }
+
+    for player in state.players() {
+        for (i, species) in player.domain().iter().enumerate() {
+            let at = format!("player {} species {}", player.id(), i);
+            if species.population() == 0 || species.population() > rules.max_population {
+                return Err(format!("{} has population {}", at, species.population()))
+            }
+            if species.body_size() > rules.max_body_size {
+                return Err(format!("{} has body size {}", at, species.body_size()))
+            }
+            if species.food_count() > species.population() {
+                return Err(format!("{} has more food than population", at))
+            }
+            if species.fat_count() != 0 &&
+               (!species.has_trait(Trait::FatTissue) || species.fat_count() > species.body_size())
+            {
+                return Err(format!("{} has {} fat", at, species.fat_count()))
+            }
+            let mut traits = species.traits().to_vec();
+            traits.sort();
+            traits.dedup();
+            if traits.len() != species.traits().len() || traits.len() > rules.max_traits {
+                return Err(format!("{} has traits {:?}", at, species.traits()))
+            }
+        }
+    }
+
+    // Every card in play is a different card of the deck.
+    let mut unplayed = deck.to_vec();
+    for card in cards_in_play(state) {
+        match unplayed.iter().position(|c| *c == card) {
+            Some(i) => { unplayed.remove(i); },
+            None => return Err(format!("{:?} isn't a card of the deck", card)),
+        }
+    }
+
+    // Traits on species come from cards no longer in play.
+    let mut traits = HashMap::new();
+    for card in deck {
+        *traits.entry(card.trait_type()).or_insert(0) += 1;
+    }
+    for card in cards_in_play(state) {
+        *traits.get_mut(&card.trait_type()).unwrap() -= 1;
+    }
+    for player in state.players() {
+        for species in player.domain().iter() {
+            for trait_type in species.traits() {
+                let left = traits.entry(*trait_type).or_insert(0);
+                *left -= 1;
+                if *left < 0 {
+                    return Err(format!("too many {:?} traits", trait_type))
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks that a step didn't make cards from nothing, and that the food in
+/// the game changed by exactly what the step made, ate or lost.
+fn check_step(state: &GameState, before: &Count, stepped: &Stepped) -> Result<(), String> {
+    let after = Count::new(state);
+    if after.deck > before.deck || after.cards > before.cards {
+        return Err("cards were made".into())
+    }
+
+    let mut food = before.food as i64;
+    // Ejected players take their food with them.
+    for kick in &state.kicked()[before.kicked..] {
+        food -= before.players[&kick.id] as i64;
+    }
+    match *stepped {
+        Stepped::Deal => {},
+        // Trading away fat tissue discards it's fat.
+        Stepped::Action => {
+            if let Some(&Event::Action { player, choice: Some(ref choice) }) = state.events().last() {
+                for trade in &choice.traits {
+                    if let Some(species) = before.domains[&player].get(trade.species_index) {
+                        if species.traits().get(trade.trait_index) == Some(&Trait::FatTissue) {
+                            food -= species.fat_count() as i64;
+                        }
+                    }
+                }
+            }
+        },
+        Stepped::Reveal(ref cards, ref outcome) => {
+            // The watering hole never holds less than no food.
+            let mut board = before.board_food as i64;
+            for card in cards {
+                board = cmp::max(0, board + card.food_value());
+            }
+            food += board - before.board_food as i64;
+            food += outcome.meat_eaten() as i64 - outcome.food_lost() as i64;
+        },
+        // Meat comes from the food bank, outside the game.
+        Stepped::Feed(ref outcome) => {
+            food += outcome.meat_eaten() as i64 - outcome.food_lost() as i64;
+        },
+        // Species without food go extinct, losing their fat.
+        Stepped::Bag => food -= before.starving_fat as i64,
+    }
+    if after.food as i64 != food {
+        return Err(format!("food went from {} to {}, not {}", before.food, after.food, food))
+    }
+    Ok(())
+}
+
+/// Plays a game, checking every step. Returns the broken invariant, and the
+/// event log up to the step which broke it.
+fn fuzz(players: usize, seed: u64, rules: RulesConfig) -> Result<(), (Vec<Event>, String)> {
+    let mut rng = Isaac64Rng::from_seed(&[seed]);
+    let mut state = GameState::with_rules(players, seed, rules).unwrap();
+    let mut fuzzers = state.players().iter().map(|p| {
+        (p.id(), Fuzzer(Isaac64Rng::from_seed(&[rng.gen()])))
+    }).collect::<HashMap<_, _>>();
+    let deck = state.deck().to_vec();
+
+    macro_rules! step {
+        ($step:expr) => {{
+            let before = Count::new(&state);
+            let stepped = $step;
+            let mut checked = check_state(&state, &deck);
+            if checked.is_ok() {
+                checked = check_step(&state, &before, &stepped);
+            }
+            if let Err(broken) = checked {
+                return Err((state.events().to_vec(), broken))
+            }
+        }}
+    }
+
+    if let Err(broken) = check_state(&state, &deck) {
+        return Err((state.events().to_vec(), broken))
+    }
+    while !state.is_over() {
+        step!({
+            step::Deal(&mut state).step().expect("deal failed");
+            Stepped::Deal
+        });
+        for _ in 0..state.players().len() {
+            if !state.has_current_player() {
+                break
+            }
+            let fuzzer = fuzzers.get_mut(&state.current_player().id()).unwrap();
+            step!({
+                step::Action(&mut state, fuzzer).step().expect("action failed");
+                Stepped::Action
+            });
+        }
+        let cards = state.board().cards().map(|c| c.to_vec()).unwrap_or(vec![]);
+        step!({
+            let outcome = state.reveal();
+            Stepped::Reveal(cards, outcome)
+        });
+        while !state.turn_is_over() && state.has_current_player() {
+            let fuzzer = fuzzers.get_mut(&state.current_player().id()).unwrap();
+            step!({
+                let observation: FeedObservation = state.observe();
+                match fuzzer.choose(&observation).unwrap() {
+                    Some(choice) => Stepped::Feed(state.apply_feed(choice).expect("illegal feed")),
+                    None => {
+                        // The player is skipped.
+                        step::Feed(&mut state, fuzzer).step().expect("feed failed");
+                        Stepped::Feed(Outcome::new())
+                    },
+                }
+            });
+        }
+        step!({
+            step::Bag(&mut state).step().expect("bag failed");
+            Stepped::Bag
+        });
+    }
+    Ok(())
+}
+
+/// Shrinks a game which broke an invariant to the fewest players which still
+/// break one, then shrinks its event log, and panics with the log.
+fn minimize(players: usize, seed: u64, rules: RulesConfig, events: Vec<Event>, broken: String) -> ! {
+    let (mut players, mut events, mut broken) = (players, events, broken);
+    for fewer in rules.min_players..players {
+        if let Err((e, b)) = fuzz(fewer, seed, rules.clone()) {
+            players = fewer;
+            events = e;
+            broken = b;
+            break
+        }
+    }
+    let (events, broken) = shrink(events, broken);
+    let log = events.iter().map(|e| format!("{:?}", e)).collect::<Vec<_>>().join("\n");
+    panic!("{} players, seed {}, {:?}: {}\n{}", players, seed, rules, broken, log)
+}
+
+/// Shrinks the event log of a game which broke an invariant, replacing each
+/// action choice, from the last to the first, with only it's food card
+/// while the log still replays up to a broken invariant. The log is kept as
+/// is if it doesn't replay to one at all.
+fn shrink(events: Vec<Event>, broken: String) -> (Vec<Event>, String) {
+    let (mut events, mut broken) = match check_log(&events) {
+        Some(checked) => checked,
+        None => return (events, broken),
+    };
+    let mut i = events.len();
+    while i > 1 {
+        i -= 1;
+        let simpler = match events[i] {
+            Event::Action { player, choice: Some(ref choice) } => {
+                let simpler = ActionChoice {
+                    food_card: choice.food_card,
+                    population_growths: vec![],
+                    body_growths: vec![],
+                    boards: vec![],
+                    traits: vec![],
+                };
+                if simpler == *choice {
+                    continue
+                }
+                Event::Action { player: player, choice: Some(simpler) }
+            },
+            _ => continue,
+        };
+        let mut shrunk = events.clone();
+        shrunk[i] = simpler;
+        if let Some((e, b)) = check_log(&shrunk) {
+            events = e;
+            broken = b;
+            i = cmp::min(i, events.len());
+        }
+    }
+    (events, broken)
+}
+
+/// Replays an event log, checking every step like `fuzz` does. Returns the
+/// log up to the step which broke an invariant, and the broken invariant,
+/// or `None` if the log doesn't replay exactly, or breaks nothing.
+fn check_log(events: &[Event]) -> Option<(Vec<Event>, String)> {
+    let mut state = match events.first() {
+        Some(&Event::Start { seed, ref players, ref rules }) => {
+            match GameState::with_rules(players.len(), seed, rules.clone()) {
+                Ok(state) => state,
+                Err(_) => return None,
+            }
+        },
+        _ => return None,
+    };
+    let deck = state.deck().to_vec();
+    if let Err(broken) = check_state(&state, &deck) {
+        return Some((state.events().to_vec(), broken))
+    }
+
+    for (i, event) in events.iter().enumerate().skip(1) {
+        // Effects of the last step were recorded by replaying it.
+        if i < state.events().len() {
+            if state.events()[i] != *event {
+                return None
+            }
+            continue
+        }
+        let before = Count::new(&state);
+        let stepped = match *event {
+            Event::Deal { .. } => {
+                if step::Deal(&mut state).step().is_err() {
+                    return None
+                }
+                Stepped::Deal
+            },
+            Event::Action { ref choice, .. } => {
+                if step::Action(&mut state, &mut Recorded(choice.clone().map(Some))).step().is_err() {
+                    return None
+                }
+                Stepped::Action
+            },
+            Event::Reveal { .. } => {
+                let cards = state.board().cards().map(|c| c.to_vec()).unwrap_or(vec![]);
+                let outcome = state.reveal();
+                Stepped::Reveal(cards, outcome)
+            },
+            Event::Feed { choice: Some(choice), .. } => {
+                match state.apply_feed(choice) {
+                    Ok(outcome) => Stepped::Feed(outcome),
+                    Err(_) => return None,
+                }
+            },
+            Event::Feed { choice: None, .. } => {
+                if step::Feed(&mut state, &mut Recorded(None)).step().is_err() {
+                    return None
+                }
+                Stepped::Feed(Outcome::new())
+            },
+            Event::Skip { .. } => {
+                if step::Feed(&mut state, &mut Recorded(Some(None))).step().is_err() {
+                    return None
+                }
+                Stepped::Feed(Outcome::new())
+            },
+            Event::Bag { .. } => {
+                if step::Bag(&mut state).step().is_err() {
+                    return None
+                }
+                Stepped::Bag
+            },
+            Event::Start { .. } | Event::Attack { .. } | Event::Eject { .. } => return None,
+        };
+        if state.events().get(i) != Some(event) {
+            return None
+        }
+        let mut checked = check_state(&state, &deck);
+        if checked.is_ok() {
+            checked = check_step(&state, &before, &stepped);
+        }
+        if let Err(broken) = checked {
+            return Some((state.events().to_vec(), broken))
+        }
+    }
+    None
+}
+
+/// A recorded response to a choice, `None` when the player failed to give a
+/// valid choice.
+#[derive(Debug)]
+struct Recorded<C: Choice>(Option<Option<C>>);
+
+impl<O: Observation, C: Choice> Choose<O, C> for Recorded<C> {
+    fn choose(&mut self, _: &O) -> Result<Option<C>, Error> {
+        self.0.clone().ok_or(Error::Choice(ChoiceError::Missing))
+    }
+}
+
+fn games() -> u64 {
+    env::var("EVOLUTION_FUZZ_GAMES").ok().and_then(|n| n.parse().ok()).unwrap_or(200)
+}
+
+fn fuzz_games(rules: RulesConfig) {
+    for seed in 0..games() {
+        let players = rules.min_players + (seed as usize % (rules.max_players - rules.min_players + 1));
+        if let Err((events, broken)) = fuzz(players, seed, rules.clone()) {
+            minimize(players, seed, rules, events, broken);
+        }
+    }
+}
+
+#[test]
+fn course_rules() {
+    fuzz_games(RulesConfig::default());
+}
+
+#[test]
+fn official_rules() {
+    fuzz_games(RulesConfig::official());
+}
+
+#[test]
+fn expansion_rules() {
+    fuzz_games(RulesConfig {
+        expansion_cards: EXPANSION_CARD_BOUNDS,
+        ..RulesConfig::default()
+    });
+}
@@ -39,7 +39,7 @@ fn one_action_response() {
     }
 
     let mut game = Game::<Channel>::new(channels).expect("invalid game");
-    game.play();
+    game.play().unwrap();
 
     // All players cheated (disconnected early).
     assert_eq!(0, game.players().len());
@@ -88,7 +88,7 @@ fn two_action_responses() {
 
     debug!("starting game");
     let mut game = Game::<Channel>::new(channels).expect("invalid game");
-    game.play();
+    game.play().unwrap();
 
     // All players cheated (disconnected early).
     assert_eq!(0, game.players().len());
@@ -153,7 +153,7 @@ fn play_one_round() {
     }
 
     let mut game = Game::<Channel>::new(channels).expect("invalid game");
-    game.play();
+    game.play().unwrap();
 
     assert_eq!(0, game.players().len());
 
@@ -238,7 +238,9 @@ fn game() {
     }
 
     let mut game = Game::<Channel>::new(channels).expect("invalid game");
-    game.play();
+    // The expected scores below are for an unshuffled deck.
+    game.deck_mut().sort();
+    game.play().unwrap();
 
     assert_eq!(4, game.players().len());
     for player in game.players() {
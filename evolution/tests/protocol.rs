@@ -24,6 +24,8 @@ macro_rules! test_action_choose {
 
             // Make a game, used just for the state.
             let mut $game = Game::<Silly>::new(3).unwrap();
+            // The expected choice below is for an unshuffled deck.
+            $game.deck_mut().sort();
             $game.step_deal().expect("failed to deal");
 
             $server_pre;